@@ -0,0 +1,34 @@
+/**
+ * Keep-alive benchmark
+ * 
+ * Sends the same run of sequential requests to a server with and without
+ * keep-alive and prints the throughput of each.
+ * 
+ *   funee examples/keep-alive-bench.ts
+ */
+
+import { log, serve } from "funee";
+
+const REQUESTS = 2000;
+
+const run = async (keepAlive: boolean) => {
+  const server = serve({ port: 0, keepAlive }, () => new Response("ok"));
+  const url = `http://127.0.0.1:${server.port}/`;
+  
+  // Warm up the client before timing
+  await (await fetch(url)).text();
+  
+  const start = Date.now();
+  for (let i = 0; i < REQUESTS; i++) {
+    await (await fetch(url)).text();
+  }
+  const elapsed = Date.now() - start;
+  await server.shutdown();
+  
+  log(`keepAlive: ${keepAlive}  ${REQUESTS} requests in ${elapsed}ms (${(REQUESTS / elapsed * 1000).toFixed(0)} req/s)`);
+};
+
+export default async () => {
+  await run(false);
+  await run(true);
+};
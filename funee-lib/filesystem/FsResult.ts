@@ -43,7 +43,26 @@ export const unwrap = <T>(result: FsResult<T>): T => {
 };
 
 /**
- * Stats returned by lstat
+ * Entries returned by readdirTyped
+ */
+export interface DirEntry {
+  name: string;
+  is_file: boolean;
+  is_directory: boolean;
+  is_symlink: boolean;
+}
+
+/**
+ * Entries returned by walk
+ */
+export interface WalkEntry {
+  path: string;
+  is_file: boolean;
+  is_directory: boolean;
+}
+
+/**
+ * Stats returned by lstat and stat
  */
 export interface FileStats {
   size: number;
@@ -53,4 +72,7 @@ export interface FileStats {
   modified_ms: number | null;
   created_ms: number | null;
   accessed_ms: number | null;
+  mode: number | null;
+  uid: number | null;
+  gid: number | null;
 }
@@ -0,0 +1,61 @@
+/**
+ * appendFile - Append to a file without reading it first
+ */
+
+import {
+  appendFile as hostAppendFile,
+  appendFileBinary as hostAppendFileBinary,
+} from "host://fs";
+import type { FilePathString, FsResult } from "./index.ts";
+import { parseResult, unwrap } from "./FsResult.ts";
+import { base64Encode } from "./readFileBinary.ts";
+
+/**
+ * Append text to a file (raw result with error handling)
+ * 
+ * The file is created if it doesn't exist.
+ * 
+ * @example
+ * ```typescript
+ * const result = appendFileRaw("/var/log/app.log" as FilePathString, "started\n");
+ * if (result.type === "error") {
+ *   console.error(result.error);
+ * }
+ * ```
+ */
+export const appendFileRaw = (path: FilePathString, content: string): FsResult<void> => {
+  return parseResult<void>(hostAppendFile(path, content));
+};
+
+/**
+ * Append text to a file (throws on error)
+ * 
+ * @example
+ * ```typescript
+ * appendFile("/var/log/app.log" as FilePathString, "started\n");
+ * ```
+ */
+export const appendFile = (path: FilePathString, content: string): void => {
+  unwrap(appendFileRaw(path, content));
+};
+
+/**
+ * Append binary data to a file (raw result with error handling)
+ * 
+ * The file is created if it doesn't exist.
+ */
+export const appendFileBinaryRaw = (path: FilePathString, data: Uint8Array): FsResult<void> => {
+  return parseResult<void>(hostAppendFileBinary(path, base64Encode(data)));
+};
+
+/**
+ * Append binary data to a file (throws on error)
+ * 
+ * @example
+ * ```typescript
+ * appendFileBinary("/data/frames.bin" as FilePathString, new Uint8Array([1, 2, 3]));
+ * ```
+ */
+export const appendFileBinary = (path: FilePathString, data: Uint8Array): void => {
+  unwrap(appendFileBinaryRaw(path, data));
+};
@@ -0,0 +1,40 @@
+/**
+ * chmod - Change file permissions
+ */
+
+import { chmod as hostChmod } from "host://fs";
+import { PathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult } from "./FsResult.ts";
+
+/**
+ * Set a path's permission bits (returns result object).
+ * 
+ * Unix only: on other platforms this returns an error result.
+ * 
+ * @param path - Path to change (symlinks are followed)
+ * @param mode - Permission bits, written in octal like 0o755
+ * @returns Result object with null value or error
+ */
+export const chmodRaw = (path: PathString, mode: number): FsResult<null> => {
+  const json = hostChmod(path, mode);
+  return parseResult(json) as FsResult<null>;
+};
+
+/**
+ * Set a path's permission bits.
+ * 
+ * @param path - Path to change (symlinks are followed)
+ * @param mode - Permission bits, written in octal like 0o755
+ * @throws Error if the path doesn't exist, can't be changed, or the platform has no file modes
+ * 
+ * @example
+ * ```typescript
+ * import { chmod, writeFile } from "funee";
+ * 
+ * writeFile("./deploy.sh" as FilePathString, script);
+ * chmod("./deploy.sh" as PathString, 0o755);
+ * ```
+ */
+export const chmod = (path: PathString, mode: number): void => {
+  unwrap(chmodRaw(path, mode));
+};
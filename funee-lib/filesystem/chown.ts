@@ -0,0 +1,43 @@
+/**
+ * chown - Change file ownership
+ */
+
+import { chown as hostChown } from "host://fs";
+import { PathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult } from "./FsResult.ts";
+
+/**
+ * Set a path's owning user and group (returns result object).
+ * 
+ * Usually requires root. Unix only: on other platforms this returns an error result.
+ * 
+ * @param path - Path to change (symlinks are followed)
+ * @param uid - New owning user id
+ * @param gid - New owning group id
+ * @returns Result object with null value or error
+ */
+export const chownRaw = (path: PathString, uid: number, gid: number): FsResult<null> => {
+  const json = hostChown(path, uid, gid);
+  return parseResult(json) as FsResult<null>;
+};
+
+/**
+ * Set a path's owning user and group.
+ * 
+ * @param path - Path to change (symlinks are followed)
+ * @param uid - New owning user id
+ * @param gid - New owning group id
+ * @throws Error if the path doesn't exist, permission is denied, or the platform has no file ownership
+ * 
+ * @example
+ * ```typescript
+ * import { chown, stat } from "funee";
+ * 
+ * // Hand a generated config to the service account that owns its directory
+ * const { uid, gid } = stat("/srv/app" as PathString);
+ * chown("/srv/app/config.json" as PathString, uid!, gid!);
+ * ```
+ */
+export const chown = (path: PathString, uid: number, gid: number): void => {
+  unwrap(chownRaw(path, uid, gid));
+};
@@ -0,0 +1,41 @@
+/**
+ * copyFile - Copy a file on the host
+ */
+
+import { copyFile as hostCopyFile } from "host://fs";
+import { FilePathString, PathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult } from "./FsResult.ts";
+
+/**
+ * Copy a file (returns result object).
+ * 
+ * The contents never pass through JavaScript, and the source's permission
+ * bits are copied too. An existing destination is overwritten.
+ * 
+ * @param source - File to copy
+ * @param dest - Path of the copy
+ * @returns Result object with the number of bytes copied, or an error
+ */
+export const copyFileRaw = (source: PathString, dest: PathString): FsResult<number> => {
+  const json = hostCopyFile(source, dest);
+  return parseResult(json) as FsResult<number>;
+};
+
+/**
+ * Copy a file.
+ * 
+ * @param source - File to copy
+ * @param dest - Path of the copy
+ * @returns Number of bytes copied
+ * @throws Error if the source doesn't exist or the destination directory is missing
+ * 
+ * @example
+ * ```typescript
+ * import { copyFile } from "funee";
+ * 
+ * copyFile("/data/big.bin" as FilePathString, "/backup/big.bin" as FilePathString);
+ * ```
+ */
+export const copyFile = (source: FilePathString, dest: FilePathString): number => {
+  return unwrap(copyFileRaw(source, dest));
+};
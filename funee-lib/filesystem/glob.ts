@@ -0,0 +1,47 @@
+/**
+ * glob - Expand a glob pattern
+ */
+
+import { glob as hostGlob } from "host://fs";
+import { PathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult } from "./FsResult.ts";
+
+/**
+ * Expand a glob pattern (returns result object).
+ * 
+ * Use this when you want to handle errors yourself instead of throwing.
+ * 
+ * @param pattern - Glob pattern, relative to the working directory unless absolute
+ * @param includeHidden - Let wildcards match dotfiles (default false)
+ * @returns Result object with array of matching paths or error
+ */
+export const globRaw = (pattern: string, includeHidden?: boolean): FsResult<PathString[]> => {
+  const json = hostGlob(pattern, includeHidden);
+  return parseResult(json) as FsResult<PathString[]>;
+};
+
+/**
+ * Expand a glob pattern.
+ * 
+ * Supports `*`, `?`, `[...]` character classes and `**` for any number of
+ * directories. Matches are returned in sorted order. Files and directories
+ * starting with `.` are only matched by a literal `.` in the pattern unless
+ * `includeHidden` is true.
+ * 
+ * @param pattern - Glob pattern, relative to the working directory unless absolute
+ * @param includeHidden - Let wildcards match dotfiles (default false)
+ * @returns Matching paths
+ * @throws Error if the pattern is invalid
+ * 
+ * @example
+ * ```typescript
+ * import { glob, log } from "funee";
+ * 
+ * for (const path of glob("src/**\/*.ts")) {
+ *   log(path);
+ * }
+ * ```
+ */
+export const glob = (pattern: string, includeHidden?: boolean): PathString[] => {
+  return unwrap(globRaw(pattern, includeHidden));
+};
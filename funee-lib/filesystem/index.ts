@@ -54,6 +54,8 @@ export type {
   FsResultOk,
   FsResultErr,
   FileStats,
+  DirEntry,
+  WalkEntry,
 } from "./FsResult.ts";
 
 export { parseResult, unwrap } from "./FsResult.ts";
@@ -63,8 +65,24 @@ export { readFile, readFileRaw } from "./readFile.ts";
 export { readFileBinary, readFileBinaryRaw, base64Encode, base64Decode } from "./readFileBinary.ts";
 export { writeFile, writeFileRaw } from "./writeFile.ts";
 export { writeFileBinary, writeFileBinaryRaw } from "./writeFileBinary.ts";
+export { appendFile, appendFileRaw, appendFileBinary, appendFileBinaryRaw } from "./appendFile.ts";
+export type { FileToWrite } from "./writeFiles.ts";
+export { writeFiles, writeFilesRaw } from "./writeFiles.ts";
+export { copyFile, copyFileRaw } from "./copyFile.ts";
+export { rename, renameRaw } from "./rename.ts";
+export { realpath, realpathRaw } from "./realpath.ts";
+export { remove, removeRaw } from "./remove.ts";
 export { isFile } from "./isFile.ts";
 export { lstat, lstatRaw } from "./lstat.ts";
+export { stat, statRaw } from "./stat.ts";
+export { chmod, chmodRaw } from "./chmod.ts";
+export { chown, chownRaw } from "./chown.ts";
+export { truncate, truncateRaw } from "./truncate.ts";
+export { utimes, utimesRaw } from "./utimes.ts";
+export { mkdtemp, mkdtempRaw } from "./mkdtemp.ts";
+export { readdirTyped, readdirTypedRaw } from "./readdirTyped.ts";
+export { walk, walkRaw } from "./walk.ts";
+export { glob, globRaw } from "./glob.ts";
 export { readdir, readdirRaw } from "./readdir.ts";
 
 // Temporary directory with async disposal
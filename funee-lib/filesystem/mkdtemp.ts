@@ -0,0 +1,41 @@
+/**
+ * mkdtemp - Create a uniquely named temporary directory
+ */
+
+import { mkdtemp as hostMkdtemp } from "host://fs";
+import { FolderPathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult } from "./FsResult.ts";
+
+/**
+ * Create a new directory in the system temp directory (returns result object).
+ * 
+ * @param prefix - Start of the directory name; random characters are appended
+ * @returns Result object with the new directory's path or error
+ */
+export const mkdtempRaw = (prefix: string = ""): FsResult<FolderPathString> => {
+  const json = hostMkdtemp(prefix);
+  return parseResult(json) as FsResult<FolderPathString>;
+};
+
+/**
+ * Create a new directory in the system temp directory.
+ * 
+ * The directory exists when this returns and is never one that existed
+ * before, so concurrent callers can't collide (unlike building a random
+ * name and then calling mkdir).
+ * 
+ * @param prefix - Start of the directory name; random characters are appended
+ * @returns Absolute path of the new directory
+ * @throws Error if the directory can't be created or the prefix contains a path separator
+ * 
+ * @example
+ * ```typescript
+ * import { mkdtemp } from "funee";
+ * 
+ * const workDir = mkdtemp("build-");
+ * // => "/tmp/build-a1b2c3d4e5f6"
+ * ```
+ */
+export const mkdtemp = (prefix: string = ""): FolderPathString => {
+  return unwrap(mkdtempRaw(prefix));
+};
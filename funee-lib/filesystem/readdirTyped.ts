@@ -0,0 +1,46 @@
+/**
+ * readdirTyped - List directory contents with entry types
+ */
+
+import { readdirTyped as hostReaddirTyped } from "host://fs";
+import { FolderPathString, PathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult, DirEntry } from "./FsResult.ts";
+
+/**
+ * List directory contents with entry types (returns result object).
+ * 
+ * Use this when you want to handle errors yourself instead of throwing.
+ * 
+ * @param path - Path to the directory
+ * @returns Result object with array of entries or error
+ */
+export const readdirTypedRaw = (path: PathString): FsResult<DirEntry[]> => {
+  const json = hostReaddirTyped(path);
+  return parseResult(json) as FsResult<DirEntry[]>;
+};
+
+/**
+ * List directory contents with entry types.
+ * 
+ * Like readdir, but each entry says whether it is a file, directory or
+ * symlink, so callers don't need an lstat per entry. Symlinks are not
+ * followed. An entry whose type can't be determined has all flags false.
+ * 
+ * @param path - Path to the directory
+ * @returns Array of entries in the directory
+ * @throws Error if the directory does not exist or cannot be read
+ * 
+ * @example
+ * ```typescript
+ * import { readdirTyped, log } from "funee";
+ * 
+ * for (const entry of readdirTyped("./src" as FolderPathString)) {
+ *   if (entry.is_directory) {
+ *     log(`${entry.name}/`);
+ *   }
+ * }
+ * ```
+ */
+export const readdirTyped = (path: FolderPathString): DirEntry[] => {
+  return unwrap(readdirTypedRaw(path));
+};
@@ -0,0 +1,38 @@
+/**
+ * realpath - Canonicalize a path
+ */
+
+import { realpath as hostRealpath } from "host://fs";
+import { AbsolutePathString, PathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult } from "./FsResult.ts";
+
+/**
+ * Resolve a path to its canonical absolute form (returns result object).
+ * 
+ * Symlinks, `.` and `..` are resolved. Fails if the path does not exist.
+ * 
+ * @param path - Path to resolve, relative to the working directory or absolute
+ * @returns Result object with the absolute path or error
+ */
+export const realpathRaw = (path: PathString): FsResult<AbsolutePathString> => {
+  const json = hostRealpath(path);
+  return parseResult(json) as FsResult<AbsolutePathString>;
+};
+
+/**
+ * Resolve a path to its canonical absolute form.
+ * 
+ * @param path - Path to resolve, relative to the working directory or absolute
+ * @returns The absolute, symlink-free path
+ * @throws Error if the path does not exist
+ * 
+ * @example
+ * ```typescript
+ * import { realpath } from "funee";
+ * 
+ * const root = realpath("./src/../" as PathString);
+ * ```
+ */
+export const realpath = (path: PathString): AbsolutePathString => {
+  return unwrap(realpathRaw(path));
+};
@@ -0,0 +1,41 @@
+/**
+ * remove - Delete a file or directory
+ */
+
+import { remove as hostRemove } from "host://fs";
+import { PathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult } from "./FsResult.ts";
+
+/**
+ * Remove a file or directory (returns result object).
+ * 
+ * Without `recursive`, directories must be empty. Symlinks are removed
+ * themselves, never followed.
+ * 
+ * @param path - Path to remove
+ * @param recursive - Also remove a directory's contents
+ * @returns Result object with null value or error
+ */
+export const removeRaw = (path: PathString, recursive = false): FsResult<null> => {
+  const json = hostRemove(path, recursive);
+  return parseResult(json) as FsResult<null>;
+};
+
+/**
+ * Remove a file or directory.
+ * 
+ * @param path - Path to remove
+ * @param recursive - Also remove a directory's contents
+ * @throws Error if the path doesn't exist, or is a non-empty directory without `recursive`
+ * 
+ * @example
+ * ```typescript
+ * import { remove } from "funee";
+ * 
+ * remove("/tmp/build/output.log" as PathString);
+ * remove("/tmp/build" as PathString, true);
+ * ```
+ */
+export const remove = (path: PathString, recursive = false): void => {
+  unwrap(removeRaw(path, recursive));
+};
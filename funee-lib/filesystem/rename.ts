@@ -0,0 +1,43 @@
+/**
+ * rename - Move or rename a file or directory
+ */
+
+import { rename as hostRename } from "host://fs";
+import { PathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult } from "./FsResult.ts";
+
+/**
+ * Rename or move a path (returns result object).
+ * 
+ * Atomic when both paths are on the same filesystem. Moving across
+ * filesystems fails with an error mentioning EXDEV; fall back to
+ * copyFile followed by removing the original.
+ * 
+ * @param from - Existing path
+ * @param to - New path (an existing file there is replaced)
+ * @returns Result object with null value or error
+ */
+export const renameRaw = (from: PathString, to: PathString): FsResult<null> => {
+  const json = hostRename(from, to);
+  return parseResult(json) as FsResult<null>;
+};
+
+/**
+ * Rename or move a path.
+ * 
+ * @param from - Existing path
+ * @param to - New path (an existing file there is replaced)
+ * @throws Error if the path can't be moved
+ * 
+ * @example
+ * ```typescript
+ * import { rename } from "funee";
+ * 
+ * // Write to a temp name, then move it into place atomically
+ * writeFile("/data/out.json.tmp" as FilePathString, json);
+ * rename("/data/out.json.tmp" as PathString, "/data/out.json" as PathString);
+ * ```
+ */
+export const rename = (from: PathString, to: PathString): void => {
+  unwrap(renameRaw(from, to));
+};
@@ -0,0 +1,42 @@
+/**
+ * stat - Get file stats, following symlinks
+ */
+
+import { stat as hostStat } from "host://fs";
+import { PathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult, FileStats } from "./FsResult.ts";
+
+/**
+ * Get file stats, following symlinks (returns result object).
+ * 
+ * Use this when you want to handle errors yourself instead of throwing.
+ * 
+ * @param path - Path to get stats for
+ * @returns Result object with stats or error
+ */
+export const statRaw = (path: PathString): FsResult<FileStats> => {
+  const json = hostStat(path);
+  return parseResult(json) as FsResult<FileStats>;
+};
+
+/**
+ * Get file stats, following symlinks.
+ * 
+ * This is similar to Node.js fs.stat - a symlink reports its target's size
+ * and type, so is_symlink is always false. Use lstat to detect links.
+ * 
+ * @param path - Path to get stats for
+ * @returns File stats object
+ * @throws Error if the path (or the link's target) does not exist or cannot be accessed
+ * 
+ * @example
+ * ```typescript
+ * import { stat } from "funee";
+ * 
+ * const stats = stat("/usr/bin/python3");
+ * log(`Target size: ${stats.size} bytes`);
+ * ```
+ */
+export const stat = (path: PathString): FileStats => {
+  return unwrap(statRaw(path));
+};
@@ -17,9 +17,8 @@
  * ```
  */
 
-import { mkdir } from "host://fs";
-import { spawn } from "host://process";
-import { someDirectory } from "../abstracts/index.ts";
+import { exists, remove } from "host://fs";
+import { mkdtemp } from "./mkdtemp.ts";
 import type { FolderPathString } from "./PathString.ts";
 
 /**
@@ -57,20 +56,18 @@ export type TempDir = {
  * ```
  */
 export const tempDir = (): TempDir => {
-  const path = someDirectory();
-  
-  // Create the directory
-  mkdir(path);
+  // Created under a fresh name, so two handles never share a directory
+  const path = mkdtemp("funee_");
   
   return {
     get path() {
       return path;
     },
     [Symbol.asyncDispose]: async () => {
-      // Use spawn to run rm -rf for reliable recursive deletion
-      const result = await spawn("rm", ["-rf", path]);
-      if (result.status.code !== 0) {
-        throw new Error(`Failed to delete temp directory ${path}: exit code ${result.status.code}`);
+      const result = JSON.parse(remove(path, true));
+      // Already gone is fine
+      if (result.type === "error" && exists(path)) {
+        throw new Error(`Failed to delete temp directory ${path}: ${result.error}`);
       }
     },
   };
@@ -0,0 +1,43 @@
+/**
+ * truncate - Resize a file
+ */
+
+import { truncate as hostTruncate } from "host://fs";
+import { FilePathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult } from "./FsResult.ts";
+
+/**
+ * Resize a file (returns result object).
+ * 
+ * Use this when you want to handle errors yourself instead of throwing.
+ * 
+ * @param path - Path to an existing file
+ * @param len - New length in bytes (default 0)
+ * @returns Result object with null value or error
+ */
+export const truncateRaw = (path: FilePathString, len?: number): FsResult<null> => {
+  const json = hostTruncate(path, len);
+  return parseResult(json) as FsResult<null>;
+};
+
+/**
+ * Resize a file.
+ * 
+ * Shrinking drops everything past `len`; growing pads the file with zero
+ * bytes. The file is never created.
+ * 
+ * @param path - Path to an existing file
+ * @param len - New length in bytes (default 0)
+ * @throws Error if the file doesn't exist or can't be written
+ * 
+ * @example
+ * ```typescript
+ * import { truncate } from "funee";
+ * 
+ * // Empty a log file in place
+ * truncate("./app.log" as FilePathString);
+ * ```
+ */
+export const truncate = (path: FilePathString, len?: number): void => {
+  unwrap(truncateRaw(path, len));
+};
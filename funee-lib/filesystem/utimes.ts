@@ -0,0 +1,47 @@
+/**
+ * utimes - Set a file's access and modification times
+ */
+
+import { utimes as hostUtimes } from "host://fs";
+import { FilePathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult } from "./FsResult.ts";
+
+/**
+ * Set a file's access and modification times (returns result object).
+ * 
+ * Use this when you want to handle errors yourself instead of throwing.
+ * 
+ * @param path - Path to the file
+ * @param atimeMs - Access time in milliseconds since the epoch
+ * @param mtimeMs - Modification time in milliseconds since the epoch
+ * @returns Result object with null value or error
+ */
+export const utimesRaw = (path: FilePathString, atimeMs: number, mtimeMs: number): FsResult<null> => {
+  const json = hostUtimes(path, atimeMs, mtimeMs);
+  return parseResult(json) as FsResult<null>;
+};
+
+/**
+ * Set a file's access and modification times.
+ * 
+ * Times use the same unit as `accessed_ms`/`modified_ms` in FileStats, so
+ * they can be copied straight from another file's stat. Both must be
+ * positive.
+ * 
+ * @param path - Path to the file
+ * @param atimeMs - Access time in milliseconds since the epoch
+ * @param mtimeMs - Modification time in milliseconds since the epoch
+ * @throws Error if a time is not positive or the file doesn't exist
+ * 
+ * @example
+ * ```typescript
+ * import { stat, utimes } from "funee";
+ * 
+ * // Preserve timestamps when copying
+ * const { accessed_ms, modified_ms } = stat(src);
+ * utimes(dest, accessed_ms!, modified_ms!);
+ * ```
+ */
+export const utimes = (path: FilePathString, atimeMs: number, mtimeMs: number): void => {
+  unwrap(utimesRaw(path, atimeMs, mtimeMs));
+};
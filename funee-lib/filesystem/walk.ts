@@ -0,0 +1,49 @@
+/**
+ * walk - Recursively list a directory tree
+ */
+
+import { walk as hostWalk } from "host://fs";
+import { FolderPathString, PathString } from "./PathString.ts";
+import { parseResult, unwrap, FsResult, WalkEntry } from "./FsResult.ts";
+
+/**
+ * Recursively list a directory tree (returns result object).
+ * 
+ * Use this when you want to handle errors yourself instead of throwing.
+ * 
+ * @param root - Directory to walk
+ * @param maxDepth - Levels to descend (1 = direct children only); 0 or omitted means unlimited
+ * @returns Result object with array of entries or error
+ */
+export const walkRaw = (root: PathString, maxDepth?: number): FsResult<WalkEntry[]> => {
+  const json = hostWalk(root, maxDepth);
+  return parseResult(json) as FsResult<WalkEntry[]>;
+};
+
+/**
+ * Recursively list a directory tree.
+ * 
+ * The whole tree is walked by the host in one call. Paths are relative to
+ * `root` and `/`-separated, with each directory's entries sorted by name
+ * and listed before descending. Symlinks are listed but never followed, so
+ * link cycles are harmless; subdirectories that can't be read are skipped.
+ * 
+ * @param root - Directory to walk
+ * @param maxDepth - Levels to descend (1 = direct children only); 0 or omitted means unlimited
+ * @returns Entries under `root`
+ * @throws Error if `root` does not exist or cannot be read
+ * 
+ * @example
+ * ```typescript
+ * import { walk, log } from "funee";
+ * 
+ * for (const entry of walk("./src" as FolderPathString)) {
+ *   if (entry.is_file && entry.path.endsWith(".ts")) {
+ *     log(entry.path);
+ *   }
+ * }
+ * ```
+ */
+export const walk = (root: FolderPathString, maxDepth?: number): WalkEntry[] => {
+  return unwrap(walkRaw(root, maxDepth));
+};
@@ -0,0 +1,56 @@
+/**
+ * Write several files as one all-or-nothing transaction
+ */
+
+import { writeFiles as hostWriteFiles } from "host://fs";
+import type { FilePathString, FsResult } from "./index.ts";
+import { parseResult, unwrap } from "./FsResult.ts";
+import { base64Encode } from "./readFileBinary.ts";
+
+/**
+ * A file to write with writeFiles - text is written as UTF-8
+ */
+export interface FileToWrite {
+  path: FilePathString;
+  content: string | Uint8Array;
+}
+
+/**
+ * Write several files as a transaction (raw result with error handling)
+ *
+ * Either every file is written or none are; the error names the file that failed.
+ *
+ * @example
+ * ```typescript
+ * const result = writeFilesRaw([
+ *   { path: "/out/a.ts" as FilePathString, content: "export const a = 1;" },
+ *   { path: "/out/b.ts" as FilePathString, content: "export const b = 2;" },
+ * ]);
+ * if (result.type === "error") {
+ *   console.error(result.error);
+ * }
+ * ```
+ */
+export const writeFilesRaw = (files: FileToWrite[]): FsResult<void> => {
+  const encoder = new TextEncoder();
+  const entries = files.map(({ path, content }) => ({
+    path,
+    content_base64: base64Encode(typeof content === "string" ? encoder.encode(content) : content),
+  }));
+  return parseResult<void>(hostWriteFiles(entries));
+};
+
+/**
+ * Write several files as a transaction (throws on error)
+ *
+ * @example
+ * ```typescript
+ * writeFiles([
+ *   { path: "/out/a.ts" as FilePathString, content: "export const a = 1;" },
+ *   { path: "/out/b.ts" as FilePathString, content: "export const b = 2;" },
+ * ]);
+ * ```
+ */
+export const writeFiles = (files: FileToWrite[]): void => {
+  unwrap(writeFilesRaw(files));
+};
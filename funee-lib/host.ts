@@ -33,6 +33,7 @@ export declare function fsMkdir(path: string): string;
 export declare function tmpdir(): string;
 
 // Watcher host functions
-export declare function watchStart(path: string, recursive: boolean): string;
-export declare function watchPoll(watcherId: number): string;
+export declare function watchStart(path: string, recursive: boolean, maxQueue?: number): string;
+export declare function watchPoll(watcherId: number, cursor?: number): string;
+export declare function watchNext(watcherId: number, cursor?: number): Promise<string>;
 export declare function watchStop(watcherId: number): void;
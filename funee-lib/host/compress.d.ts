@@ -0,0 +1,36 @@
+/**
+ * Host Compress Module
+ * 
+ * Provides gzip compression.
+ * Import from "host://compress"
+ */
+
+/**
+ * Compress data into the gzip format
+ * 
+ * @param data - Bytes to compress; strings are UTF-8 encoded first
+ * @param level - Compression level from 0 (none) to 9 (smallest), clamped (default: 6)
+ * @returns The gzip bytes
+ * 
+ * @example
+ * ```typescript
+ * import { gzip, gunzip } from "host://compress";
+ * 
+ * const compressed = gzip(JSON.stringify(data), 9);
+ * const restored = gunzip(compressed);
+ * ```
+ */
+export declare function gzip(data: Uint8Array | string, level?: number): Uint8Array;
+
+/**
+ * Decompress gzip data
+ * 
+ * Concatenated gzip members are decompressed in sequence.
+ * 
+ * @param data - The gzip bytes
+ * @param options.maxBytes - Largest decompressed size accepted (default: 256 MiB)
+ * @returns The decompressed bytes
+ * @throws TypeError if the data isn't valid gzip
+ * @throws RangeError as soon as the output grows past maxBytes
+ */
+export declare function gunzip(data: Uint8Array, options?: { maxBytes?: number }): Uint8Array;
@@ -8,8 +8,9 @@
 /**
  * Generate cryptographically secure random bytes
  * 
- * @param length - Number of random bytes to generate
+ * @param length - Number of random bytes to generate, at most 65536
  * @returns Uint8Array of random bytes
+ * @throws RangeError if more than 65536 bytes are requested
  * 
  * @example
  * ```typescript
@@ -20,3 +21,120 @@
  * ```
  */
 export declare function randomBytes(length: number): Uint8Array;
+
+/**
+ * Generate a random (version 4) UUID, as in the Web Crypto API
+ * 
+ * @returns Lowercase UUID string, e.g. "3b241101-e2bb-4255-8caf-4136c566a962"
+ * 
+ * @example
+ * ```typescript
+ * import { randomUUID } from "host://crypto";
+ * 
+ * const id = randomUUID();
+ * ```
+ */
+export declare function randomUUID(): string;
+
+/**
+ * Derive a key from a password with PBKDF2-HMAC
+ * 
+ * @param password - The password
+ * @param salt - Salt bytes, or a string (UTF-8 encoded)
+ * @param iterations - Iteration count (at least 1)
+ * @param keyLength - Length of the derived key in bytes
+ * @param digest - "sha1", "sha256" (default), "sha384" or "sha512"
+ * @returns The derived key
+ * 
+ * @example
+ * ```typescript
+ * import { pbkdf2, randomBytes } from "host://crypto";
+ * 
+ * const salt = randomBytes(16);
+ * const key = pbkdf2("hunter2", salt, 600000, 32, "sha256");
+ * ```
+ */
+export declare function pbkdf2(
+  password: string,
+  salt: Uint8Array | string,
+  iterations: number,
+  keyLength: number,
+  digest?: "sha1" | "sha256" | "sha384" | "sha512"
+): Uint8Array;
+
+/**
+ * Hash a password with Argon2id, using a random salt
+ * 
+ * @param password - The password
+ * @returns PHC-formatted hash string ("$argon2id$v=19$...") to store
+ * 
+ * @example
+ * ```typescript
+ * import { argon2Hash, argon2Verify } from "host://crypto";
+ * 
+ * const stored = argon2Hash("hunter2");
+ * argon2Verify("hunter2", stored); // true
+ * ```
+ */
+export declare function argon2Hash(password: string): string;
+
+/**
+ * Check a password against a hash from argon2Hash()
+ * 
+ * @param password - The password to check
+ * @param hash - PHC-formatted Argon2 hash
+ * @returns true if the password matches; throws if the hash is malformed
+ */
+export declare function argon2Verify(password: string, hash: string): boolean;
+
+/**
+ * Hash data and return the hex digest
+ * 
+ * @param algorithm - "sha256", "sha512", "sha384", "sha1" or "md5" (case-insensitive)
+ * @param data - Bytes to hash, or a string (UTF-8 encoded)
+ * @returns Lowercase hex digest; throws a TypeError for an unknown algorithm
+ * 
+ * @example
+ * ```typescript
+ * import { digest } from "host://crypto";
+ * 
+ * digest("sha256", "hello"); // "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+ * ```
+ */
+export declare function digest(
+  algorithm: "sha256" | "sha512" | "sha384" | "sha1" | "md5" | (string & {}),
+  data: Uint8Array | string
+): string;
+
+/**
+ * Sign data with HMAC and return the hex MAC
+ * 
+ * @param algorithm - "sha256", "sha512", "sha384" or "sha1" (case-insensitive)
+ * @param key - Secret key bytes, or a string (UTF-8 encoded)
+ * @param data - Bytes to sign, or a string (UTF-8 encoded)
+ * @returns Lowercase hex MAC; throws a TypeError for an unknown algorithm
+ * 
+ * @example
+ * ```typescript
+ * import { hmac, timingSafeEqual } from "host://crypto";
+ * 
+ * // Verify a GitHub webhook's X-Hub-Signature-256 header
+ * const expected = hmac("sha256", secret, body);
+ * const valid = timingSafeEqual(expected, signature.replace("sha256=", ""));
+ * ```
+ */
+export declare function hmac(
+  algorithm: "sha256" | "sha512" | "sha384" | "sha1" | (string & {}),
+  key: Uint8Array | string,
+  data: Uint8Array | string
+): string;
+
+/**
+ * Compare two hex strings (such as MACs) in constant time, so checking a
+ * signature doesn't reveal how much of it was right
+ * 
+ * @param a - Hex string
+ * @param b - Hex string
+ * @returns true if they encode the same bytes; throws a TypeError on invalid hex
+ */
+export declare function timingSafeEqual(a: string, b: string): boolean;
@@ -0,0 +1,47 @@
+/**
+ * Host Env Module
+ * 
+ * Provides access to the process's environment variables.
+ * Import from "host://env"
+ */
+
+/**
+ * Read an environment variable
+ * 
+ * @returns The value (possibly ""), or undefined when the variable is unset
+ * @throws Error if the value isn't valid Unicode
+ * 
+ * @example
+ * ```typescript
+ * import { get } from "host://env";
+ * 
+ * const port = Number(get("PORT") ?? "8080");
+ * ```
+ */
+export declare function get(name: string): string | undefined;
+
+/**
+ * Set an environment variable for this script — get(), toObject() and fetch
+ * proxy settings — and subprocesses spawned afterwards. The runtime's own
+ * process environment is left as it is
+ * 
+ * @throws Error if the name is empty or contains "=" or a NUL byte, or the
+ *   value contains a NUL byte
+ */
+export declare function set(name: string, value: string): void;
+
+/**
+ * Unset an environment variable; does nothing if it isn't set.
+ * `delete` is a reserved word, so import it under another name:
+ * `import { delete as unset } from "host://env"`
+ * 
+ * @throws Error if the name is empty or contains "=" or a NUL byte
+ */
+declare function deleteVar(name: string): void;
+export { deleteVar as delete };
+
+/**
+ * All environment variables as a plain object. Names and values that aren't
+ * valid Unicode are converted lossily
+ */
+export declare function toObject(): Record<string, string>;
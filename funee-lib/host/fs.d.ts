@@ -9,7 +9,7 @@
  */
 
 /**
- * File/directory stats returned by lstat()
+ * File/directory stats returned by lstat() and stat()
  */
 export interface FileStats {
   /** Size in bytes */
@@ -20,6 +20,12 @@ export interface FileStats {
   is_directory: boolean;
   /** Last modification time as milliseconds since epoch */
   modified_ms: number;
+  /** Permission bits (e.g. 0o644) on Unix; null on other platforms */
+  mode: number | null;
+  /** Owning user id on Unix; null on other platforms */
+  uid: number | null;
+  /** Owning group id on Unix; null on other platforms */
+  gid: number | null;
 }
 
 /**
@@ -46,6 +52,47 @@ export declare function writeFile(path: string, content: string): string;
  */
 export declare function writeFileBinary(path: string, contentBase64: string): string;
 
+/**
+ * Append a UTF-8 string to a file, creating the file if it doesn't exist
+ * @returns JSON string with result format
+ */
+export declare function appendFile(path: string, content: string): string;
+
+/**
+ * Append binary data (base64 encoded) to a file, creating the file if it doesn't exist
+ * @returns JSON string with result format
+ */
+export declare function appendFileBinary(path: string, contentBase64: string): string;
+
+/**
+ * Write several files (base64 encoded) as one transaction: either all of them
+ * are written or none are
+ * @returns JSON string with result format (the error names the file that failed)
+ */
+export declare function writeFiles(files: { path: string; content_base64: string }[]): string;
+
+/**
+ * Copy a file without passing its contents through JavaScript; permission
+ * bits are copied too
+ * @returns JSON string with result format (the value is the number of bytes copied)
+ */
+export declare function copyFile(source: string, dest: string): string;
+
+/**
+ * Rename or move a file or directory, replacing an existing file at `to`.
+ * Atomic within one filesystem; moving across filesystems fails with an
+ * error mentioning EXDEV (copy and remove instead)
+ * @returns JSON string with result format
+ */
+export declare function rename(from: string, to: string): string;
+
+/**
+ * Resolve a path to its absolute form with symlinks, `.` and `..` resolved.
+ * The path must exist
+ * @returns JSON string with result format
+ */
+export declare function realpath(path: string): string;
+
 /**
  * Check if path is a regular file
  */
@@ -62,17 +109,116 @@ export declare function exists(path: string): boolean;
  */
 export declare function lstat(path: string): string;
 
+/**
+ * Get file/directory stats, following symlinks to their target
+ * (so is_symlink is always false; use lstat to detect links)
+ * @returns JSON string with result format
+ */
+export declare function stat(path: string): string;
+
+/**
+ * Set a file's permission bits, e.g. chmod(path, 0o600). Follows symlinks.
+ * Unix only; returns an error result on other platforms
+ * @returns JSON string with result format
+ */
+export declare function chmod(path: string, mode: number): string;
+
+/**
+ * Change a file's owning user and group ids. Follows symlinks.
+ * Usually requires root. Unix only; returns an error result on other platforms
+ * @returns JSON string with result format
+ */
+export declare function chown(path: string, uid: number, gid: number): string;
+
+/**
+ * Resize an existing file to `len` bytes (default 0); growing it zero-fills.
+ * Never creates the file
+ * @returns JSON string with result format
+ */
+export declare function truncate(path: string, len?: number): string;
+
+/**
+ * Set a file's access and modification times, in milliseconds since the
+ * epoch (as in FileStats). Both must be positive. Follows symlinks
+ * @returns JSON string with result format
+ */
+export declare function utimes(path: string, atimeMs: number, mtimeMs: number): string;
+
 /**
  * Create a directory
  */
 export declare function mkdir(path: string, recursive?: boolean): void;
 
+/**
+ * Remove a file or an empty directory; with `recursive`, a directory and
+ * everything in it. Symlinks are removed, not followed
+ * @returns JSON string with result format
+ */
+export declare function remove(path: string, recursive?: boolean): string;
+
+/**
+ * Create a new, uniquely named directory in the system temp directory,
+ * named `prefix` plus random characters. Never reuses an existing directory,
+ * so it's safe against name collisions and concurrent callers
+ * @returns JSON string with result format (value is the new directory's path)
+ */
+export declare function mkdtemp(prefix?: string): string;
+
 /**
  * Read directory contents
  * @returns JSON string with array of entry names
  */
 export declare function readdir(path: string): string;
 
+/**
+ * Directory entry returned by readdirTyped()
+ */
+export interface DirEntry {
+  /** Entry name (not a full path) */
+  name: string;
+  /** True if this is a regular file */
+  is_file: boolean;
+  /** True if this is a directory */
+  is_directory: boolean;
+  /** True if the entry is a symlink (is_file/is_directory then describe the link itself) */
+  is_symlink: boolean;
+}
+
+/**
+ * Read directory contents with each entry's type, without a stat per entry.
+ * Entries whose type can't be determined have all three flags false
+ * @returns JSON string with array of DirEntry objects
+ */
+export declare function readdirTyped(path: string): string;
+
+/**
+ * Entry returned by walk()
+ */
+export interface WalkEntry {
+  /** Path relative to the walk root, `/`-separated */
+  path: string;
+  /** True if this is a regular file */
+  is_file: boolean;
+  /** True if this is a directory */
+  is_directory: boolean;
+}
+
+/**
+ * Recursively list a directory tree in a single call.
+ * Symlinks are listed but not followed; unreadable subdirectories are skipped
+ * @param maxDepth - Levels to descend (1 = direct children only); 0 or omitted means unlimited
+ * @returns JSON string with array of WalkEntry objects
+ */
+export declare function walk(root: string, maxDepth?: number): string;
+
+/**
+ * Expand a glob pattern (e.g. `src/**\/*.ts`); relative patterns are matched
+ * against the working directory
+ * @param includeHidden - Let wildcards match dotfiles (default false)
+ * @returns JSON string with array of matching paths, or an error for an invalid pattern
+ */
+export declare function glob(pattern: string, includeHidden?: boolean): string;
+
 /**
  * Get the system temporary directory path
  */
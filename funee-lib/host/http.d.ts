@@ -10,6 +10,10 @@
  * 
  * This is the web-standard fetch() function.
  * 
+ * `init.body` may also be an async iterable (e.g. an async generator) or a
+ * ReadableStream-like object yielding strings or bytes; it is uploaded with
+ * chunked transfer encoding as it is produced, without buffering.
+ * 
  * @param input - URL string, URL object, or Request object
  * @param init - Optional request configuration
  * @returns Promise resolving to the Response
@@ -11,10 +11,14 @@
  * import { fetch } from "host://http";
  * import { serve } from "host://server";
  * import { spawn } from "host://process";
+ * import { platform } from "host://os";
+ * import { get } from "host://env";
  * import { setTimeout } from "host://time";
  * import { watchFile } from "host://watch";
  * import { randomBytes } from "host://crypto";
+ * import { gzip, gunzip } from "host://compress";
  * import { log } from "host://console";
+ * import { test, assertEqual } from "host://test";
  * ```
  */
 
@@ -22,17 +26,25 @@
 /// <reference path="./http.d.ts" />
 /// <reference path="./server.d.ts" />
 /// <reference path="./process.d.ts" />
+/// <reference path="./os.d.ts" />
+/// <reference path="./env.d.ts" />
 /// <reference path="./time.d.ts" />
 /// <reference path="./watch.d.ts" />
 /// <reference path="./crypto.d.ts" />
+/// <reference path="./compress.d.ts" />
 /// <reference path="./console.d.ts" />
+/// <reference path="./test.d.ts" />
 
 // Re-export all types for convenience
 export * from "./fs.d.ts";
 export * from "./http.d.ts";
 export * from "./server.d.ts";
 export * from "./process.d.ts";
+export * from "./os.d.ts";
+export * as env from "./env.d.ts";
 export * from "./time.d.ts";
 export * from "./watch.d.ts";
 export * from "./crypto.d.ts";
+export * from "./compress.d.ts";
 export * from "./console.d.ts";
+export * from "./test.d.ts";
@@ -0,0 +1,73 @@
+/**
+ * Host OS Module
+ * 
+ * Provides information about the operating system and machine.
+ * Import from "host://os"
+ */
+
+/**
+ * The operating system: "linux", "darwin" or "windows". Other platforms
+ * report Rust's name for them, e.g. "freebsd"
+ * 
+ * @example
+ * ```typescript
+ * import { platform } from "host://os";
+ * 
+ * const opener = platform() === "darwin" ? "open" : "xdg-open";
+ * ```
+ */
+export declare function platform(): "linux" | "darwin" | "windows" | (string & {});
+
+/**
+ * The CPU architecture, e.g. "x86_64" or "aarch64"
+ */
+export declare function arch(): string;
+
+/**
+ * The machine's host name
+ * 
+ * @throws Error if the system call fails
+ */
+export declare function hostname(): string;
+
+/**
+ * The current user's home directory
+ * 
+ * @throws Error if it can't be determined
+ */
+export declare function homedir(): string;
+
+/**
+ * An address of a network interface
+ */
+export interface NetworkInterface {
+  /** Interface name, e.g. "eth0" or "lo" */
+  name: string;
+  /** The address, e.g. "192.168.1.10" or "fe80::1" */
+  address: string;
+  family: "IPv4" | "IPv6";
+  /** True for loopback addresses */
+  internal: boolean;
+}
+
+/**
+ * The machine's network interface addresses, one entry per address
+ * (interfaces with both IPv4 and IPv6 addresses appear more than once)
+ * 
+ * @throws Error if the interfaces can't be enumerated
+ * 
+ * @example
+ * ```typescript
+ * import { networkInterfaces } from "host://os";
+ * 
+ * const lanIps = networkInterfaces()
+ *   .filter((i) => i.family === "IPv4" && !i.internal)
+ *   .map((i) => i.address);
+ * ```
+ */
+export declare function networkInterfaces(): NetworkInterface[];
+
+/**
+ * Number of logical CPUs available to this process, e.g. to size a worker pool
+ */
+export declare function cpus(): number;
@@ -34,11 +34,34 @@ export interface SpawnOptions {
   /** How to handle stdin: "piped" | "inherit" | "null" (default: "null") */
   stdin?: "piped" | "inherit" | "null";
 
-  /** How to handle stdout: "piped" | "inherit" | "null" (default: "piped") */
+  /** How to handle stdout: "piped" | "inherit" | "null" (default: "piped", or "null" when detached) */
   stdout?: "piped" | "inherit" | "null";
 
-  /** How to handle stderr: "piped" | "inherit" | "null" (default: "piped") */
+  /** How to handle stderr: "piped" | "inherit" | "null" (default: "piped", or "null" when detached) */
   stderr?: "piped" | "inherit" | "null";
+
+  /**
+   * Run the child in a new session and process group (Unix) or a new
+   * process group (Windows), so it outlives the parent and doesn't
+   * receive its signals (default: false)
+   */
+  detached?: boolean;
+}
+
+/**
+ * Text encodings for decoding child output
+ */
+export type OutputEncoding = "utf-8" | "latin1";
+
+/**
+ * Options for decoding child output as text
+ */
+export interface TextDecodeOptions {
+  /**
+   * Throw a TypeError naming the byte offset on invalid UTF-8 instead of
+   * replacing each bad sequence with U+FFFD (default: false)
+   */
+  strict?: boolean;
 }
 
 /**
@@ -68,11 +91,11 @@ export interface CommandOutput {
   /** Stderr as Uint8Array (empty if stderr not piped) */
   stderr: Uint8Array;
 
-  /** Convenience: stdout decoded as UTF-8 string */
-  stdoutText(): string;
+  /** Convenience: stdout decoded as UTF-8 string (invalid bytes become U+FFFD unless strict) */
+  stdoutText(options?: TextDecodeOptions): string;
 
-  /** Convenience: stderr decoded as UTF-8 string */
-  stderrText(): string;
+  /** Convenience: stderr decoded as UTF-8 string (invalid bytes become U+FFFD unless strict) */
+  stderrText(options?: TextDecodeOptions): string;
 }
 
 /**
@@ -88,9 +111,62 @@ export interface Process {
   /** Send a signal to the process */
   kill(signal?: Signal): void;
 
-  /** Wait for process and collect all output */
+  /**
+   * Check whether the process has exited without waiting: its status if it
+   * has, otherwise null. `status` still resolves afterwards
+   */
+  poll(): ProcessStatus | null;
+
+  /**
+   * Wait for process and collect all output. stdout and stderr are read
+   * together, so a child writing lots to both can't stall. Throws if
+   * stdout is already being read another way (e.g. stdoutLines())
+   */
   output(): Promise<CommandOutput>;
 
+  /**
+   * Read all of stdout as text (default encoding: "utf-8"). Invalid UTF-8
+   * becomes U+FFFD unless `strict` is set. If stderr is piped as well,
+   * this can hang once the child fills the stderr pipe; use output() or
+   * stderr: "inherit" / "null" for chatty processes
+   */
+  readStdoutText(encoding?: OutputEncoding, options?: TextDecodeOptions): Promise<string>;
+
+  /**
+   * Iterate over stdout line by line as it arrives, without the "\n" or
+   * "\r\n" terminators; a last line without a terminator is included
+   *
+   * @example
+   * ```typescript
+   * const proc = spawn({ cmd: ["ls", "-1"] });
+   * for await (const line of proc.stdoutLines()) {
+   *   log(line);
+   * }
+   * ```
+   */
+  stdoutLines(encoding?: OutputEncoding, options?: TextDecodeOptions): AsyncIterableIterator<string>;
+
+  /**
+   * Read the stdout written so far, waiting for at least one byte and
+   * returning up to `maxBytes` (default: 65536). `done` is true, with empty
+   * `data`, once stdout has closed. Can be called repeatedly while the
+   * process runs
+   */
+  readStdoutChunk(maxBytes?: number): Promise<{ data: Uint8Array; done: boolean }>;
+
+  /**
+   * Iterate over stdout in chunks as the process writes it
+   *
+   * @example
+   * ```typescript
+   * const build = spawn({ cmd: ["cargo", "build"], stderr: "inherit" });
+   * for await (const chunk of build.stdoutChunks()) {
+   *   log(`build wrote ${chunk.length} bytes`);
+   * }
+   * ```
+   */
+  stdoutChunks(maxBytes?: number): AsyncIterableIterator<Uint8Array>;
+
   /** Write data to stdin and close it */
   writeInput(data: string | Uint8Array): Promise<void>;
 }
@@ -131,3 +207,112 @@ export declare function spawn(command: string, args?: string[]): Promise<Command
  * ```
  */
 export declare function spawn(options: SpawnOptions): Process;
+
+/**
+ * Options for exec()
+ */
+export interface ExecOptions {
+  /** Command and arguments as array */
+  cmd: string[];
+
+  /** Working directory for the process */
+  cwd?: string;
+
+  /** Environment variables (replaces or merges with process env) */
+  env?: Record<string, string>;
+
+  /** Inherit environment and merge with env option (default: true) */
+  inheritEnv?: boolean;
+
+  /** Data written to the process's stdin, which is then closed (default: no stdin) */
+  stdin?: string | Uint8Array;
+}
+
+/**
+ * Run a command to completion and capture its output
+ * 
+ * Input is written and stdout/stderr are drained at the same time, so large
+ * outputs can't deadlock. Prefer this over spawn() unless you need to
+ * stream or interact with the process.
+ * 
+ * @example
+ * ```typescript
+ * import { exec } from "host://process";
+ * 
+ * const result = await exec("git", ["status", "--short"]);
+ * const sorted = await exec({ cmd: ["sort"], stdin: "b\na\n" });
+ * console.log(sorted.stdoutText()); // "a\nb\n"
+ * ```
+ */
+export declare function exec(command: string, args?: string[]): Promise<CommandOutput>;
+export declare function exec(options: ExecOptions): Promise<CommandOutput>;
+
+/**
+ * When the runtime started, in milliseconds since the Unix epoch
+ * 
+ * @example
+ * ```typescript
+ * import { startTime } from "host://process";
+ * 
+ * log(`started at ${new Date(startTime()).toISOString()}`);
+ * ```
+ */
+export declare function startTime(): number;
+
+/**
+ * Seconds elapsed since the runtime started, from a monotonic clock with
+ * sub-millisecond precision
+ * 
+ * @example
+ * ```typescript
+ * import { uptime } from "host://process";
+ * 
+ * log(`up for ${uptime().toFixed(3)}s`);
+ * ```
+ */
+export declare function uptime(): number;
+
+/**
+ * Whether a standard stream is attached to a terminal, e.g. to decide on
+ * colors, prompts or progress bars
+ * 
+ * @example
+ * ```typescript
+ * import { isatty } from "host://process";
+ * 
+ * const color = isatty("stdout");
+ * ```
+ */
+export declare function isatty(stream: "stdin" | "stdout" | "stderr"): boolean;
+
+/**
+ * Signals a script can listen for with onSignal
+ */
+export type ListenSignal =
+  | "SIGHUP"
+  | "SIGINT"
+  | "SIGQUIT"
+  | "SIGTERM"
+  | "SIGUSR1"
+  | "SIGUSR2"
+  | "SIGWINCH";
+
+/**
+ * Iterate over deliveries of a Unix signal to this process, e.g. to reload
+ * config on SIGHUP. The handler is installed on the first `next()` and
+ * replaces the signal's default action (such as terminating) for the rest of
+ * the run, even after the loop ends. Signals delivered while the loop body
+ * runs are coalesced into one iteration. Breaking out of the loop stops
+ * listening; other iterators for the same signal keep receiving it. Rejects
+ * on non-Unix platforms
+ * 
+ * @example
+ * ```typescript
+ * import { onSignal } from "host://process";
+ * 
+ * for await (const _ of onSignal("SIGHUP")) {
+ *   config = loadConfig();
+ * }
+ * ```
+ */
+export declare function onSignal(signal: ListenSignal): AsyncIterableIterator<ListenSignal>;
@@ -5,10 +5,90 @@
  * Import from "host://http/server"
  */
 
+/**
+ * Request received by a serve() handler
+ */
+export interface ServerRequest extends Request {
+  /**
+   * Send "100 Continue" to a client that sent `Expect: 100-continue`.
+   * Reading the body does this automatically; responding without reading
+   * the body rejects the upload. No-op for other requests.
+   */
+  sendContinue(): Promise<void>;
+  /**
+   * Respond with a child process's stdout, streamed by the host without
+   * passing each chunk through JavaScript. The process must be spawned with
+   * `stdout: "piped"`. Resolves when the output has been sent; the handler's
+   * return value is then ignored.
+   */
+  respondFromProcess(
+    process: import("./process").Process,
+    init?: { status?: number; headers?: Record<string, string> },
+  ): Promise<void>;
+  /**
+   * Respond with a file, streamed from disk by the host. With the default
+   * 200 status a single-range `Range` header is answered with 206 Partial
+   * Content (or 416 when unsatisfiable); multi-range requests get the whole
+   * file. The handler's return value is then ignored.
+   */
+  respondFile(
+    path: string,
+    init?: { status?: number; headers?: Record<string, string> },
+  ): Promise<void>;
+  /**
+   * Start a response whose body is written in chunks. The status and headers
+   * are sent right away and each write() is flushed to the client as it
+   * arrives, so large or open-ended bodies (server-sent events) are never
+   * buffered. The handler's return value is then ignored. The body ends when
+   * the handler settles (if close() wasn't called first), and is cut short if
+   * the handler throws.
+   */
+  respondStream(
+    init?: { status?: number; headers?: Record<string, string> },
+  ): Promise<ResponseStream>;
+  /**
+   * Answer a request that asked for a protocol upgrade (an `Upgrade` header
+   * with `Connection: upgrade`, e.g. a WebSocket handshake) with 101
+   * Switching Protocols and take over its connection. The `Upgrade`
+   * response header defaults to the requested protocol. Resolves once the
+   * 101 response is sent; the handler's return value is then ignored.
+   * Rejects with a TypeError for requests that didn't ask to upgrade.
+   */
+  upgrade(
+    init?: { headers?: Record<string, string> },
+  ): Promise<UpgradedConnection>;
+}
+
+/**
+ * Body writer returned by ServerRequest.respondStream()
+ */
+export interface ResponseStream {
+  /** Send a chunk; waits while the client is behind, rejects once it has gone away */
+  write(chunk: string | Uint8Array | ArrayBuffer): Promise<void>;
+  /** End the body */
+  close(): Promise<void>;
+}
+
+/**
+ * Connection returned by ServerRequest.upgrade(). It no longer speaks HTTP,
+ * and isn't counted by activeConnections() or waited for by shutdown()
+ */
+export interface UpgradedConnection {
+  /**
+   * Wait for bytes from the client, up to maxBytes (default 64 KiB);
+   * `done` is true, with no data, once the client has closed its side
+   */
+  read(maxBytes?: number): Promise<{ data: Uint8Array; done: boolean }>;
+  /** Send bytes; resolves once they're flushed to the client */
+  write(chunk: string | Uint8Array | ArrayBuffer): Promise<void>;
+  /** Close the connection after pending writes */
+  close(): Promise<void>;
+}
+
 /**
  * Request handler function type
  */
-export type RequestHandler = (request: Request) => Response | Promise<Response>;
+export type RequestHandler = (request: ServerRequest) => Response | Promise<Response>;
 
 /**
  * Options for serve()
@@ -22,18 +102,47 @@ export interface ServeOptions {
   onListen?: (info: { port: number; hostname: string }) => void;
   /** Called when handler throws an error */
   onError?: (error: Error) => Response | Promise<Response>;
+  /**
+   * Serve HTTPS using these PEM files (certificate chain and private key).
+   * serve() throws if they can't be read or parsed.
+   */
+  tls?: { certFile: string; keyFile: string };
+  /**
+   * Largest request body accepted, in bytes; bigger requests are answered
+   * with 413 Payload Too Large without reading the rest. Default: 10 MiB
+   */
+  maxBodyBytes?: number;
+  /**
+   * Keep connections open for further requests. When false, each
+   * connection closes after its response. Default: true
+   */
+  keepAlive?: boolean;
+  /**
+   * Close connections that don't send a complete request head (or, over
+   * HTTPS, finish the TLS handshake) within this many milliseconds,
+   * including idle kept-alive ones. Default: 30000
+   */
+  idleTimeoutMs?: number;
 }
 
 /**
  * Server handle returned by serve()
  */
 export interface Server {
+  /** Host ID of the server, for activeConnections() */
+  readonly serverId: number;
   /** Port the server is listening on */
   readonly port: number;
   /** Hostname the server is bound to */
   readonly hostname: string;
-  /** Gracefully shutdown the server */
-  shutdown(): Promise<void>;
+  /** Resolves once the server has stopped accepting requests */
+  readonly finished: Promise<void>;
+  /**
+   * Gracefully shutdown the server, waiting up to `drainTimeoutMs`
+   * (default 30000; 0 waits indefinitely) for in-flight requests.
+   * Resolves to true if they finished, false if the timeout elapsed first.
+   */
+  shutdown(options?: { drainTimeoutMs?: number }): Promise<boolean>;
   /** Async disposable - calls shutdown() when disposed */
   [Symbol.asyncDispose](): Promise<void>;
 }
@@ -55,6 +164,14 @@ export interface Server {
  */
 export declare function serve(options: ServeOptions, handler: RequestHandler): Server;
 
+/**
+ * Get the number of connections currently open to a server
+ * 
+ * @param serverId - The server's `serverId`
+ * @returns Open connection count, or -1 if the server is unknown or stopped
+ */
+export declare function activeConnections(serverId: number): number;
+
 /**
  * Create a Response with optional body and init
  * 
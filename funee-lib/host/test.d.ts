@@ -0,0 +1,42 @@
+/**
+ * Host Test Module
+ * 
+ * Registers tests for `funee test`.
+ * Import from "host://test"
+ */
+
+/**
+ * Register a test. Tests run one at a time once the file's default export
+ * has returned; a test fails if it throws or its promise rejects.
+ * 
+ * @param name - Name shown in the test report
+ * @param fn - Test body
+ */
+export declare function test(name: string, fn: () => void | Promise<void>): void;
+
+/**
+ * Fail the current test unless `condition` is truthy
+ * 
+ * @param condition - Value that must be truthy
+ * @param message - Failure message (default: "Assertion failed")
+ */
+export declare function assert(condition: unknown, message?: string): asserts condition;
+
+/**
+ * Fail the current test unless `actual` deeply equals `expected`
+ * (plain objects and arrays are compared by their own enumerable keys)
+ * 
+ * @param actual - Value produced by the code under test
+ * @param expected - Value it should equal
+ * @param message - Failure message (default: "Expected <expected>, got <actual>")
+ */
+export declare function assertEqual<T>(actual: T, expected: T, message?: string): void;
+
+/**
+ * Fail the current test unless `fn` throws
+ * 
+ * @param fn - Function expected to throw
+ * @param message - Failure message (default: "Expected function to throw")
+ * @returns The thrown value
+ */
+export declare function assertThrows(fn: () => unknown, message?: string): unknown;
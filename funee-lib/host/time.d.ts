@@ -1,7 +1,7 @@
 /**
  * Host Time Module
  * 
- * Provides timer functions.
+ * Provides timer functions and a monotonic clock.
  * Import from "host://time"
  */
 
@@ -36,3 +36,20 @@ export declare function setInterval(callback: () => void, ms: number): number;
  * @param id - Timer ID returned by setInterval
  */
 export declare function clearInterval(id: number): void;
+
+/**
+ * Nanoseconds since the runtime started, from a monotonic clock
+ * 
+ * Unlike Date.now(), it never goes backwards or jumps when the system clock
+ * is adjusted, which makes it the right clock for measuring durations.
+ * 
+ * @example
+ * ```typescript
+ * import { monotonicNanos } from "host://time";
+ * 
+ * const start = monotonicNanos();
+ * work();
+ * log(`took ${Number(monotonicNanos() - start) / 1e6}ms`);
+ * ```
+ */
+export declare function monotonicNanos(): bigint;
@@ -8,9 +8,14 @@
 /**
  * Start watching a path for changes
  * 
+ * A path that doesn't exist yet is watched through its parent directory
+ * until it is created; only its own events are reported, and the result has
+ * `pending: true`.
+ * 
  * @param path - Path to watch
  * @param recursive - Whether to watch subdirectories
- * @returns JSON string with watcher ID or error
+ * @param maxQueue - Maximum undelivered events kept before the oldest are dropped (default: 10000)
+ * @returns JSON string with watcher ID (and `pending`) or error
  * 
  * @example
  * ```typescript
@@ -20,21 +25,38 @@
  * const watcherId = result.value;
  * 
  * // Poll for events
- * const events = JSON.parse(watchPoll(watcherId));
+ * let cursor = 0;
+ * const { events, nextCursor, dropped } = JSON.parse(watchPoll(watcherId, cursor));
+ * cursor = nextCursor;
  * 
  * // Stop watching
  * watchStop(watcherId);
  * ```
  */
-export declare function watchStart(path: string, recursive: boolean): string;
+export declare function watchStart(path: string, recursive: boolean, maxQueue?: number): string;
 
 /**
  * Poll for watch events from a watcher
  * 
  * @param watcherId - The watcher ID from watchStart
- * @returns JSON string with array of events, or "null" if no events
+ * @param cursor - Only return events with `seq >= cursor` (default: 0)
+ * @returns JSON string with { events, nextCursor, dropped }, or "null" if the
+ *   watcher doesn't exist. `dropped` counts events at or after `cursor` lost
+ *   because the queue was full.
+ */
+export declare function watchPoll(watcherId: number, cursor?: number): string;
+
+/**
+ * Wait for watch events from a watcher
+ * 
+ * Resolves as soon as at least one event is queued, instead of polling.
+ * 
+ * @param watcherId - The watcher ID from watchStart
+ * @param cursor - Only return events with `seq >= cursor` (default: 0)
+ * @returns JSON string with { events, nextCursor, dropped }, or "null" once
+ *   the watcher is stopped
  */
-export declare function watchPoll(watcherId: number): string;
+export declare function watchNext(watcherId: number, cursor?: number): Promise<string>;
 
 /**
  * Stop a watcher
@@ -42,3 +64,53 @@ export declare function watchPoll(watcherId: number): string;
  * @param watcherId - The watcher ID from watchStart
  */
 export declare function watchStop(watcherId: number): void;
+
+/**
+ * A single file system change
+ */
+export interface WatchEvent {
+  kind: "create" | "modify" | "remove" | "access" | "other" | "any";
+  path: string;
+  seq: number;
+}
+
+/**
+ * Options for watch()
+ */
+export interface WatchOptions {
+  /** Watch subdirectories too (default: false) */
+  recursive?: boolean;
+  /** Skip events whose path contains a string pattern or matches a RegExp */
+  ignore?: (string | RegExp)[];
+  /** Wait this many ms after an event and coalesce the burst (default: 0) */
+  debounce?: number;
+  /** Maximum undelivered events kept before the oldest are dropped (default: 10000) */
+  maxQueue?: number;
+}
+
+/**
+ * Handle returned by watch()
+ */
+export interface FsWatcher extends AsyncIterable<WatchEvent> {
+  /** Stop watching; pending and future iterations end */
+  close(): void;
+  /** True when the path didn't exist yet and is awaiting creation */
+  pending: boolean;
+}
+
+/**
+ * Watch a path and iterate its events
+ * 
+ * Breaking out of the loop or calling close() stops the watcher.
+ * 
+ * @example
+ * ```typescript
+ * import { watch } from "host://watch";
+ * 
+ * const watcher = watch("./src", { recursive: true, ignore: [".git"], debounce: 50 });
+ * for await (const event of watcher) {
+ *   log(`${event.kind}: ${event.path}`);
+ * }
+ * ```
+ */
+export declare function watch(path: string, options?: WatchOptions): FsWatcher;
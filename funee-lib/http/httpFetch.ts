@@ -5,6 +5,8 @@
  * or httpPostJSON functions, or use the standard fetch API from "host://http".
  */
 
+import { base64Decode } from "../filesystem/readFileBinary.ts";
+
 /**
  * Parsed HTTP response from httpRequest.
  */
@@ -17,6 +19,59 @@ export interface HttpResponse {
   body: string;
 }
 
+/**
+ * Result of the `_fetch` host function (op_fetch). Text bodies come in
+ * `body`; with the `binary` flag set, the body comes base64 encoded in
+ * `bodyBase64` instead, so bytes that aren't valid UTF-8 survive.
+ */
+export interface FetchResult {
+  status: number;
+  statusText: string;
+  /** Response headers in order; a header sent more than once appears once per value */
+  headers: [string, string][];
+  url: string;
+  redirected: boolean;
+  body?: string;
+  bodyBase64?: string;
+}
+
+/**
+ * A parsed FetchResult with Response-style body readers
+ */
+export interface ParsedFetchResult extends FetchResult {
+  /** The body bytes (decoded from `bodyBase64`, or the UTF-8 of `body`) */
+  arrayBuffer(): ArrayBuffer;
+  /** The body as text (`body`, or `bodyBase64` decoded as UTF-8) */
+  text(): string;
+}
+
+/**
+ * Parse the JSON returned by `_fetch`.
+ *
+ * @example
+ * ```typescript
+ * import { _fetch, parseFetchResult } from "funee";
+ *
+ * const json = await _fetch("GET", url, "{}", "", "", true, "", "", "", false, 0, 0, true, "", 0);
+ * const png = new Uint8Array(parseFetchResult(json).arrayBuffer());
+ * ```
+ */
+export const parseFetchResult = (json: string): ParsedFetchResult => {
+  const result = JSON.parse(json) as FetchResult;
+  const bytes = (): Uint8Array =>
+    result.bodyBase64 !== undefined
+      ? base64Decode(result.bodyBase64)
+      : new TextEncoder().encode(result.body ?? "");
+  return {
+    ...result,
+    arrayBuffer: () => {
+      const view = bytes();
+      return view.buffer.slice(view.byteOffset, view.byteOffset + view.byteLength) as ArrayBuffer;
+    },
+    text: () => result.body ?? new TextDecoder().decode(bytes()),
+  };
+};
+
 /**
  * Parse a JSON string to HttpResponse.
  * 
@@ -61,8 +61,8 @@ export { isURL, targetToURL } from "./HttpTarget.ts";
 
 // Low-level fetch
 // Note: httpFetch is exported from funee/host.ts, not here
-export type { HttpResponse } from "./httpFetch.ts";
-export { parseHttpResponse } from "./httpFetch.ts";
+export type { HttpResponse, FetchResult, ParsedFetchResult } from "./httpFetch.ts";
+export { parseHttpResponse, parseFetchResult } from "./httpFetch.ts";
 
 // Core request function
 export type { HttpMethod, HttpRequestOptions } from "./httpRequest.ts";
@@ -124,5 +124,5 @@ export {
 } from "./Response.ts";
 
 // Fetch function and types
-export type { RequestInit, RequestRedirect } from "./fetch.ts";
+export type { RequestInit, RequestRedirect, FetchTlsOptions, DownloadProgress, ResponseToFileOptions } from "./fetch.ts";
 export { fetch } from "./fetch.ts";
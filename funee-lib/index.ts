@@ -518,6 +518,8 @@ export type {
   HostAndPathTarget,
   HttpTarget,
   HttpResponse,
+  FetchResult,
+  ParsedFetchResult,
   HttpMethod,
   HttpRequestOptions,
   HttpGetJSONOptions,
@@ -533,6 +535,9 @@ export type {
   ResponseConstructor,
   RequestInit,
   RequestRedirect,
+  FetchTlsOptions,
+  DownloadProgress,
+  ResponseToFileOptions,
   BodyInit,
   Blob,
 } from "./http/index.ts";
@@ -542,6 +547,7 @@ export {
   isURL,
   targetToURL,
   parseHttpResponse,
+  parseFetchResult,
   httpRequest,
   httpGetJSON,
   httpPostJSON,
@@ -592,6 +598,9 @@ export type {
   FsResultOk,
   FsResultErr,
   FileStats,
+  DirEntry,
+  WalkEntry,
+  FileToWrite,
   TempDir,
 } from "./filesystem/index.ts";
 
@@ -607,11 +616,43 @@ export {
   writeFileRaw,
   writeFileBinary,
   writeFileBinaryRaw,
+  appendFile,
+  appendFileRaw,
+  appendFileBinary,
+  appendFileBinaryRaw,
+  writeFiles,
+  writeFilesRaw,
+  copyFile,
+  copyFileRaw,
+  rename,
+  renameRaw,
+  realpath,
+  realpathRaw,
+  remove,
+  removeRaw,
   base64Encode,
   base64Decode,
   isFile,
   lstat,
   lstatRaw,
+  stat,
+  statRaw,
+  chmod,
+  chmodRaw,
+  chown,
+  chownRaw,
+  truncate,
+  truncateRaw,
+  utimes,
+  utimesRaw,
+  mkdtemp,
+  mkdtempRaw,
+  readdirTyped,
+  readdirTypedRaw,
+  walk,
+  walkRaw,
+  glob,
+  globRaw,
   readdir,
   readdirRaw,
   tempDir,
@@ -705,9 +746,12 @@ export type {
   RequestHandler,
   ServeOptions,
   Server,
+  ServerRequest,
+  ResponseStream,
+  UpgradedConnection,
 } from "./server/index.ts";
 
-export { serve } from "./server/index.ts";
+export { serve, activeConnections } from "./server/index.ts";
 
 // ============================================================================
 // Subprocess - Child Process Management
@@ -715,10 +759,11 @@ export { serve } from "./server/index.ts";
 
 export type {
   SpawnOptions,
+  ExecOptions,
   ProcessStatus,
   CommandOutput,
   Process,
   Signal,
 } from "./process/index.ts";
 
-export { spawn } from "./process/index.ts";
+export { spawn, exec } from "./process/index.ts";
@@ -25,11 +25,11 @@
  * ```
  */
 
-import { spawn as hostSpawn } from "host://process";
-import type { SpawnOptions, ProcessStatus, CommandOutput, Process, Signal } from "./types.ts";
+import { spawn as hostSpawn, exec as hostExec } from "host://process";
+import type { SpawnOptions, ExecOptions, ProcessStatus, CommandOutput, Process, Signal } from "./types.ts";
 
 // Re-export types
-export type { SpawnOptions, ProcessStatus, CommandOutput, Process, Signal } from "./types.ts";
+export type { SpawnOptions, ExecOptions, ProcessStatus, CommandOutput, Process, Signal, OutputEncoding, TextDecodeOptions } from "./types.ts";
 
 /**
  * Spawn a subprocess.
@@ -63,3 +63,25 @@ export type { SpawnOptions, ProcessStatus, CommandOutput, Process, Signal } from
  * ```
  */
 export const spawn = hostSpawn;
+
+/**
+ * Run a command to completion and capture its output.
+ * 
+ * Stdin is written while stdout and stderr are drained, all in one host
+ * call, so large outputs can't deadlock the way a hand-ordered
+ * spawn/read/wait sequence can. This is the recommended way to run a
+ * command; use spawn() to stream or interact with a process.
+ * 
+ * @example
+ * ```typescript
+ * const result = await exec("git", ["rev-parse", "HEAD"]);
+ * console.log(result.stdoutText().trim());
+ * 
+ * const sorted = await exec({ cmd: ["sort"], stdin: "b\na\n" });
+ * console.log(sorted.stdoutText()); // "a\nb\n"
+ * ```
+ */
+export const exec = hostExec as {
+  (command: string, args?: string[]): Promise<CommandOutput>;
+  (options: ExecOptions): Promise<CommandOutput>;
+};
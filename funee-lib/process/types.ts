@@ -33,11 +33,54 @@ export interface SpawnOptions {
   /** How to handle stdin: "piped" | "inherit" | "null" (default: "null") */
   stdin?: "piped" | "inherit" | "null";
   
-  /** How to handle stdout: "piped" | "inherit" | "null" (default: "piped") */
+  /** How to handle stdout: "piped" | "inherit" | "null" (default: "piped", or "null" when detached) */
   stdout?: "piped" | "inherit" | "null";
   
-  /** How to handle stderr: "piped" | "inherit" | "null" (default: "piped") */
+  /** How to handle stderr: "piped" | "inherit" | "null" (default: "piped", or "null" when detached) */
   stderr?: "piped" | "inherit" | "null";
+  
+  /**
+   * Run the child in a new session and process group (Unix) or a new
+   * process group (Windows), so it outlives the parent and doesn't
+   * receive its signals (default: false)
+   */
+  detached?: boolean;
+}
+
+/**
+ * Options for exec()
+ */
+export interface ExecOptions {
+  /** Command and arguments as array */
+  cmd: string[];
+  
+  /** Working directory for the process */
+  cwd?: string;
+  
+  /** Environment variables (replaces or merges with process env) */
+  env?: Record<string, string>;
+  
+  /** Inherit environment and merge with env option (default: true) */
+  inheritEnv?: boolean;
+  
+  /** Data written to the process's stdin, which is then closed (default: no stdin) */
+  stdin?: string | Uint8Array;
+}
+
+/**
+ * Text encodings for decoding child output
+ */
+export type OutputEncoding = "utf-8" | "latin1";
+
+/**
+ * Options for decoding child output as text
+ */
+export interface TextDecodeOptions {
+  /**
+   * Throw a TypeError naming the byte offset on invalid UTF-8 instead of
+   * replacing each bad sequence with U+FFFD (default: false)
+   */
+  strict?: boolean;
 }
 
 /**
@@ -67,11 +110,11 @@ export interface CommandOutput {
   /** Stderr as Uint8Array (empty if stderr not piped) */
   stderr: Uint8Array;
   
-  /** Convenience: stdout decoded as UTF-8 string */
-  stdoutText(): string;
+  /** Convenience: stdout decoded as UTF-8 string (invalid bytes become U+FFFD unless strict) */
+  stdoutText(options?: TextDecodeOptions): string;
   
-  /** Convenience: stderr decoded as UTF-8 string */
-  stderrText(): string;
+  /** Convenience: stderr decoded as UTF-8 string (invalid bytes become U+FFFD unless strict) */
+  stderrText(options?: TextDecodeOptions): string;
 }
 
 /**
@@ -87,9 +130,51 @@ export interface Process {
   /** Send a signal to the process */
   kill(signal?: Signal): void;
   
-  /** Wait for process and collect all output */
+  /**
+   * Check whether the process has exited without waiting: its status if it
+   * has, otherwise null. `status` still resolves afterwards
+   */
+  poll(): ProcessStatus | null;
+  
+  /**
+   * Wait for process and collect all output. stdout and stderr are read
+   * together, so a child writing lots to both can't stall. Throws if
+   * stdout is already being read another way (e.g. stdoutLines())
+   */
   output(): Promise<CommandOutput>;
   
+  /**
+   * Read all of stdout as text (default encoding: "utf-8"). Invalid UTF-8
+   * becomes U+FFFD unless `strict` is set. If stderr is piped as well,
+   * this can hang once the child fills the stderr pipe; use output() or
+   * stderr: "inherit" / "null" for chatty processes
+   */
+  readStdoutText(encoding?: OutputEncoding, options?: TextDecodeOptions): Promise<string>;
+  
+  /** Iterate over stdout line by line as it arrives, without line terminators */
+  stdoutLines(encoding?: OutputEncoding, options?: TextDecodeOptions): AsyncIterableIterator<string>;
+  
+  /**
+   * Read the stdout written so far, waiting for at least one byte and
+   * returning up to `maxBytes` (default: 65536). `done` is true, with empty
+   * `data`, once stdout has closed. Can be called repeatedly while the
+   * process runs
+   */
+  readStdoutChunk(maxBytes?: number): Promise<{ data: Uint8Array; done: boolean }>;
+  
+  /**
+   * Iterate over stdout in chunks as the process writes it
+   *
+   * @example
+   * ```typescript
+   * const build = spawn({ cmd: ["cargo", "build"], stderr: "inherit" });
+   * for await (const chunk of build.stdoutChunks()) {
+   *   log(`build wrote ${chunk.length} bytes`);
+   * }
+   * ```
+   */
+  stdoutChunks(maxBytes?: number): AsyncIterableIterator<Uint8Array>;
+  
   /** Write data to stdin and close it */
   writeInput(data: string | Uint8Array): Promise<void>;
 }
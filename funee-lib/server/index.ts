@@ -7,7 +7,10 @@
 export type {
   RequestHandler,
   ServeOptions,
+  ServerRequest,
   Server,
+  ResponseStream,
+  UpgradedConnection,
 } from "./serve.ts";
 
-export { serve } from "./serve.ts";
+export { serve, activeConnections } from "./serve.ts";
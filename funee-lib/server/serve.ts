@@ -18,12 +18,84 @@
  * ```
  */
 
-import { serve as hostServe } from "host://http/server";
+import { serve as hostServe, activeConnections as hostActiveConnections } from "host://http/server";
+import type { Process } from "../process/types.ts";
+
+/**
+ * Request received by a serve() handler
+ */
+export type ServerRequest = Request & {
+  /**
+   * Send "100 Continue" to a client that sent `Expect: 100-continue`.
+   * Reading the body does this automatically; responding without reading
+   * the body rejects the upload. No-op for other requests.
+   */
+  sendContinue: () => Promise<void>;
+  /**
+   * Respond with a child process's stdout, streamed by the host. The process
+   * must be spawned with `stdout: "piped"`; the handler's return value is
+   * ignored afterwards.
+   */
+  respondFromProcess: (
+    process: Process,
+    init?: { status?: number; headers?: Record<string, string> },
+  ) => Promise<void>;
+  /**
+   * Respond with a file, streamed from disk by the host. A `Range` header is
+   * honored for 200 responses (206 Partial Content, or 416 when
+   * unsatisfiable); the handler's return value is ignored afterwards.
+   */
+  respondFile: (
+    path: string,
+    init?: { status?: number; headers?: Record<string, string> },
+  ) => Promise<void>;
+  /**
+   * Start a response whose body is written in chunks, each flushed to the
+   * client as it arrives; the handler's return value is ignored afterwards.
+   * The body ends when the handler settles, and is cut short if it throws.
+   */
+  respondStream: (
+    init?: { status?: number; headers?: Record<string, string> },
+  ) => Promise<ResponseStream>;
+  /**
+   * Answer a request that asked for a protocol upgrade (`Upgrade` with
+   * `Connection: upgrade`) with 101 Switching Protocols and take over its
+   * connection; the handler's return value is ignored afterwards. The
+   * `Upgrade` response header defaults to the requested protocol. Rejects
+   * with a TypeError for other requests.
+   */
+  upgrade: (
+    init?: { headers?: Record<string, string> },
+  ) => Promise<UpgradedConnection>;
+};
+
+/**
+ * Body writer returned by ServerRequest.respondStream()
+ */
+export type ResponseStream = {
+  /** Send a chunk; waits while the client is behind, rejects once it has gone away */
+  write: (chunk: string | Uint8Array | ArrayBuffer) => Promise<void>;
+  /** End the body */
+  close: () => Promise<void>;
+};
+
+/**
+ * Connection taken over by ServerRequest.upgrade(). It no longer speaks
+ * HTTP, and isn't counted by activeConnections() or waited for by shutdown()
+ */
+export type UpgradedConnection = {
+  /** Wait for bytes from the client, up to maxBytes (default 64 KiB); `done` once it has closed its side */
+  read: (maxBytes?: number) => Promise<{ data: Uint8Array; done: boolean }>;
+  /** Send bytes; resolves once they're flushed */
+  write: (chunk: string | Uint8Array | ArrayBuffer) => Promise<void>;
+  /** Close the connection after pending writes */
+  close: () => Promise<void>;
+};
 
 /**
  * Request handler function
  */
-export type RequestHandler = (request: Request) => Response | Promise<Response>;
+export type RequestHandler = (request: ServerRequest) => Response | Promise<Response>;
 
 /**
  * Options for serve()
@@ -37,18 +109,47 @@ export type ServeOptions = {
   onListen?: (info: { port: number; hostname: string }) => void;
   /** Called when handler throws an error */
   onError?: (error: Error) => Response | Promise<Response>;
+  /**
+   * Serve HTTPS using these PEM files (certificate chain and private key).
+   * serve() throws if they can't be read or parsed.
+   */
+  tls?: { certFile: string; keyFile: string };
+  /**
+   * Largest request body accepted, in bytes; bigger requests are answered
+   * with 413 Payload Too Large without reading the rest. Default: 10 MiB
+   */
+  maxBodyBytes?: number;
+  /**
+   * Keep connections open for further requests. When false, each
+   * connection closes after its response. Default: true
+   */
+  keepAlive?: boolean;
+  /**
+   * Close connections that don't send a complete request head (or, over
+   * HTTPS, finish the TLS handshake) within this many milliseconds,
+   * including idle kept-alive ones. Default: 30000
+   */
+  idleTimeoutMs?: number;
 };
 
 /**
  * Server handle returned by serve()
  */
 export type Server = {
+  /** Host ID of the server, for activeConnections() */
+  readonly serverId: number;
   /** Port the server is listening on */
   readonly port: number;
   /** Hostname the server is bound to */
   readonly hostname: string;
-  /** Gracefully shutdown the server */
-  shutdown: () => Promise<void>;
+  /** Resolves once the server has stopped accepting requests */
+  readonly finished: Promise<void>;
+  /**
+   * Gracefully shutdown the server, waiting up to `drainTimeoutMs`
+   * (default 30000; 0 waits indefinitely) for in-flight requests.
+   * Resolves to true if they finished, false if the timeout elapsed first.
+   */
+  shutdown: (options?: { drainTimeoutMs?: number }) => Promise<boolean>;
   /** Async disposable - calls shutdown() when disposed */
   [Symbol.asyncDispose]: () => Promise<void>;
 };
@@ -75,3 +176,19 @@ export const serve = hostServe as (
   options: ServeOptions,
   handler: RequestHandler,
 ) => Server;
+
+/**
+ * Number of connections currently open to a server, or -1 if it has
+ * stopped. Lets a handler shed load when the server is saturated.
+ * 
+ * @example
+ * ```typescript
+ * const server = serve({ port: 3000 }, (req) => {
+ *   if (activeConnections(server.serverId) > 100) {
+ *     return new Response("Busy", { status: 503 });
+ *   }
+ *   return new Response("OK");
+ * });
+ * ```
+ */
+export const activeConnections = hostActiveConnections as (serverId: number) => number;
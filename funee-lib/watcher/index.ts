@@ -18,6 +18,17 @@ export type WatchEventKind = "create" | "modify" | "remove" | "access" | "other"
 export type WatchEvent = {
   kind: WatchEventKind;
   path: string;
+  /** Position of this event in the watcher's stream */
+  seq: number;
+};
+
+/**
+ * Result of a single watchPoll call
+ */
+type WatchPollResult = {
+  events: WatchEvent[];
+  nextCursor: number;
+  dropped: number;
 };
 
 /**
@@ -30,7 +41,7 @@ export type WatchOptions = {
 /**
  * Result type from watcher ops
  */
-type WatchResult = { type: "ok"; value: number } | { type: "error"; error: string };
+type WatchResult = { type: "ok"; value: number; pending: boolean } | { type: "error"; error: string };
 
 /**
  * Parse the result from watchStart
@@ -62,14 +73,16 @@ export type Watcher = AsyncIterable<WatchEvent> & {
 const createWatcher = (path: string, recursive: boolean): Watcher => {
   const watcherId = parseWatchResult(watchStart(path, recursive));
   let stopped = false;
+  let cursor = 0;
 
   const iterable: Watcher = {
     [Symbol.asyncIterator]: async function* () {
       while (!stopped) {
-        const eventsJson = watchPoll(watcherId);
-        if (eventsJson !== "null") {
-          const events = JSON.parse(eventsJson) as WatchEvent[];
-          for (const event of events) {
+        const resultJson = watchPoll(watcherId, cursor);
+        if (resultJson !== "null") {
+          const result = JSON.parse(resultJson) as WatchPollResult;
+          cursor = result.nextCursor;
+          for (const event of result.events) {
             yield event;
           }
         }
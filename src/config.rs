@@ -0,0 +1,116 @@
+//! `funee.json`: per-project defaults for CLI flags
+//!
+//! Read from `--config=<path>`, or else the nearest `funee.json` in the entry's
+//! directory (the working directory for `funee test`) or one of its ancestors. Flags given on the command line win over
+//! the values found here.
+//!
+//! A standalone import map (`--import-map=<path>` or `FUNEE_IMPORT_MAP`) is
+//! read here too, and its entries override the config's `imports`.
+
+use crate::diagnostics;
+use relative_path::RelativePath;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub const CONFIG_FILE_NAME: &str = "funee.json";
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Config {
+    /// Import map: bare specifiers and `prefix/` keys to URLs or paths
+    /// (`./` and `../` paths are relative to the config file)
+    pub imports: HashMap<String, String>,
+    /// Default for `--allow-fetch-schemes`
+    pub allow_fetch_schemes: Option<Vec<String>>,
+    /// Default for `--allow-import-hosts`
+    pub allow_import_hosts: Option<Vec<String>>,
+    /// Default for `--allow-insecure-fetch`
+    pub allow_insecure_fetch: bool,
+    /// Default for `--conditions`
+    pub conditions: Option<Vec<String>>,
+    /// Where fetched modules are cached (default: ~/.funee/cache)
+    pub cache_dir: Option<PathBuf>,
+    #[serde(flatten)]
+    unknown: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Config {
+    /// Load the config for a run starting in `dir`; the default config if there's no file
+    pub fn load(explicit: Option<&str>, dir: &Path) -> Result<Config, String> {
+        let path = match explicit {
+            Some(path) => std::env::current_dir()
+                .map(|cwd| cwd.join(path))
+                .unwrap_or_else(|_| PathBuf::from(path)),
+            None => match dir
+                .ancestors()
+                .map(|dir| dir.join(CONFIG_FILE_NAME))
+                .find(|path| path.is_file())
+            {
+                Some(path) => path,
+                None => return Ok(Config::default()),
+            },
+        };
+
+        let source = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Cannot read config '{}': {}", path.display(), e))?;
+        let mut config: Config = serde_json::from_str(&source)
+            .map_err(|e| format!("Invalid config '{}': {}", path.display(), e))?;
+        for key in config.unknown.keys() {
+            diagnostics::warning(format!("Unknown key '{}' in {}", key, path.display()));
+        }
+
+        let dir = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        resolve_relative_targets(&mut config.imports, &dir);
+        if let Some(cache_dir) = config.cache_dir.take() {
+            config.cache_dir = Some(dir.join(cache_dir));
+        }
+        Ok(config)
+    }
+}
+
+/// An import map file in the browser/Deno format: `{ "imports": { ... } }`
+#[derive(Deserialize)]
+struct ImportMapFile {
+    #[serde(default)]
+    imports: HashMap<String, String>,
+    #[serde(flatten)]
+    unknown: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Read the import map at `path` (relative to the working directory)
+///
+/// `./` and `../` targets are resolved against the map's directory. Keys other
+/// than `imports` (such as `scopes`) are warned about and ignored.
+pub fn load_import_map(path: &str) -> Result<HashMap<String, String>, String> {
+    let path = std::env::current_dir()
+        .map(|cwd| cwd.join(path))
+        .unwrap_or_else(|_| PathBuf::from(path));
+    let source = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Cannot read import map '{}': {}", path.display(), e))?;
+    let mut import_map: ImportMapFile = serde_json::from_str(&source)
+        .map_err(|e| format!("Invalid import map '{}': {}", path.display(), e))?;
+    for key in import_map.unknown.keys() {
+        diagnostics::warning(format!("Unsupported key '{}' in import map {}", key, path.display()));
+    }
+
+    resolve_relative_targets(&mut import_map.imports, path.parent().unwrap_or(Path::new("")));
+    Ok(import_map.imports)
+}
+
+/// Make `./` and `../` import map targets absolute, relative to `dir`
+fn resolve_relative_targets(imports: &mut HashMap<String, String>, dir: &Path) {
+    for target in imports.values_mut() {
+        if target.starts_with("./") || target.starts_with("../") {
+            let mut resolved = RelativePath::new(target.as_str())
+                .to_logical_path(dir)
+                .to_string_lossy()
+                .to_string();
+            // Keep prefix mappings usable as prefixes
+            if target.ends_with('/') && !resolved.ends_with('/') {
+                resolved.push('/');
+            }
+            *target = resolved;
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Diagnostic output for the funee CLI
+//!
+//! Every error and warning funee prints goes through this module so that
+//! formatting and color handling stay consistent:
+//! - `error:` labels are red, `warning:` labels are yellow
+//! - Color is used when stderr is a terminal
+//! - `NO_COLOR` (non-empty) always disables color
+//! - `FORCE_COLOR` (non-empty, not "0") enables color even when piped
+
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::sync::LazyLock;
+
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const CYAN: &str = "\x1b[36m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const RESET: &str = "\x1b[0m";
+
+static USE_COLOR: LazyLock<bool> = LazyLock::new(|| {
+    color_enabled(
+        std::env::var("NO_COLOR").ok().as_deref(),
+        std::env::var("FORCE_COLOR").ok().as_deref(),
+        std::io::stderr().is_terminal(),
+    )
+});
+
+/// Decide whether to colorize output given the environment and terminal state
+fn color_enabled(no_color: Option<&str>, force_color: Option<&str>, is_terminal: bool) -> bool {
+    if no_color.is_some_and(|v| !v.is_empty()) {
+        return false;
+    }
+    if let Some(force) = force_color {
+        if !force.is_empty() && force != "0" && force != "false" {
+            return true;
+        }
+    }
+    is_terminal
+}
+
+/// Whether diagnostics written to stderr should be colorized
+pub fn use_color() -> bool {
+    *USE_COLOR
+}
+
+fn paint(style: &str, text: &str) -> String {
+    if use_color() {
+        format!("{}{}{}", style, text, RESET)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Print an error diagnostic: `error: <message>`
+pub fn error(message: impl Display) {
+    eprintln!("{}: {}", paint(&format!("{}{}", BOLD, RED), "error"), message);
+}
+
+/// Print a warning diagnostic: `warning: <message>`
+pub fn warning(message: impl Display) {
+    eprintln!("{}: {}", paint(&format!("{}{}", BOLD, YELLOW), "warning"), message);
+}
+
+/// Print a secondary line attached to the previous diagnostic: `  --> <message>`
+pub fn note(message: impl Display) {
+    eprintln!("  {} {}", paint(CYAN, "-->"), message);
+}
+
+/// Print a code frame for a 1-based line and 0-based column of `source`
+pub fn code_frame(source: &str, line: usize, col: usize) {
+    eprint!("{}", format_code_frame(source, line, col, use_color()));
+}
+
+/// Render the line before, the offending line, and a caret under `col`
+fn format_code_frame(source: &str, line: usize, col: usize, color: bool) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    if line == 0 || line > lines.len() {
+        return String::new();
+    }
+
+    let style = |code: &str, text: &str| {
+        if color {
+            format!("{}{}{}", code, text, RESET)
+        } else {
+            text.to_string()
+        }
+    };
+
+    let width = line.to_string().len();
+    let mut out = String::new();
+    let first = if line > 1 { line - 1 } else { line };
+    for n in first..=line {
+        out.push_str(&style(DIM, &format!("{:>width$} | ", n, width = width)));
+        out.push_str(lines[n - 1]);
+        out.push('\n');
+    }
+    out.push_str(&style(DIM, &format!("{:>width$} | ", "", width = width)));
+    out.push_str(&" ".repeat(col));
+    out.push_str(&style(&format!("{}{}", BOLD, RED), "^"));
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_enabled() {
+        assert!(color_enabled(None, None, true));
+        assert!(!color_enabled(None, None, false));
+        assert!(!color_enabled(Some("1"), None, true));
+        assert!(!color_enabled(Some("1"), Some("1"), true));
+        assert!(color_enabled(Some(""), None, true));
+        assert!(color_enabled(None, Some("1"), false));
+        assert!(!color_enabled(None, Some("0"), false));
+    }
+
+    #[test]
+    fn test_format_code_frame() {
+        let source = "const a = 1;\nconst b = ;\n";
+        let frame = format_code_frame(source, 2, 10, false);
+        assert_eq!(frame, "1 | const a = 1;\n2 | const b = ;\n  |           ^\n");
+        assert_eq!(format_code_frame(source, 5, 0, false), "");
+    }
+}
@@ -1,5 +1,8 @@
 use std::rc::Rc;
-use swc_common::{BytePos, LineCol, SourceMap};
+use swc_common::{
+    comments::{Comment, CommentKind, Comments, SingleThreadedComments},
+    BytePos, LineCol, SourceMap,
+};
 use swc_ecma_ast::Module;
 use swc_ecma_codegen::{
     self,
@@ -7,7 +10,11 @@ use swc_ecma_codegen::{
     Emitter,
 };
 
-pub fn emit_module(cm: Rc<SourceMap>, module: Module) -> (Vec<(BytePos, LineCol)>, Vec<u8>) {
+pub fn emit_module(
+    cm: Rc<SourceMap>,
+    module: Module,
+    comments: Option<&dyn Comments>,
+) -> (Vec<(BytePos, LineCol)>, Vec<u8>) {
     let mut buf = vec![];
     let mut srcmap = vec![];
     {
@@ -21,7 +28,7 @@ pub fn emit_module(cm: Rc<SourceMap>, module: Module) -> (Vec<(BytePos, LineCol)
         let mut emitter = Emitter {
             cfg: swc_ecma_codegen::Config::default(),
             cm: cm,
-            comments: None,
+            comments,
             wr,
         };
 
@@ -30,3 +37,45 @@ pub fn emit_module(cm: Rc<SourceMap>, module: Module) -> (Vec<(BytePos, LineCol)
 
     (srcmap, buf)
 }
+
+/// Whether a comment is a license banner that must survive bundling:
+/// `/*! ... */`, or any comment mentioning `@license` or `@preserve`
+fn is_license_comment(comment: &Comment) -> bool {
+    comment.text.starts_with('!')
+        || comment.text.contains("@license")
+        || comment.text.contains("@preserve")
+}
+
+/// Remove license banners from `comments` and return them as source text, in
+/// source order and without duplicates
+///
+/// They're emitted as a block ahead of the bundled code, so they survive even
+/// when the declaration they were attached to is tree-shaken.
+pub fn take_license_comments(comments: &SingleThreadedComments) -> Vec<String> {
+    let (mut leading, mut trailing) = comments.borrow_all_mut();
+    let mut found: Vec<(BytePos, String)> = vec![];
+    for map in [&mut *leading, &mut *trailing] {
+        for (pos, list) in map.iter_mut() {
+            list.retain(|comment| {
+                if !is_license_comment(comment) {
+                    return true;
+                }
+                let text = match comment.kind {
+                    CommentKind::Block => format!("/*{}*/", comment.text),
+                    CommentKind::Line => format!("//{}", comment.text),
+                };
+                found.push((*pos, text));
+                false
+            });
+        }
+    }
+
+    found.sort();
+    let mut banners: Vec<String> = vec![];
+    for (_, text) in found {
+        if !banners.contains(&text) {
+            banners.push(text);
+        }
+    }
+    banners
+}
@@ -1,23 +1,38 @@
 mod capture_closure;
 mod closure;
+mod data_url;
 mod declaration;
 mod detect_macro_calls;
 mod get_inline_source_map;
 mod get_module_declarations;
 mod get_references_from_declaration;
+mod import_map;
+mod integrity;
+mod json_module;
 mod load_module_declaration;
+mod namespace_import;
 mod macro_runtime;
+mod npm_specifier;
+mod ordered_json;
+mod raw_ops_lint;
+mod resolve_error;
+mod resolve_package;
 mod source_graph;
 mod source_graph_to_js_execution_code;
+mod virtual_modules;
 
-use crate::{funee_identifier::FuneeIdentifier, http_loader::HttpFileLoader, run_js::run_js};
+use crate::{diagnostics, funee_identifier::FuneeIdentifier, http_loader::HttpFileLoader, log_locations, run_js::run_js, trace};
 use ast::Expr;
 use deno_core::{error::AnyError, OpDecl};
 use std::collections::HashMap;
 use swc_common::FileLoader;
 use swc_ecma_ast as ast;
 
+use self::get_inline_source_map::get_inline_source_map;
 use self::source_graph::{LoadParams, SourceGraph};
+pub use self::source_graph_to_js_execution_code::EmitFormat;
+pub use self::resolve_error::{ResolveError, SourceLocation};
+pub use self::resolve_package::default_export_conditions;
 
 pub struct ExecutionRequest {
     pub expression: Expr,
@@ -26,6 +41,21 @@ pub struct ExecutionRequest {
     pub file_loader: Box<dyn FileLoader + Sync + Send>,
     /// Path to the funee standard library (funee-lib/index.ts)
     pub funee_lib_path: Option<String>,
+    /// Conditions used to pick package.json "exports" entries (default: import, default)
+    pub export_conditions: Vec<String>,
+    /// In-memory module sources keyed by name, importable as "virtual:<name>"
+    pub virtual_modules: HashMap<String, String>,
+    /// Keep comments (at least `/*!`, `@license` and `@preserve` banners) in the bundle
+    pub preserve_comments: bool,
+    /// Reject `Deno.core` access in user modules instead of warning about it
+    pub deny_raw_ops: bool,
+    /// Disable host capabilities: fs, network and process imports become throwing stubs
+    /// (the embedder should also leave the matching ops out of `host_functions`)
+    pub sandbox: bool,
+    /// Hosts fetched modules may import from besides their own and the entry's
+    pub allowed_import_hosts: Vec<String>,
+    /// Import map: specifiers and `prefix/` keys to URLs or absolute paths
+    pub import_map: HashMap<String, String>,
 }
 
 impl Default for ExecutionRequest {
@@ -38,45 +68,110 @@ impl Default for ExecutionRequest {
             host_functions: HashMap::new(),
             file_loader: Box::new(HttpFileLoader::default()),
             funee_lib_path: None,
+            export_conditions: default_export_conditions(),
+            virtual_modules: HashMap::new(),
+            preserve_comments: false,
+            deny_raw_ops: false,
+            sandbox: false,
+            allowed_import_hosts: vec![],
+            import_map: HashMap::new(),
         }
     }
 }
 
+/// Bundled code with its source map kept separate
+pub struct EmittedCode {
+    pub code: String,
+    /// Maps positions in `code` back to the original modules
+    pub source_map: sourcemap::SourceMap,
+}
+
 impl ExecutionRequest {
-    /// Build the source graph and emit bundled JavaScript code
-    pub fn emit(self) -> String {
+    /// Build the source graph and emit bundled JavaScript code, with the source map inlined
+    pub fn emit(self) -> Result<String, ResolveError> {
+        self.emit_as(EmitFormat::Esm)
+    }
+
+    /// Like `emit`, in the given module format
+    pub fn emit_as(self, format: EmitFormat) -> Result<String, ResolveError> {
+        let emitted = self.emit_with_map_as(format)?;
+        Ok(format!("{}{}", emitted.code, get_inline_source_map(&emitted.source_map)))
+    }
+
+    /// Build the source graph and emit bundled JavaScript code, returning the
+    /// source map separately instead of inlining it
+    pub fn emit_with_map(self) -> Result<EmittedCode, ResolveError> {
+        self.emit_with_map_as(EmitFormat::Esm)
+    }
+
+    /// Like `emit_with_map`, in the given module format
+    pub fn emit_with_map_as(self, format: EmitFormat) -> Result<EmittedCode, ResolveError> {
         let source_graph = SourceGraph::load(LoadParams {
             scope: self.scope,
             expression: self.expression,
             host_functions: self.host_functions.keys().cloned().collect(),
             file_loader: self.file_loader,
             funee_lib_path: self.funee_lib_path,
-        });
+            export_conditions: self.export_conditions,
+            virtual_modules: self.virtual_modules,
+            preserve_comments: self.preserve_comments,
+            deny_raw_ops: self.deny_raw_ops,
+            sandbox: self.sandbox,
+            allowed_import_hosts: self.allowed_import_hosts,
+            import_map: self.import_map,
+        })?;
 
-        source_graph.into_js_execution_code()
+        let (code, source_map) = source_graph.into_js_code_with_map(format)?;
+        Ok(EmittedCode { code, source_map })
     }
 
     /// Build and execute the bundled code
+    ///
+    /// Resolution errors are reported before being returned, like errors
+    /// thrown by the code itself.
     pub fn execute(self) -> Result<(), AnyError> {
-        let source_graph = SourceGraph::load(LoadParams {
+        let bundled = SourceGraph::load(LoadParams {
             scope: self.scope,
             expression: self.expression,
             host_functions: self.host_functions.keys().cloned().collect(),
             file_loader: self.file_loader,
             funee_lib_path: self.funee_lib_path,
+            export_conditions: self.export_conditions,
+            virtual_modules: self.virtual_modules,
+            preserve_comments: self.preserve_comments,
+            deny_raw_ops: self.deny_raw_ops,
+            sandbox: self.sandbox,
+            allowed_import_hosts: self.allowed_import_hosts,
+            import_map: self.import_map,
+        })
+        .and_then(|source_graph| {
+            if log_locations::enabled() {
+                let (code, source_map) = source_graph.into_js_execution_code_with_map()?;
+                let inline_map = get_inline_source_map(&source_map);
+                log_locations::set_source_map(source_map);
+                Ok(format!("{}{}", code, inline_map))
+            } else {
+                source_graph.into_js_execution_code()
+            }
         });
-
-        let execution_code = source_graph.into_js_execution_code();
+        let execution_code = match bundled {
+            Ok(code) => code,
+            Err(error) => {
+                error.report();
+                return Err(error.into());
+            }
+        };
 
         let runtime = tokio::runtime::Builder::new_current_thread()
             .enable_all()
             .build()?;
 
+        let _span = trace::span("execute");
         if let Err(error) = runtime.block_on(run_js(
             &execution_code,
             self.host_functions.into_values().collect(),
         )) {
-            eprintln!("error: {}", error);
+            diagnostics::error(&error);
             return Err(error);
         }
 
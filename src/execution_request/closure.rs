@@ -1,6 +1,11 @@
-use crate::funee_identifier::FuneeIdentifier;
+use crate::{emit_module::emit_module, funee_identifier::FuneeIdentifier};
 use std::collections::HashMap;
-use swc_ecma_ast::Expr;
+use std::rc::Rc;
+use swc_common::{SourceMap, SyntaxContext};
+use swc_ecma_ast::{
+    ArrayLit, Expr, ExprOrSpread, ExprStmt, Ident, IdentName, KeyValueProp, Lit, Module,
+    ModuleItem, NewExpr, ObjectLit, Prop, PropName, PropOrSpread, Stmt, Str,
+};
 
 /// A Closure captures an expression and its out-of-scope references
 /// This is used for macro arguments to preserve the AST and context
@@ -20,4 +25,102 @@ impl Closure {
             references,
         }
     }
+
+    /// Build the runtime value of this closure, shaped like funee's `Closure()`:
+    /// `{ expression: "<source>", references: new Map([["name", { uri, name }], ...]) }`
+    ///
+    /// The expression is serialized as written, so it lines up with the local
+    /// names used as keys in `references`. References are sorted by name to
+    /// keep the output deterministic.
+    pub fn to_runtime_value(&self, cm: Rc<SourceMap>) -> Expr {
+        let mut references: Vec<_> = self.references.iter().collect();
+        references.sort_by(|a, b| a.0.cmp(b.0));
+
+        let entries = references
+            .into_iter()
+            .map(|(local_name, identifier)| {
+                array(vec![
+                    string(local_name),
+                    object(vec![
+                        ("uri", string(&identifier.uri)),
+                        ("name", string(&identifier.name)),
+                    ]),
+                ])
+            })
+            .collect();
+
+        object(vec![
+            ("expression", string(&self.expression_source(cm))),
+            (
+                "references",
+                Expr::New(NewExpr {
+                    span: Default::default(),
+                    ctxt: SyntaxContext::empty(),
+                    callee: Box::new(Expr::Ident(Ident::new(
+                        "Map".into(),
+                        Default::default(),
+                        SyntaxContext::empty(),
+                    ))),
+                    args: Some(vec![ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(array(entries)),
+                    }]),
+                    type_args: None,
+                }),
+            ),
+        ])
+    }
+
+    /// JavaScript source of the captured expression
+    fn expression_source(&self, cm: Rc<SourceMap>) -> String {
+        let module = Module {
+            span: Default::default(),
+            body: vec![ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+                span: Default::default(),
+                expr: Box::new(self.expression.clone()),
+            }))],
+            shebang: None,
+        };
+        let (_, buf) = emit_module(cm, module, None);
+        let code = String::from_utf8(buf).expect("failed to convert to utf8");
+        code.trim().trim_end_matches(';').to_string()
+    }
+}
+
+fn string(value: &str) -> Expr {
+    Expr::Lit(Lit::Str(Str {
+        span: Default::default(),
+        value: swc_atoms::Atom::from(value).into(),
+        raw: None,
+    }))
+}
+
+fn array(elems: Vec<Expr>) -> Expr {
+    Expr::Array(ArrayLit {
+        span: Default::default(),
+        elems: elems
+            .into_iter()
+            .map(|expr| {
+                Some(ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(expr),
+                })
+            })
+            .collect(),
+    })
+}
+
+fn object(props: Vec<(&str, Expr)>) -> Expr {
+    Expr::Object(ObjectLit {
+        span: Default::default(),
+        props: props
+            .into_iter()
+            .map(|(key, value)| {
+                PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(IdentName::new(key.into(), Default::default())),
+                    value: Box::new(value),
+                })))
+            })
+            .collect(),
+    })
 }
@@ -0,0 +1,90 @@
+//! `data:` URL imports, e.g. `import { x } from "data:text/typescript;base64,..."`
+//!
+//! The module source is the URL's own payload, so nothing is fetched or read.
+//! Both the base64 form and the percent-encoded form are accepted.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+/// Media types a data: URL module may declare (an empty one is allowed too)
+const MODULE_MEDIA_TYPES: &[&str] = &[
+    "text/typescript",
+    "application/typescript",
+    "text/javascript",
+    "application/javascript",
+    "application/json",
+];
+
+/// Check if a URI is a `data:` URL
+pub fn is_data_uri(uri: &str) -> bool {
+    uri.starts_with("data:")
+}
+
+/// Decode the module source carried by a `data:` URL
+pub fn decode_data_url(uri: &str) -> Result<String, String> {
+    let rest = uri
+        .strip_prefix("data:")
+        .ok_or_else(|| "not a data: URL".to_string())?;
+    let (header, payload) = rest
+        .split_once(',')
+        .ok_or_else(|| "missing ',' between the media type and the data".to_string())?;
+
+    let mut params = header.split(';');
+    let media_type = params.next().unwrap_or("").trim().to_ascii_lowercase();
+    if !media_type.is_empty() && !MODULE_MEDIA_TYPES.contains(&media_type.as_str()) {
+        return Err(format!("unsupported media type '{}', expected TypeScript, JavaScript or JSON", media_type));
+    }
+    let base64 = params.any(|param| param.trim().eq_ignore_ascii_case("base64"));
+
+    let bytes = percent_decode(payload)?;
+    let bytes = if base64 {
+        STANDARD
+            .decode(bytes.iter().filter(|byte| !byte.is_ascii_whitespace()).copied().collect::<Vec<_>>())
+            .map_err(|e| format!("invalid base64: {}", e))?
+    } else {
+        bytes
+    };
+    String::from_utf8(bytes).map_err(|_| "the decoded source isn't valid UTF-8".to_string())
+}
+
+/// Replace `%XX` escapes with the bytes they stand for
+fn percent_decode(text: &str) -> Result<Vec<u8>, String> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let escape = text.get(i + 1..i + 3).filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()));
+            let escape = escape.ok_or_else(|| format!("invalid percent escape at offset {}", i))?;
+            decoded.push(u8::from_str_radix(escape, 16).unwrap());
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_data_url() {
+        // "export const x = 1;"
+        assert_eq!(
+            decode_data_url("data:text/typescript;base64,ZXhwb3J0IGNvbnN0IHggPSAxOw==").as_deref(),
+            Ok("export const x = 1;")
+        );
+        assert_eq!(
+            decode_data_url("data:application/javascript,export%20const%20y%20%3D%202%3B").as_deref(),
+            Ok("export const y = 2;")
+        );
+        assert_eq!(decode_data_url("data:,export const z = 3;").as_deref(), Ok("export const z = 3;"));
+
+        assert!(decode_data_url("data:text/typescript;base64").unwrap_err().contains("missing ','"));
+        assert!(decode_data_url("data:text/typescript;base64,!!!").unwrap_err().contains("invalid base64"));
+        assert!(decode_data_url("data:image/png;base64,AAAA").unwrap_err().contains("unsupported media type"));
+        assert!(decode_data_url("data:,100%").unwrap_err().contains("percent escape"));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::funee_identifier::FuneeIdentifier;
-use super::closure::Closure;
-use swc_common::SyntaxContext;
+use super::{closure::Closure, namespace_import::NAMESPACE};
+use std::rc::Rc;
+use swc_common::{SourceMap, SyntaxContext};
 use swc_ecma_ast::{
     BlockStmt, CallExpr, Callee, Decl, Expr, ExprOrSpread, ExprStmt, FnDecl,
     FnExpr, Ident, IdentName, MemberExpr, MemberProp, ModuleItem, Param, Pat, RestPat, ReturnStmt, Stmt,
@@ -35,7 +36,9 @@ fn ident_name(name: &str) -> IdentName {
 }
 
 impl Declaration {
-    pub fn into_module_item(self, name: String) -> ModuleItem {
+    /// `cm` is the source map the declaration's spans belong to, used to
+    /// serialize retained closures
+    pub fn into_module_item(self, name: String, cm: &Rc<SourceMap>) -> ModuleItem {
         ModuleItem::Stmt(match self {
             Declaration::FnDecl(mut fn_decl) => {
                 fn_decl.ident.sym = name.into();
@@ -85,9 +88,7 @@ impl Declaration {
                 })))
             }
             Declaration::ClosureValue(closure) => {
-                // Emit closure expression wrapped in a Closure object construction
-                // TODO: Emit proper Closure({ expression: ..., references: ... })
-                // For now, just emit the expression as a placeholder
+                // Generate: var name = { expression: "...", references: new Map([...]) };
                 Stmt::Decl(Decl::Var(Box::new(VarDecl {
                     span: Default::default(),
                     ctxt: SyntaxContext::empty(),
@@ -96,7 +97,7 @@ impl Declaration {
                     decls: vec![VarDeclarator {
                         span: Default::default(),
                         name: Pat::Ident(ident(&name).into()),
-                        init: Some(Box::new(closure.expression.clone())),
+                        init: Some(Box::new(closure.to_runtime_value(cm.clone()))),
                         definite: false,
                     }],
                 })))
@@ -160,8 +161,19 @@ impl Declaration {
             }
             Declaration::HostModule(namespace, export_name) => {
                 // Generate: var name = __host_namespace.export_name;
+                // (or `var name = __host_namespace;` for `import * as name`)
                 // The __host_* objects are defined in the bundle preamble
                 let host_obj_name = format!("__host_{}", namespace.replace('/', "_"));
+                let host_obj = Expr::Ident(ident(&host_obj_name));
+                let init = if export_name == NAMESPACE {
+                    host_obj
+                } else {
+                    Expr::Member(MemberExpr {
+                        span: Default::default(),
+                        obj: Box::new(host_obj),
+                        prop: MemberProp::Ident(ident_name(&export_name)),
+                    })
+                };
                 Stmt::Decl(Decl::Var(Box::new(VarDecl {
                     span: Default::default(),
                     ctxt: SyntaxContext::empty(),
@@ -170,11 +182,7 @@ impl Declaration {
                     decls: vec![VarDeclarator {
                         span: Default::default(),
                         name: Pat::Ident(ident(&name).into()),
-                        init: Some(Box::new(Expr::Member(MemberExpr {
-                            span: Default::default(),
-                            obj: Box::new(Expr::Ident(ident(&host_obj_name))),
-                            prop: MemberProp::Ident(ident_name(&export_name)),
-                        }))),
+                        init: Some(Box::new(init)),
                         definite: false,
                     }],
                 })))
@@ -6,9 +6,28 @@ use swc_common::SourceMap;
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD;
 
-pub fn get_inline_source_map(cm: &Rc<SourceMap>, srcmap: &mut Vec<(BytePos, LineCol)>) -> String {
+/// Build the source map for an emitted module
+///
+/// `line_offset` is the number of lines placed ahead of the module in the final
+/// output (runtime preamble, license banners), so mappings point into that output.
+pub fn build_source_map(
+    cm: &Rc<SourceMap>,
+    srcmap: &mut [(BytePos, LineCol)],
+    line_offset: u32,
+) -> sourcemap::SourceMap {
+    for (_, line_col) in srcmap.iter_mut() {
+        line_col.line += line_offset;
+    }
     let srcmap = cm.build_source_map(srcmap, None, DefaultSourceMapGenConfig);
 
+    // swc builds its own copy of the sourcemap types; re-read the JSON so
+    // embedders get the public `sourcemap` crate's
+    let mut output: Vec<u8> = vec![];
+    srcmap.to_writer(&mut output).unwrap();
+    sourcemap::SourceMap::from_slice(&output).unwrap()
+}
+
+pub fn get_inline_source_map(srcmap: &sourcemap::SourceMap) -> String {
     let mut output: Vec<u8> = vec![];
     srcmap.to_writer(&mut output).unwrap();
 
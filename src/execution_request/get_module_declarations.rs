@@ -1,9 +1,10 @@
-use super::declaration::Declaration;
+use super::{declaration::Declaration, namespace_import::NAMESPACE};
 use crate::funee_identifier::FuneeIdentifier;
 use std::{collections::HashMap, path::Path};
+use swc_common::Span;
 use swc_ecma_ast::{
-    Callee, Decl, DefaultDecl, ExportSpecifier, Expr, ImportSpecifier, Module, ModuleDecl,
-    ModuleExportName, ModuleItem, Pat, Stmt,
+    Callee, Decl, DefaultDecl, ExportSpecifier, Expr, ImportSpecifier, Lit, Module, ModuleDecl,
+    ModuleExportName, ModuleItem, Pat, Prop, PropName, PropOrSpread, Stmt,
 };
 
 pub fn get_module_declarations(module: Module) -> HashMap<String, ModuleDeclaration> {
@@ -15,9 +16,72 @@ pub fn get_module_declarations(module: Module) -> HashMap<String, ModuleDeclarat
     )
 }
 
+/// Import attributes of a module's imports and re-exports, keyed by
+/// specifier and then attribute name
+///
+/// `import { x } from "./m.ts" with { integrity: "sha256-..." }` pins the
+/// imported module's contents; the hash is checked when it's loaded.
+/// `with { type: "json" }` loads the module as JSON whatever its extension.
+pub fn get_import_attributes(module: &Module) -> HashMap<String, HashMap<String, String>> {
+    module
+        .body
+        .iter()
+        .filter_map(|item| {
+            let (src, with) = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => (&decl.src, &decl.with),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(decl)) => (decl.src.as_ref()?, &decl.with),
+                ModuleItem::ModuleDecl(ModuleDecl::ExportAll(decl)) => (&decl.src, &decl.with),
+                _ => return None,
+            };
+            let attributes = with
+                .as_ref()?
+                .props
+                .iter()
+                .filter_map(|prop| match prop {
+                    PropOrSpread::Prop(prop) => match &**prop {
+                        Prop::KeyValue(kv) => {
+                            let key = match &kv.key {
+                                PropName::Ident(ident) => ident.sym.to_string(),
+                                PropName::Str(s) => wtf8_to_string(&s.value),
+                                _ => return None,
+                            };
+                            match &*kv.value {
+                                Expr::Lit(Lit::Str(value)) => Some((key, wtf8_to_string(&value.value))),
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    },
+                    PropOrSpread::Spread(_) => None,
+                })
+                .collect();
+            Some((wtf8_to_string(&src.value), attributes))
+        })
+        .collect()
+}
+
+/// Specifiers of a module's `export * from "..."` re-exports, in order, each
+/// with its span
+///
+/// Names the module doesn't declare itself are looked up behind these.
+pub fn get_star_exports(module: &Module) -> Vec<(String, Span)> {
+    module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportAll(decl)) if !decl.type_only => {
+                Some((wtf8_to_string(&decl.src.value), decl.src.span))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[derive(Clone)]
 pub struct ModuleDeclaration {
-    #[allow(dead_code)]
     pub exported: bool,
+    /// Where it's declared in its module; for imports and re-exports, the specifier
+    pub span: Span,
     pub declaration: Declaration,
 }
 
@@ -68,6 +132,7 @@ fn get_module_declarations_from_module_item(
                 "default".to_string(),
                 ModuleDeclaration {
                     exported: true,
+                    span: func.function.span,
                     declaration: Declaration::FnExpr(func),
                 },
             )],
@@ -78,6 +143,7 @@ fn get_module_declarations_from_module_item(
                 atom_to_string(&fn_decl.ident.sym),
                 ModuleDeclaration {
                     exported: true,
+                    span: fn_decl.ident.span,
                     declaration: Declaration::FnDecl(fn_decl),
                 },
             )],
@@ -98,6 +164,7 @@ fn get_module_declarations_from_module_item(
                             atom_to_string(&ident.sym),
                             ModuleDeclaration {
                                 exported: true,
+                                span: declarator.span,
                                 declaration,
                             },
                         ))
@@ -119,6 +186,7 @@ fn get_module_declarations_from_module_item(
                     }),
                     ModuleDeclaration {
                         exported: true,
+                        span: n.span,
                         declaration: Declaration::FuneeIdentifier(FuneeIdentifier {
                             name: get_name_from_module_export_name(&n.orig),
                             uri: match decl.src {
@@ -129,7 +197,18 @@ fn get_module_declarations_from_module_item(
                     },
                 )),
                 ExportSpecifier::Default(_) => None,
-                ExportSpecifier::Namespace(_) => None,
+                // export * as ns from "..."
+                ExportSpecifier::Namespace(n) => Some((
+                    get_name_from_module_export_name(&n.name),
+                    ModuleDeclaration {
+                        exported: true,
+                        span: n.span,
+                        declaration: Declaration::FuneeIdentifier(FuneeIdentifier {
+                            name: NAMESPACE.to_string(),
+                            uri: wtf8_to_string(&decl.src.as_ref()?.value),
+                        }),
+                    },
+                )),
             })
             .collect(),
         ModuleItem::ModuleDecl(ModuleDecl::Import(decl)) => decl
@@ -140,6 +219,7 @@ fn get_module_declarations_from_module_item(
                     atom_to_string(&n.local.sym),
                     ModuleDeclaration {
                         exported: false,
+                        span: n.span,
                         declaration: Declaration::FuneeIdentifier(FuneeIdentifier {
                             name: match n.imported {
                                 Some(ref imported) => get_name_from_module_export_name(imported),
@@ -153,19 +233,31 @@ fn get_module_declarations_from_module_item(
                     atom_to_string(&n.local.sym),
                     ModuleDeclaration {
                         exported: false,
+                        span: n.span,
                         declaration: Declaration::FuneeIdentifier(FuneeIdentifier {
                             name: "default".to_string(),
                             uri: get_import_decl_uri(&current_uri, &decl),
                         }),
                     },
                 )),
-                ImportSpecifier::Namespace(_) => None,
+                ImportSpecifier::Namespace(n) => Some((
+                    atom_to_string(&n.local.sym),
+                    ModuleDeclaration {
+                        exported: false,
+                        span: n.span,
+                        declaration: Declaration::FuneeIdentifier(FuneeIdentifier {
+                            name: NAMESPACE.to_string(),
+                            uri: get_import_decl_uri(&current_uri, &decl),
+                        }),
+                    },
+                )),
             })
             .collect(),
         ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultExpr(expr)) => vec![(
             "default".to_string(),
             ModuleDeclaration {
                 exported: true,
+                span: expr.span,
                 declaration: Declaration::VarInit(*expr.expr),
             },
         )],
@@ -173,6 +265,7 @@ fn get_module_declarations_from_module_item(
             atom_to_string(&func.ident.sym),
             ModuleDeclaration {
                 exported: false,
+                span: func.ident.span,
                 declaration: Declaration::FnDecl(func),
             },
         )],
@@ -194,6 +287,7 @@ fn get_module_declarations_from_module_item(
                         atom_to_string(&ident.sym),
                         ModuleDeclaration {
                             exported: false,
+                            span: declarator.span,
                             declaration,
                         },
                     ))
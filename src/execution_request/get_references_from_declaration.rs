@@ -1,16 +1,18 @@
 use super::declaration::Declaration;
-use std::collections::{HashMap, HashSet};
-use swc_common::{Globals, Mark, GLOBALS};
+use std::collections::HashMap;
+use swc_common::{Globals, Mark, Span, DUMMY_SP, GLOBALS};
 use swc_ecma_ast::Ident;
 use swc_ecma_transforms_base::resolver;
 use swc_ecma_visit::{
     noop_visit_mut_type, noop_visit_type, Visit, VisitMut, VisitMutWith, VisitWith,
 };
 
+/// Names the declaration refers to from outside itself, each with the span
+/// of its first use
 pub fn get_references_from_declaration(
     decl: &mut Declaration,
     unresolved_mark: (&Globals, Mark),
-) -> HashSet<String> {
+) -> HashMap<String, Span> {
     match decl {
         Declaration::FnDecl(n) => get_references_from_ast(&mut n.function, unresolved_mark),
         Declaration::FnExpr(n) => get_references_from_ast(n, unresolved_mark),
@@ -20,18 +22,18 @@ pub fn get_references_from_declaration(
         Declaration::ClosureValue(closure) => {
             // Closure already has its references captured
             // Return the reference names from the closure
-            closure.references.keys().cloned().collect()
+            closure.references.keys().map(|name| (name.clone(), DUMMY_SP)).collect()
         }
-        Declaration::FuneeIdentifier(_) => HashSet::new(),
-        Declaration::HostFn(_) => HashSet::new(),
-        Declaration::HostModule(_, _) => HashSet::new(),
+        Declaration::FuneeIdentifier(_) => HashMap::new(),
+        Declaration::HostFn(_) => HashMap::new(),
+        Declaration::HostModule(_, _) => HashMap::new(),
     }
 }
 
 #[derive(Default)]
 pub(super) struct ResolveReferences {
     pub unresolved_mark: Mark,
-    pub references: HashSet<String>,
+    pub references: HashMap<String, Span>,
 }
 
 impl Visit for ResolveReferences {
@@ -39,7 +41,7 @@ impl Visit for ResolveReferences {
 
     fn visit_ident(&mut self, n: &Ident) {
         if n.ctxt.has_mark(self.unresolved_mark) {
-            self.references.insert(n.sym.as_str().to_string());
+            self.references.entry(n.sym.as_str().to_string()).or_insert(n.span);
         }
     }
 }
@@ -47,7 +49,7 @@ impl Visit for ResolveReferences {
 pub fn get_references_from_ast<T: Clone + VisitMutWith<dyn VisitMut> + VisitWith<ResolveReferences>>(
     ast: &mut T,
     unresolved_mark: (&Globals, Mark),
-) -> HashSet<String> {
+) -> HashMap<String, Span> {
     GLOBALS.set(unresolved_mark.0, || {
         let resolver = &mut resolver(unresolved_mark.1, Mark::new(), true);
         ast.visit_mut_with(resolver);
@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+/// Resolve `specifier` through an import map's `imports`
+///
+/// An exact key wins; otherwise the longest key ending in `/` that prefixes the
+/// specifier replaces that prefix, e.g. `"std/": "https://deno.land/std@0.200.0/"`
+/// maps `std/path/mod.ts` to `https://deno.land/std@0.200.0/path/mod.ts`.
+/// Returns None if nothing matches.
+pub fn resolve_import_map(specifier: &str, imports: &HashMap<String, String>) -> Option<String> {
+    if let Some(target) = imports.get(specifier) {
+        return Some(target.clone());
+    }
+    imports
+        .iter()
+        .filter(|(key, _)| key.ends_with('/') && specifier.starts_with(key.as_str()))
+        .max_by_key(|(key, _)| key.len())
+        .map(|(key, target)| format!("{}{}", target, &specifier[key.len()..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_import_map() {
+        let imports = HashMap::from([
+            ("lodash".to_string(), "https://esm.sh/lodash-es@4.17.21".to_string()),
+            ("std/".to_string(), "https://deno.land/std@0.200.0/".to_string()),
+            ("std/path/".to_string(), "/vendor/path/".to_string()),
+        ]);
+
+        assert_eq!(
+            resolve_import_map("lodash", &imports).as_deref(),
+            Some("https://esm.sh/lodash-es@4.17.21")
+        );
+        assert_eq!(
+            resolve_import_map("std/fs/mod.ts", &imports).as_deref(),
+            Some("https://deno.land/std@0.200.0/fs/mod.ts")
+        );
+        // The longest matching prefix wins
+        assert_eq!(resolve_import_map("std/path/mod.ts", &imports).as_deref(), Some("/vendor/path/mod.ts"));
+        assert_eq!(resolve_import_map("lodash/fp", &imports), None);
+        assert_eq!(resolve_import_map("./local.ts", &imports), None);
+    }
+}
@@ -0,0 +1,56 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+/// Check module source against a Subresource Integrity value
+///
+/// `integrity` holds one or more space-separated `<algorithm>-<base64 digest>`
+/// tokens (sha256, sha384 or sha512); the source matches if any token does.
+/// On mismatch, returns the source's actual hash using the first token's
+/// algorithm, for the error message.
+pub fn verify_integrity(source: &str, integrity: &str) -> Result<(), String> {
+    let mut actual = None;
+    for token in integrity.split_whitespace() {
+        // Options after "?" are reserved by the SRI spec and ignored
+        let token = token.split('?').next().unwrap_or_default();
+        let Some((algorithm, expected)) = token.split_once('-') else {
+            continue;
+        };
+        let Some(digest) = digest(algorithm, source.as_bytes()) else {
+            continue;
+        };
+        let encoded = STANDARD.encode(digest);
+        if encoded == expected {
+            return Ok(());
+        }
+        actual.get_or_insert_with(|| format!("{}-{}", algorithm, encoded));
+    }
+    Err(actual.unwrap_or_else(|| format!("no supported hash in '{}' (expected sha256, sha384 or sha512)", integrity)))
+}
+
+fn digest(algorithm: &str, data: &[u8]) -> Option<Vec<u8>> {
+    match algorithm {
+        "sha256" => Some(Sha256::digest(data).to_vec()),
+        "sha384" => Some(Sha384::digest(data).to_vec()),
+        "sha512" => Some(Sha512::digest(data).to_vec()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_integrity() {
+        // printf 'hello' | openssl dgst -sha256 -binary | base64
+        let hello = "sha256-LPJNul+wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ=";
+        assert_eq!(verify_integrity("hello", hello), Ok(()));
+        assert_eq!(
+            verify_integrity("hello!", hello),
+            Err("sha256-zgYJL7lI2f+sfRo3bkBLJrdXW8wR7gWkYV/vT+w6MIs=".to_string())
+        );
+        // Any matching token is enough
+        assert_eq!(verify_integrity("hello", &format!("sha512-bogus {}", hello)), Ok(()));
+        assert!(verify_integrity("hello", "md5-XUFAKrxLKna5cZ2REBfFkg==").is_err());
+    }
+}
@@ -0,0 +1,221 @@
+//! JSON modules, e.g. `import config from "./config.json"`
+//!
+//! The document becomes one synthetic declaration holding it as an object
+//! literal. The default export and every top-level key are declared in terms
+//! of it, so `import { name }` and `config.name` are the same value.
+
+use super::{
+    declaration::Declaration,
+    get_module_declarations::ModuleDeclaration,
+    ordered_json::Json,
+    resolve_error::{ResolveError, SourceLocation},
+};
+use std::{collections::HashMap, path::Path, rc::Rc};
+use swc_common::{SourceMap, SyntaxContext, DUMMY_SP};
+use swc_ecma_ast::{
+    ArrayLit, Bool, ComputedPropName, Expr, ExprOrSpread, Ident, KeyValueProp, Lit, MemberExpr,
+    MemberProp, Null, Number, ObjectLit, Prop, PropName, PropOrSpread, Str, UnaryExpr, UnaryOp,
+};
+
+/// Check if a URI names a JSON document by its extension (or media type, for
+/// `data:` URLs)
+pub fn is_json_uri(uri: &str) -> bool {
+    if let Some(data) = uri.strip_prefix("data:") {
+        return data.to_ascii_lowercase().starts_with("application/json");
+    }
+    let path = uri.split(['?', '#']).next().unwrap_or(uri);
+    path.to_ascii_lowercase().ends_with(".json")
+}
+
+/// Read and parse a JSON module into its declarations
+///
+/// Invalid JSON is returned as `ResolveError::InvalidJson`, located at the
+/// offending position.
+pub fn load_json_module(cm: &Rc<SourceMap>, path: &Path) -> Result<HashMap<String, ModuleDeclaration>, ResolveError> {
+    let uri = path.to_string_lossy().to_string();
+    let fm = cm.load_file(path).map_err(|e| ResolveError::Unreadable {
+        uri: uri.clone(),
+        reason: e.to_string(),
+    })?;
+    let value: Json = serde_json::from_str(&fm.src).map_err(|e| {
+        // serde_json appends the position, which the location already carries
+        let message = e.to_string();
+        let position = format!(" at line {} column {}", e.line(), e.column());
+        ResolveError::InvalidJson {
+            reason: message.strip_suffix(&position).unwrap_or(&message).to_string(),
+            location: Some(SourceLocation {
+                file: uri.clone(),
+                line: e.line(),
+                col: e.column().saturating_sub(1),
+                source: fm.src.to_string(),
+            }),
+            uri,
+        }
+    })?;
+    Ok(json_module_declarations(value))
+}
+
+/// Declarations of a JSON module: the document under a name no key uses,
+/// `default` referring to it, and each top-level key (except "default") as a
+/// member of it
+fn json_module_declarations(value: Json) -> HashMap<String, ModuleDeclaration> {
+    let keys: Vec<String> = match &value {
+        Json::Object(entries) => entries.iter().map(|(key, _)| key).filter(|key| *key != "default").cloned().collect(),
+        _ => vec![],
+    };
+    let mut document = "json".to_string();
+    while keys.contains(&document) {
+        document.push('_');
+    }
+
+    let mut declarations = HashMap::from([
+        (document.clone(), declaration(false, json_to_expr(value))),
+        ("default".to_string(), declaration(true, Expr::Ident(ident(&document)))),
+    ]);
+    for key in keys {
+        let member = Expr::Member(MemberExpr {
+            span: DUMMY_SP,
+            obj: Box::new(Expr::Ident(ident(&document))),
+            prop: MemberProp::Computed(ComputedPropName {
+                span: DUMMY_SP,
+                expr: Box::new(string(&key)),
+            }),
+        });
+        declarations.insert(key, declaration(true, member));
+    }
+    declarations
+}
+
+fn declaration(exported: bool, init: Expr) -> ModuleDeclaration {
+    ModuleDeclaration {
+        exported,
+        span: DUMMY_SP,
+        declaration: Declaration::VarInit(init),
+    }
+}
+
+fn ident(name: &str) -> Ident {
+    Ident::new(name.into(), DUMMY_SP, SyntaxContext::empty())
+}
+
+fn string(value: &str) -> Expr {
+    Expr::Lit(Lit::Str(Str {
+        span: DUMMY_SP,
+        value: swc_atoms::Atom::from(value).into(),
+        raw: None,
+    }))
+}
+
+/// The literal expression a JSON value evaluates to
+fn json_to_expr(value: Json) -> Expr {
+    match value {
+        Json::Null => Expr::Lit(Lit::Null(Null { span: DUMMY_SP })),
+        Json::Bool(value) => Expr::Lit(Lit::Bool(Bool { span: DUMMY_SP, value })),
+        Json::Number(number) => {
+            let value = number.as_f64().unwrap_or(f64::NAN);
+            let literal = Expr::Lit(Lit::Num(Number {
+                span: DUMMY_SP,
+                value: value.abs(),
+                raw: None,
+            }));
+            if value.is_sign_negative() {
+                Expr::Unary(UnaryExpr {
+                    span: DUMMY_SP,
+                    op: UnaryOp::Minus,
+                    arg: Box::new(literal),
+                })
+            } else {
+                literal
+            }
+        }
+        Json::String(value) => string(&value),
+        Json::Array(items) => Expr::Array(ArrayLit {
+            span: DUMMY_SP,
+            elems: items
+                .into_iter()
+                .map(|item| {
+                    Some(ExprOrSpread {
+                        spread: None,
+                        expr: Box::new(json_to_expr(item)),
+                    })
+                })
+                .collect(),
+        }),
+        Json::Object(entries) => Expr::Object(ObjectLit {
+            span: DUMMY_SP,
+            props: entries
+                .into_iter()
+                .map(|(key, value)| {
+                    // A literal "__proto__" key would set the prototype instead
+                    let key = if key == "__proto__" {
+                        PropName::Computed(ComputedPropName {
+                            span: DUMMY_SP,
+                            expr: Box::new(string(&key)),
+                        })
+                    } else {
+                        PropName::Str(Str {
+                            span: DUMMY_SP,
+                            value: swc_atoms::Atom::from(key).into(),
+                            raw: None,
+                        })
+                    };
+                    PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                        key,
+                        value: Box::new(json_to_expr(value)),
+                    })))
+                })
+                .collect(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_json_uri() {
+        assert!(is_json_uri("/project/config.json"));
+        assert!(is_json_uri("https://example.com/data.JSON?v=2"));
+        assert!(is_json_uri("data:application/json,{}"));
+        assert!(!is_json_uri("/project/config.ts"));
+        assert!(!is_json_uri("data:text/typescript,export const json = 1;"));
+    }
+
+    #[test]
+    fn test_json_module_declarations() {
+        let parse = |text: &str| serde_json::from_str::<Json>(text).unwrap();
+        let declarations = json_module_declarations(parse(r#"{ "name": "funee", "json": true, "default": 1 }"#));
+
+        let mut names: Vec<_> = declarations.keys().cloned().collect();
+        names.sort();
+        // "json" is taken by a key, so the document is declared as "json_"
+        assert_eq!(names, ["default", "json", "json_", "name"]);
+        assert!(!declarations["json_"].exported);
+        assert!(matches!(
+            &declarations["default"].declaration,
+            Declaration::VarInit(Expr::Ident(ident)) if &*ident.sym == "json_"
+        ));
+
+        let declarations = json_module_declarations(parse("[1, -2.5]"));
+        assert_eq!(declarations.len(), 2);
+
+        // Keys keep the document's order in the object literal
+        let declarations = json_module_declarations(parse(r#"{ "zebra": 1, "apple": 2 }"#));
+        let Declaration::VarInit(Expr::Object(object)) = &declarations["json"].declaration else {
+            panic!("expected the document as an object literal");
+        };
+        let keys: Vec<_> = object
+            .props
+            .iter()
+            .map(|prop| match prop {
+                PropOrSpread::Prop(prop) => match &**prop {
+                    Prop::KeyValue(KeyValueProp { key: PropName::Str(key), .. }) => key.value.as_str().unwrap_or_default().to_string(),
+                    _ => String::new(),
+                },
+                PropOrSpread::Spread(_) => String::new(),
+            })
+            .collect();
+        assert_eq!(keys, ["zebra", "apple"]);
+    }
+}
@@ -1,11 +1,92 @@
-use super::get_module_declarations::{get_module_declarations, ModuleDeclaration};
+use super::get_module_declarations::{get_import_attributes, get_module_declarations, get_star_exports, ModuleDeclaration};
+use super::json_module::{is_json_uri, load_json_module};
+use super::resolve_error::ResolveError;
 use crate::{funee_identifier::FuneeIdentifier, load_module::load_module};
-use std::{path::PathBuf, rc::Rc};
-use swc_common::SourceMap;
-
-pub fn load_declaration(cm: &Rc<SourceMap>, t: &FuneeIdentifier) -> Option<ModuleDeclaration> {
-    let module = load_module(cm, PathBuf::from(t.uri.as_str()));
-    let mut module_declarations = get_module_declarations(module.clone());
-    let declaration = module_declarations.remove(t.name.as_str());
-    declaration
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+use swc_common::{comments::SingleThreadedComments, SourceMap, Span};
+
+/// Declarations of every module parsed during a single run, keyed by module URI.
+/// Each module is parsed once no matter how many of its exports are used.
+/// The cache lives only as long as one `SourceGraph::load`, so `--reload`
+/// still refetches remote modules on the next run.
+#[derive(Default)]
+pub struct ModuleCache {
+    declarations: HashMap<String, HashMap<String, ModuleDeclaration>>,
+    /// `with { ... }` import attributes, by module URI, specifier and attribute name
+    attributes: HashMap<String, HashMap<String, HashMap<String, String>>>,
+    /// `export * from` specifiers (and their spans), by module URI
+    star_exports: HashMap<String, Vec<(String, Span)>>,
+    /// Modules imported `with { type: "json" }`, loaded as JSON whatever their extension
+    json_modules: HashSet<String>,
+}
+
+impl ModuleCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The `key` attribute the module at `module_uri` gave its import of `specifier`
+    pub fn import_attribute(&self, module_uri: &str, specifier: &str, key: &str) -> Option<&String> {
+        self.attributes.get(module_uri)?.get(specifier)?.get(key)
+    }
+
+    /// The `export * from` specifiers of the (already loaded) module at `module_uri`
+    pub fn star_exports(&self, module_uri: &str) -> &[(String, Span)] {
+        self.star_exports.get(module_uri).map_or(&[], Vec::as_slice)
+    }
+
+    /// Load the module at `uri` as JSON when it's first needed
+    pub fn mark_json(&mut self, uri: &str) {
+        self.json_modules.insert(uri.to_string());
+    }
+}
+
+/// Declarations of the module at `uri`, parsing it if it isn't cached yet
+fn load_declarations<'a>(
+    cm: &Rc<SourceMap>,
+    cache: &'a mut ModuleCache,
+    comments: Option<&SingleThreadedComments>,
+    uri: &str,
+) -> Result<&'a HashMap<String, ModuleDeclaration>, ResolveError> {
+    if !cache.declarations.contains_key(uri) {
+        let declarations = if is_json_uri(uri) || cache.json_modules.contains(uri) {
+            load_json_module(cm, Path::new(uri))?
+        } else {
+            let module = load_module(cm, PathBuf::from(uri), comments);
+            cache.attributes.insert(uri.to_string(), get_import_attributes(&module));
+            cache.star_exports.insert(uri.to_string(), get_star_exports(&module));
+            get_module_declarations(module)
+        };
+        cache.declarations.insert(uri.to_string(), declarations);
+    }
+    Ok(&cache.declarations[uri])
+}
+
+pub fn load_declaration(
+    cm: &Rc<SourceMap>,
+    cache: &mut ModuleCache,
+    comments: Option<&SingleThreadedComments>,
+    t: &FuneeIdentifier,
+) -> Result<Option<ModuleDeclaration>, ResolveError> {
+    Ok(load_declarations(cm, cache, comments, &t.uri)?.get(t.name.as_str()).cloned())
+}
+
+/// Names the module at `uri` exports itself (not through `export *`), sorted
+pub fn load_exports(
+    cm: &Rc<SourceMap>,
+    cache: &mut ModuleCache,
+    comments: Option<&SingleThreadedComments>,
+    uri: &str,
+) -> Result<Vec<String>, ResolveError> {
+    let mut names: Vec<String> = load_declarations(cm, cache, comments, uri)?
+        .iter()
+        .filter(|(_, declaration)| declaration.exported)
+        .map(|(name, _)| name.clone())
+        .collect();
+    names.sort();
+    Ok(names)
 }
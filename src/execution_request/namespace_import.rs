@@ -0,0 +1,154 @@
+//! Namespace imports, e.g. `import * as utils from "./utils.ts"`
+//!
+//! Static member accesses like `utils.add` are rewritten into references to
+//! the members themselves, so only the members a module uses are bundled.
+//! The namespace object is only built where the namespace is used as a value.
+
+use super::declaration::Declaration;
+use std::collections::HashMap;
+use swc_common::{Globals, Mark, Span, GLOBALS};
+use swc_ecma_ast::{ComputedPropName, Expr, Ident, Lit, MemberProp};
+use swc_ecma_visit::{noop_visit_mut_type, VisitMut, VisitMutWith};
+
+/// Name a namespace import refers to in its target module
+pub const NAMESPACE: &str = "*";
+
+/// What `rewrite_namespace_members` found
+#[derive(Debug, Default)]
+pub struct NamespaceMembers {
+    /// Export name and span of the first access, by the local name the
+    /// accesses were rewritten to
+    pub members: HashMap<String, (String, Span)>,
+    /// The namespace is also used other than through a static member access
+    pub used_as_value: bool,
+}
+
+/// Rewrite `namespace.name` (and `namespace["name"]`) in the declaration to
+/// an identifier `namespace.name`, which can't clash with a real binding and
+/// is renamed like any other reference when the bundle is emitted
+pub fn rewrite_namespace_members(
+    decl: &mut Declaration,
+    namespace: &str,
+    unresolved_mark: (&Globals, Mark),
+) -> NamespaceMembers {
+    let mut rewrite = RewriteMembers {
+        namespace,
+        unresolved_mark: unresolved_mark.1,
+        found: NamespaceMembers::default(),
+    };
+    GLOBALS.set(unresolved_mark.0, || match decl {
+        Declaration::FnDecl(n) => n.function.visit_mut_with(&mut rewrite),
+        Declaration::FnExpr(n) => n.visit_mut_with(&mut rewrite),
+        Declaration::Expr(n) => n.visit_mut_with(&mut rewrite),
+        Declaration::VarInit(n) => n.visit_mut_with(&mut rewrite),
+        Declaration::Macro(n) => n.visit_mut_with(&mut rewrite),
+        // A captured closure keeps its references as they were
+        _ => rewrite.found.used_as_value = true,
+    });
+    rewrite.found
+}
+
+struct RewriteMembers<'a> {
+    namespace: &'a str,
+    unresolved_mark: Mark,
+    found: NamespaceMembers,
+}
+
+impl RewriteMembers<'_> {
+    fn is_namespace(&self, ident: &Ident) -> bool {
+        &*ident.sym == self.namespace && ident.ctxt.has_mark(self.unresolved_mark)
+    }
+}
+
+impl VisitMut for RewriteMembers<'_> {
+    noop_visit_mut_type!();
+
+    fn visit_mut_expr(&mut self, n: &mut Expr) {
+        if let Expr::Member(member) = n {
+            if let Expr::Ident(obj) = &*member.obj {
+                let name = match &member.prop {
+                    MemberProp::Ident(prop) => Some(prop.sym.to_string()),
+                    MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+                        Expr::Lit(Lit::Str(s)) => s.value.as_str().map(str::to_string),
+                        _ => None,
+                    },
+                    MemberProp::PrivateName(_) => None,
+                };
+                if let (true, Some(name)) = (self.is_namespace(obj), name) {
+                    let local_name = format!("{}.{}", self.namespace, name);
+                    let ident = Ident::new(local_name.as_str().into(), member.span, obj.ctxt);
+                    self.found.members.entry(local_name).or_insert((name, member.span));
+                    *n = Expr::Ident(ident);
+                    return;
+                }
+            }
+        }
+        n.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_ident(&mut self, n: &mut Ident) {
+        if self.is_namespace(n) {
+            self.found.used_as_value = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution_request::get_references_from_declaration::get_references_from_declaration;
+    use swc_common::SyntaxContext;
+    use swc_ecma_ast::{CallExpr, Callee, ExprOrSpread, IdentName, MemberExpr};
+
+    fn ident(name: &str) -> Ident {
+        Ident::new(name.into(), Default::default(), SyntaxContext::empty())
+    }
+
+    fn member(obj: &str, prop: &str) -> Expr {
+        Expr::Member(MemberExpr {
+            span: Default::default(),
+            obj: Box::new(Expr::Ident(ident(obj))),
+            prop: MemberProp::Ident(IdentName::new(prop.into(), Default::default())),
+        })
+    }
+
+    fn call(callee: Expr, args: Vec<Expr>) -> Expr {
+        Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(callee)),
+            args: args
+                .into_iter()
+                .map(|expr| ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(expr),
+                })
+                .collect(),
+            type_args: None,
+        })
+    }
+
+    #[test]
+    fn test_rewrite_namespace_members() {
+        let globals = Globals::default();
+        let mark = GLOBALS.set(&globals, Mark::new);
+
+        // utils.add(utils.add, utils.double)
+        let mut decl = Declaration::Expr(call(
+            member("utils", "add"),
+            vec![member("utils", "add"), member("utils", "double")],
+        ));
+        get_references_from_declaration(&mut decl, (&globals, mark));
+        let found = rewrite_namespace_members(&mut decl, "utils", (&globals, mark));
+
+        let mut members: Vec<_> = found.members.iter().map(|(local, (name, _))| (local.as_str(), name.as_str())).collect();
+        members.sort();
+        assert_eq!(members, [("utils.add", "add"), ("utils.double", "double")]);
+        assert!(!found.used_as_value);
+
+        // log(utils) keeps the namespace itself
+        let mut decl = Declaration::Expr(call(Expr::Ident(ident("log")), vec![Expr::Ident(ident("utils"))]));
+        get_references_from_declaration(&mut decl, (&globals, mark));
+        assert!(rewrite_namespace_members(&mut decl, "utils", (&globals, mark)).used_as_value);
+    }
+}
@@ -0,0 +1,495 @@
+//! `npm:` import specifiers, e.g. `npm:lodash@^4/merge`
+//!
+//! The version range is resolved against the npm registry to the highest
+//! matching published version, which is then imported from the CDN as
+//! `https://esm.sh/lodash@4.17.21/merge`. Exact versions skip the registry.
+//! Each package's version list is cached under `~/.funee/cache/npm` for a
+//! day, and used regardless of age when the registry can't be reached.
+//!
+//! This is a convenience shim over HTTP imports, not an npm installer: the
+//! CDN (`FUNEE_NPM_CDN`, default esm.sh) does the bundling, and the URL is
+//! then fetched like any other remote module.
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::{LazyLock, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where resolved packages are imported from, unless `FUNEE_NPM_CDN` is set
+const DEFAULT_NPM_CDN: &str = "https://esm.sh";
+
+/// Where published versions are looked up
+const NPM_REGISTRY: &str = "https://registry.npmjs.org";
+
+/// How long a cached version list is trusted before asking the registry again
+const VERSIONS_MAX_AGE_SECS: u64 = 24 * 60 * 60;
+
+/// Specifiers already resolved by this process
+static RESOLVED: LazyLock<Mutex<HashMap<String, String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Check if a URI uses the `npm:` scheme
+pub fn is_npm_uri(uri: &str) -> bool {
+    uri.starts_with("npm:")
+}
+
+/// Resolve an `npm:` specifier to the CDN URL of the highest matching version
+pub fn resolve_npm_uri(uri: &str) -> Result<String, String> {
+    if let Some(url) = RESOLVED.lock().unwrap().get(uri) {
+        return Ok(url.clone());
+    }
+    let cache_dir = dirs::home_dir().map(|home| home.join(".funee").join("cache").join("npm"));
+    let url = resolve_with(uri, &npm_cdn(), cache_dir.as_deref(), fetch_registry_versions)?;
+    RESOLVED.lock().unwrap().insert(uri.to_string(), url.clone());
+    Ok(url)
+}
+
+/// Base URL of the CDN packages are imported from (`FUNEE_NPM_CDN`, e.g.
+/// `https://cdn.jsdelivr.net/npm` or a self-hosted esm.sh)
+fn npm_cdn() -> String {
+    std::env::var("FUNEE_NPM_CDN")
+        .ok()
+        .map(|cdn| cdn.trim_end_matches('/').to_string())
+        .filter(|cdn| !cdn.is_empty())
+        .unwrap_or_else(|| DEFAULT_NPM_CDN.to_string())
+}
+
+/// `resolve_npm_uri` with the CDN, cache location and registry lookup supplied
+fn resolve_with(
+    uri: &str,
+    cdn: &str,
+    cache_dir: Option<&Path>,
+    fetch_versions: impl Fn(&str) -> Result<Vec<String>, String>,
+) -> Result<String, String> {
+    let specifier = NpmSpecifier::parse(uri).ok_or_else(|| format!("Invalid npm specifier '{}'", uri))?;
+    let version = match specifier.range {
+        Some(range) if Version::parse(range).is_some() => range.to_string(),
+        range => {
+            let range_text = range.unwrap_or("*");
+            let range = Range::parse(range_text)
+                .ok_or_else(|| format!("Unsupported version range '{}' in '{}'", range_text, uri))?;
+            let no_match = || format!("No published version of '{}' matches '{}'", specifier.name, range_text);
+
+            let cache_file = cache_dir.map(|dir| dir.join(format!("{}.json", specifier.name.replace('/', "%2f"))));
+            let cached = cache_file.as_deref().and_then(CachedVersions::read);
+            let fresh_match = cached
+                .as_ref()
+                .filter(|cached| cached.is_fresh())
+                .and_then(|cached| range.highest_match(&cached.versions));
+            match fresh_match {
+                Some(version) => version,
+                None => match fetch_versions(specifier.name) {
+                    Ok(versions) => {
+                        if let Some(cache_file) = &cache_file {
+                            CachedVersions::write(cache_file, &versions);
+                        }
+                        range.highest_match(&versions).ok_or_else(no_match)?
+                    }
+                    // Offline: any cached list is better than nothing
+                    Err(e) => cached
+                        .and_then(|cached| range.highest_match(&cached.versions))
+                        .ok_or_else(|| format!("Cannot resolve '{}': {}", uri, e))?,
+                },
+            }
+        }
+    };
+    Ok(format!("{}/{}@{}{}", cdn, specifier.name, version, specifier.subpath))
+}
+
+/// Published versions of a package, from the registry's abbreviated metadata
+fn fetch_registry_versions(name: &str) -> Result<Vec<String>, String> {
+    let client = reqwest::blocking::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .map_err(|e| e.to_string())?;
+    let response = client
+        .get(format!("{}/{}", NPM_REGISTRY, name.replace('/', "%2f")))
+        .header("Accept", "application/vnd.npm.install-v1+json")
+        .send()
+        .map_err(|e| format!("npm registry request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("npm registry returned {} for '{}'", response.status(), name));
+    }
+    let metadata: serde_json::Value = response.json().map_err(|e| format!("Invalid npm registry response: {}", e))?;
+    Ok(metadata
+        .get("versions")
+        .and_then(|versions| versions.as_object())
+        .map(|versions| versions.keys().cloned().collect())
+        .unwrap_or_default())
+}
+
+/// A package's version list as cached on disk
+#[derive(Serialize, Deserialize)]
+struct CachedVersions {
+    cached_at: u64,
+    versions: Vec<String>,
+}
+
+impl CachedVersions {
+    fn read(path: &Path) -> Option<Self> {
+        serde_json::from_str(&fs::read_to_string(path).ok()?).ok()
+    }
+
+    fn write(path: &Path, versions: &[String]) {
+        let cached = Self { cached_at: now_secs(), versions: versions.to_vec() };
+        // Caching is best-effort; the resolution itself already succeeded
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        if let Ok(json) = serde_json::to_string(&cached) {
+            let _ = fs::write(path, json);
+        }
+    }
+
+    fn is_fresh(&self) -> bool {
+        now_secs().saturating_sub(self.cached_at) < VERSIONS_MAX_AGE_SECS
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+/// `npm:<name>[@<range>][/<subpath>]`, where the name may be scoped
+#[derive(Debug, PartialEq)]
+struct NpmSpecifier<'a> {
+    name: &'a str,
+    range: Option<&'a str>,
+    /// Empty, or starting with `/`
+    subpath: &'a str,
+}
+
+impl<'a> NpmSpecifier<'a> {
+    fn parse(uri: &'a str) -> Option<Self> {
+        let rest = uri.strip_prefix("npm:")?.trim_start_matches('/');
+        // A scope's `/` belongs to the name
+        let search_from = if rest.starts_with('@') { rest.find('/')? + 1 } else { 0 };
+        let name_end = rest[search_from..].find(['@', '/']).map_or(rest.len(), |i| i + search_from);
+        let (name, rest) = rest.split_at(name_end);
+        if name.is_empty() || name.ends_with('/') {
+            return None;
+        }
+        let (range, subpath) = match rest.strip_prefix('@') {
+            Some(versioned) => {
+                let range_end = versioned.find('/').unwrap_or(versioned.len());
+                let (range, subpath) = versioned.split_at(range_end);
+                (Some(range).filter(|range| !range.is_empty()), subpath)
+            }
+            None => (None, rest),
+        };
+        Some(Self { name, range, subpath })
+    }
+}
+
+/// A semver version; build metadata is ignored
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    prerelease: Option<String>,
+}
+
+impl Version {
+    fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self { major, minor, patch, prerelease: None }
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.split('+').next()?;
+        let (core, prerelease) = match text.split_once('-') {
+            Some((core, prerelease)) => (core, Some(prerelease.to_string())),
+            None => (text, None),
+        };
+        let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+        let version = Self {
+            major: parts.next()??,
+            minor: parts.next()??,
+            patch: parts.next()??,
+            prerelease,
+        };
+        parts.next().is_none().then_some(version)
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (&self.prerelease, &other.prerelease) {
+                (None, None) => Ordering::Equal,
+                // A prerelease comes before its release
+                (None, Some(_)) => Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (Some(a), Some(b)) => compare_prereleases(a, b),
+            })
+    }
+}
+
+/// Semver precedence of two prereleases: dot-separated identifiers compared
+/// in turn, numerically when both are numeric, with numeric identifiers below
+/// alphanumeric ones and a shorter list below a longer one it starts
+fn compare_prereleases(a: &str, b: &str) -> Ordering {
+    let identifier = |part: &str| match part.parse::<u64>() {
+        Ok(number) if part.bytes().all(|byte| byte.is_ascii_digit()) => Ok(number),
+        _ => Err(part.to_string()),
+    };
+    // Ok (numeric) orders before Err (alphanumeric), as §11 requires
+    a.split('.').map(identifier).cmp(b.split('.').map(identifier))
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// A version with trailing parts left out or wildcarded (`1`, `1.2`, `1.x`, `*`)
+struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+}
+
+impl Partial {
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim_start_matches('v');
+        if let Some(version) = Version::parse(text) {
+            if version.prerelease.is_none() {
+                return Some(Self { major: Some(version.major), minor: Some(version.minor), patch: Some(version.patch) });
+            }
+        }
+        let mut parts = text.split('.').map(|part| match part {
+            "x" | "X" | "*" => Ok(None),
+            number => number.parse::<u64>().map(Some),
+        });
+        let major = parts.next().unwrap_or(Ok(None)).ok()?;
+        let minor = parts.next().unwrap_or(Ok(None)).ok()?;
+        let patch = parts.next().unwrap_or(Ok(None)).ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { major, minor, patch })
+    }
+
+    fn floor(&self) -> Version {
+        Version::new(self.major.unwrap_or(0), self.minor.unwrap_or(0), self.patch.unwrap_or(0))
+    }
+
+    /// The first version past every version this partial covers
+    fn ceiling(&self) -> Option<Version> {
+        match (self.major, self.minor, self.patch) {
+            (None, _, _) => None,
+            (Some(major), None, _) => Some(Version::new(major + 1, 0, 0)),
+            (Some(major), Some(minor), None) => Some(Version::new(major, minor + 1, 0)),
+            (Some(major), Some(minor), Some(patch)) => Some(Version::new(major, minor, patch + 1)),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Comparator {
+    AtLeast(Version),
+    Below(Version),
+    Exactly(Version),
+}
+
+impl Comparator {
+    fn matches(&self, version: &Version) -> bool {
+        match self {
+            Comparator::AtLeast(bound) => version >= bound,
+            Comparator::Below(bound) => version < bound,
+            Comparator::Exactly(bound) => version == bound,
+        }
+    }
+}
+
+/// A version range: `||`-separated sets of space-separated comparators
+/// (`^1.2`, `~1.2.3`, `>=1 <3`, `1.x`, `*`, ...); prereleases never match
+struct Range {
+    alternatives: Vec<Vec<Comparator>>,
+}
+
+impl Range {
+    fn parse(text: &str) -> Option<Self> {
+        let alternatives = text
+            .split("||")
+            .map(|set| {
+                let mut comparators = vec![];
+                for part in set.split_whitespace() {
+                    comparators.extend(Self::parse_comparator(part)?);
+                }
+                Some(comparators)
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self { alternatives })
+    }
+
+    fn parse_comparator(part: &str) -> Option<Vec<Comparator>> {
+        use Comparator::*;
+        if part == "latest" {
+            return Some(vec![]);
+        }
+        let operator_end = part.find(|c: char| !matches!(c, '^' | '~' | '>' | '<' | '=')).unwrap_or(part.len());
+        let (operator, version) = part.split_at(operator_end);
+        let partial = Partial::parse(version)?;
+        let floor = partial.floor();
+        let ceiling = partial.ceiling();
+        Some(match operator {
+            "" | "=" => match (&partial.patch, ceiling) {
+                (Some(_), _) => vec![Exactly(floor)],
+                (None, Some(ceiling)) => vec![AtLeast(floor), Below(ceiling)],
+                (None, None) => vec![],
+            },
+            "^" => {
+                // Changes to the left-most non-zero part are breaking
+                let upper = match (partial.major, partial.minor, partial.patch) {
+                    (Some(0), Some(0), Some(patch)) => Some(Version::new(0, 0, patch + 1)),
+                    (Some(0), Some(minor), _) => Some(Version::new(0, minor + 1, 0)),
+                    (Some(major), _, _) => Some(Version::new(major + 1, 0, 0)),
+                    (None, _, _) => None,
+                };
+                [Some(AtLeast(floor)), upper.map(Below)].into_iter().flatten().collect()
+            }
+            "~" => {
+                let upper = match (partial.major, partial.minor) {
+                    (Some(major), Some(minor)) => Some(Version::new(major, minor + 1, 0)),
+                    (Some(major), None) => Some(Version::new(major + 1, 0, 0)),
+                    (None, _) => None,
+                };
+                [Some(AtLeast(floor)), upper.map(Below)].into_iter().flatten().collect()
+            }
+            ">=" => vec![AtLeast(floor)],
+            // Past everything the partial covers: >1.2 means >=1.3.0
+            ">" => vec![AtLeast(ceiling?)],
+            "<" => vec![Below(floor)],
+            "<=" => ceiling.map(|ceiling| vec![Below(ceiling)]).unwrap_or_default(),
+            _ => return None,
+        })
+    }
+
+    fn matches(&self, version: &Version) -> bool {
+        version.prerelease.is_none()
+            && self
+                .alternatives
+                .iter()
+                .any(|comparators| comparators.iter().all(|comparator| comparator.matches(version)))
+    }
+
+    /// The highest of `versions` in this range, as written in the list
+    fn highest_match(&self, versions: &[String]) -> Option<String> {
+        versions
+            .iter()
+            .filter_map(|text| Version::parse(text).map(|version| (version, text)))
+            .filter(|(version, _)| self.matches(version))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, text)| text.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(list: &[&str]) -> Vec<String> {
+        list.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_npm_specifier() {
+        assert_eq!(
+            NpmSpecifier::parse("npm:lodash@^4/merge"),
+            Some(NpmSpecifier { name: "lodash", range: Some("^4"), subpath: "/merge" })
+        );
+        assert_eq!(
+            NpmSpecifier::parse("npm:@scope/pkg@1.2.3/a/b.js"),
+            Some(NpmSpecifier { name: "@scope/pkg", range: Some("1.2.3"), subpath: "/a/b.js" })
+        );
+        assert_eq!(
+            NpmSpecifier::parse("npm:preact/hooks"),
+            Some(NpmSpecifier { name: "preact", range: None, subpath: "/hooks" })
+        );
+        assert_eq!(NpmSpecifier::parse("npm:@scope"), None);
+        assert_eq!(NpmSpecifier::parse("lodash"), None);
+    }
+
+    #[test]
+    fn test_range_highest_match() {
+        let published = versions(&["1.0.0", "1.2.3", "1.4.0", "2.0.0-beta.1", "2.0.0", "2.1.5", "0.3.1", "0.3.9", "0.4.0"]);
+        let highest = |range: &str| Range::parse(range).unwrap().highest_match(&published);
+
+        assert_eq!(highest("^1").as_deref(), Some("1.4.0"));
+        assert_eq!(highest("^1.2.3").as_deref(), Some("1.4.0"));
+        assert_eq!(highest("~1.2.0").as_deref(), Some("1.2.3"));
+        assert_eq!(highest("^0.3.2").as_deref(), Some("0.3.9"));
+        assert_eq!(highest("1.x").as_deref(), Some("1.4.0"));
+        assert_eq!(highest("*").as_deref(), Some("2.1.5"));
+        assert_eq!(highest(">=1.1 <2").as_deref(), Some("1.4.0"));
+        assert_eq!(highest(">1.4").as_deref(), Some("2.1.5"));
+        assert_eq!(highest("<=1.2").as_deref(), Some("1.2.3"));
+        assert_eq!(highest("^0.3 || ^1.0.0").as_deref(), Some("1.4.0"));
+        assert_eq!(highest("1.2.3").as_deref(), Some("1.2.3"));
+        // Prereleases are never picked for a range
+        assert_eq!(highest("~2.0.0").as_deref(), Some("2.0.0"));
+        assert_eq!(highest("^3"), None);
+        assert!(Range::parse("1.2 - 2.0").is_none());
+    }
+
+    #[test]
+    fn test_prerelease_precedence() {
+        // The ordering example from the semver spec
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(Version::parse(pair[0]).unwrap() < Version::parse(pair[1]).unwrap(), "{} < {}", pair[0], pair[1]);
+        }
+        assert!(Version::parse("2.0.0-10").unwrap() > Version::parse("2.0.0-9").unwrap());
+        assert!(Version::parse("2.0.0-1").unwrap() < Version::parse("2.0.0-a").unwrap());
+    }
+
+    #[test]
+    fn test_resolve_pinned_version_without_registry() {
+        let url = resolve_with("npm:pkg@1.2.3", DEFAULT_NPM_CDN, None, |_| panic!("pinned versions don't need the registry"));
+        assert_eq!(url.as_deref(), Ok("https://esm.sh/pkg@1.2.3"));
+
+        let url = resolve_with("npm:@scope/pkg@1.2.3/sub/mod.js", DEFAULT_NPM_CDN, None, |_| unreachable!());
+        assert_eq!(url.as_deref(), Ok("https://esm.sh/@scope/pkg@1.2.3/sub/mod.js"));
+
+        // Another CDN keeps the same name@version/subpath layout
+        let url = resolve_with("npm:preact@10.19.0/hooks", "https://cdn.example.com/npm", None, |_| unreachable!());
+        assert_eq!(url.as_deref(), Ok("https://cdn.example.com/npm/preact@10.19.0/hooks"));
+    }
+
+    #[test]
+    fn test_resolve_range_from_cached_versions() {
+        let cache_dir = std::env::temp_dir().join(format!("funee-npm-cache-{}", std::process::id()));
+        fs::create_dir_all(&cache_dir).unwrap();
+        CachedVersions::write(&cache_dir.join("lodash.json"), &versions(&["4.17.20", "4.17.21", "5.0.0"]));
+
+        // A fresh cache answers without the registry
+        let url = resolve_with("npm:lodash@^4/merge", DEFAULT_NPM_CDN, Some(&cache_dir), |_| panic!("cache is fresh"));
+        assert_eq!(url.as_deref(), Ok("https://esm.sh/lodash@4.17.21/merge"));
+
+        // No cached match: the registry is asked, and its answer is cached
+        let url = resolve_with("npm:lodash@^6", DEFAULT_NPM_CDN, Some(&cache_dir), |_| Ok(versions(&["5.0.0", "6.1.0"])));
+        assert_eq!(url.as_deref(), Ok("https://esm.sh/lodash@6.1.0"));
+        let cached = CachedVersions::read(&cache_dir.join("lodash.json")).unwrap();
+        assert_eq!(cached.versions, versions(&["5.0.0", "6.1.0"]));
+
+        // Offline and nothing cached matches
+        let error = resolve_with("npm:lodash@^7", DEFAULT_NPM_CDN, Some(&cache_dir), |_| Err("offline".to_string()));
+        assert_eq!(error, Err("Cannot resolve 'npm:lodash@^7': offline".to_string()));
+
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+}
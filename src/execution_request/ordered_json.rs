@@ -0,0 +1,129 @@
+//! JSON parsed with object keys kept in the order they're written
+//!
+//! serde_json's own `Value` sorts keys. Where order means something — which
+//! package.json "exports" condition wins, the key order of an imported JSON
+//! module — documents are parsed into `Json` instead.
+
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use std::fmt;
+
+/// A JSON value whose objects keep their entries in document order
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Number(serde_json::Number),
+    String(String),
+    Array(Vec<Json>),
+    /// Entries in document order; a repeated key keeps each occurrence, as
+    /// an object literal would
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    /// The value of `key` in an object, the last one if it's repeated
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        match self {
+            Json::Object(entries) => entries.iter().rev().find(|(name, _)| name == key).map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Json::String(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Json {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(JsonVisitor)
+    }
+}
+
+struct JsonVisitor;
+
+impl<'de> Visitor<'de> for JsonVisitor {
+    type Value = Json;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any JSON value")
+    }
+
+    fn visit_unit<E>(self) -> Result<Json, E> {
+        Ok(Json::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Json, E> {
+        Ok(Json::Null)
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Json, E> {
+        Ok(Json::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Json, E> {
+        Ok(Json::Number(value.into()))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Json, E> {
+        Ok(Json::Number(value.into()))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Json, E> {
+        // JSON text can't hold NaN or infinities, so this always succeeds
+        Ok(serde_json::Number::from_f64(value).map_or(Json::Null, Json::Number))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Json, E> {
+        Ok(Json::String(value.to_string()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Json, E> {
+        Ok(Json::String(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Json, A::Error> {
+        let mut items = vec![];
+        while let Some(item) = seq.next_element()? {
+            items.push(item);
+        }
+        Ok(Json::Array(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Json, A::Error> {
+        let mut entries = vec![];
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(Json::Object(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_key_order() {
+        let json: Json = serde_json::from_str(r#"{ "z": 1, "a": [true, null, "x"], "m": { "b": 2.5, "a": -1 } }"#).unwrap();
+        let Json::Object(entries) = &json else {
+            panic!("expected an object");
+        };
+        let keys: Vec<_> = entries.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, ["z", "a", "m"]);
+        assert_eq!(
+            json.get("m"),
+            Some(&Json::Object(vec![
+                ("b".to_string(), Json::Number(serde_json::Number::from_f64(2.5).unwrap())),
+                ("a".to_string(), Json::Number((-1).into())),
+            ]))
+        );
+        assert_eq!(json.get("a"), Some(&Json::Array(vec![Json::Bool(true), Json::Null, Json::String("x".to_string())])));
+
+        let repeated: Json = serde_json::from_str(r#"{ "a": 1, "a": 2 }"#).unwrap();
+        assert_eq!(repeated.get("a"), Some(&Json::Number(2.into())));
+    }
+}
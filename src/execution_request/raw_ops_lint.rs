@@ -0,0 +1,85 @@
+use super::declaration::Declaration;
+use swc_common::Span;
+use swc_ecma_ast::{Expr, Lit, MemberExpr, MemberProp};
+use swc_ecma_visit::{noop_visit_type, Visit, VisitWith};
+
+/// Find `Deno.core` accesses (`Deno.core.ops.op_x(...)`, `globalThis.Deno.core`, ...)
+///
+/// Host functions are meant to be reached through imports ("funee", "host://..."),
+/// so user modules touching the raw ops table bypass that boundary.
+pub fn find_raw_ops_access(declaration: &Declaration) -> Vec<Span> {
+    let mut finder = RawOpsFinder::default();
+    match declaration {
+        Declaration::FnDecl(n) => n.function.visit_with(&mut finder),
+        Declaration::FnExpr(n) => n.visit_with(&mut finder),
+        Declaration::Expr(n) | Declaration::VarInit(n) | Declaration::Macro(n) => n.visit_with(&mut finder),
+        Declaration::ClosureValue(closure) => closure.expression.visit_with(&mut finder),
+        Declaration::FuneeIdentifier(_) | Declaration::HostFn(_) | Declaration::HostModule(_, _) => {}
+    }
+    finder.spans
+}
+
+#[derive(Default)]
+struct RawOpsFinder {
+    spans: Vec<Span>,
+}
+
+impl Visit for RawOpsFinder {
+    noop_visit_type!();
+
+    fn visit_member_expr(&mut self, n: &MemberExpr) {
+        if prop_name(&n.prop) == Some("core") && is_deno(&n.obj) {
+            self.spans.push(n.span);
+        }
+        n.visit_children_with(self);
+    }
+}
+
+/// `Deno` or `globalThis.Deno`
+fn is_deno(expr: &Expr) -> bool {
+    match expr {
+        Expr::Ident(ident) => ident.sym == "Deno",
+        Expr::Member(member) => {
+            prop_name(&member.prop) == Some("Deno")
+                && matches!(&*member.obj, Expr::Ident(ident) if ident.sym == "globalThis")
+        }
+        Expr::Paren(paren) => is_deno(&paren.expr),
+        _ => false,
+    }
+}
+
+/// Name of `.name` or `["name"]`
+fn prop_name(prop: &MemberProp) -> Option<&str> {
+    match prop {
+        MemberProp::Ident(ident) => Some(ident.sym.as_str()),
+        MemberProp::Computed(computed) => match &*computed.expr {
+            Expr::Lit(Lit::Str(s)) => s.value.as_str(),
+            _ => None,
+        },
+        MemberProp::PrivateName(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use swc_common::{sync::Lrc, FileName, SourceMap};
+    use swc_ecma_parser::{parse_file_as_expr, Syntax};
+
+    fn count(code: &str) -> usize {
+        let cm: Lrc<SourceMap> = Default::default();
+        let fm = cm.new_source_file(FileName::Anon.into(), code.to_string());
+        let expr = parse_file_as_expr(&fm, Syntax::default(), Default::default(), None, &mut vec![]).unwrap();
+        find_raw_ops_access(&Declaration::Expr(*expr)).len()
+    }
+
+    #[test]
+    fn test_find_raw_ops_access() {
+        assert_eq!(count("() => Deno.core.ops.op_fsReadFile('/etc/passwd')"), 1);
+        assert_eq!(count("() => globalThis.Deno.core.ops.op_log('x')"), 1);
+        assert_eq!(count("() => Deno['core']['ops']"), 1);
+        assert_eq!(count("() => [Deno.core.ops.a(), Deno.core.print('x')]"), 2);
+        assert_eq!(count("() => other.core.ops.op_log('x')"), 0);
+        assert_eq!(count("() => Deno.version"), 0);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::diagnostics;
+use std::fmt;
+
+/// Why a module graph couldn't be loaded
+///
+/// Returned by `SourceGraph::load` (and the `ExecutionRequest` methods built
+/// on it) instead of exiting, so embedders and tests can recover. The CLI
+/// prints it with `report`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResolveError {
+    /// "funee" was imported but no funee_lib_path is configured
+    NoFuneeLib,
+    /// An "npm:" specifier couldn't be resolved to a published version
+    Npm(String),
+    /// A specifier couldn't be joined onto its importer's URL
+    InvalidUrl { specifier: String, base: String, reason: String },
+    /// The importer's own URL doesn't parse
+    InvalidBaseUrl { base: String, reason: String },
+    /// A data: URL import couldn't be decoded into module source
+    InvalidDataUrl { reason: String },
+    /// A JSON module isn't valid JSON; `location` is where parsing failed
+    InvalidJson { uri: String, reason: String, location: Option<SourceLocation> },
+    /// A package's "exports" map has no entry for the subpath and conditions
+    NotExported { package: String, subpath: String, conditions: Vec<String>, manifest: String },
+    /// A module doesn't declare or re-export the imported name; `location` is
+    /// the import (or first use) that asked for it, when known
+    NotFound { name: String, uri: String, referenced_from: String, location: Option<SourceLocation> },
+    /// More than one of a module's `export * from` targets exports the name
+    AmbiguousExport { name: String, uri: String, sources: Vec<String>, location: Option<SourceLocation> },
+    /// A remote module imported from a host that isn't allowed
+    BlockedHost { uri: String, host: String, importer: String },
+    /// A module pinned with an integrity hash couldn't be read
+    Unreadable { uri: String, reason: String },
+    /// A pinned module's contents don't match its integrity hash
+    IntegrityMismatch { specifier: String, importer: String, expected: String, actual: String },
+    /// A user module accesses `Deno.core` and `deny_raw_ops` is set
+    /// (each access is reported as it's found)
+    RawOpsDenied,
+}
+
+/// A position in a module's source, for pointing diagnostics at an import
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceLocation {
+    pub file: String,
+    /// 1-based
+    pub line: usize,
+    /// 0-based, in display columns
+    pub col: usize,
+    /// The module's source, for the code frame
+    pub source: String,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.col + 1)
+    }
+}
+
+impl ResolveError {
+    /// Extra context printed as `note:` lines after the error
+    pub fn notes(&self) -> Vec<String> {
+        match self {
+            Self::NotExported { manifest, .. } => vec![manifest.clone()],
+            Self::NotFound { referenced_from, .. } => vec![format!("Referenced from: {}", referenced_from)],
+            Self::AmbiguousExport { name, sources, .. } => sources
+                .iter()
+                .map(|source| format!("Exported by: {}", source))
+                .chain([format!("Re-export '{}' by name to pick one", name)])
+                .collect(),
+            Self::BlockedHost { host, importer, .. } => vec![
+                format!("Imported from: {}", importer),
+                format!("Allow it with --allow-import-hosts={}", host),
+            ],
+            Self::IntegrityMismatch { importer, expected, actual, .. } => vec![
+                format!("Imported from: {}", importer),
+                format!("Expected: {}", expected),
+                format!("Actual: {}", actual),
+            ],
+            Self::RawOpsDenied => vec![
+                "Import host functions from \"funee\" or \"host://...\" instead (rejected by --no-raw-ops)".to_string(),
+            ],
+            _ => vec![],
+        }
+    }
+
+    /// Print the error and its notes as diagnostics, with a code frame when
+    /// the error has a source location
+    pub fn report(&self) {
+        if *self != Self::RawOpsDenied {
+            diagnostics::error(self);
+        }
+        if let Self::NotFound { location: Some(location), .. }
+        | Self::AmbiguousExport { location: Some(location), .. }
+        | Self::InvalidJson { location: Some(location), .. } = self
+        {
+            diagnostics::note(location);
+            diagnostics::code_frame(&location.source, location.line, location.col);
+        }
+        for note in self.notes() {
+            diagnostics::note(note);
+        }
+    }
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoFuneeLib => write!(f, "Cannot resolve 'funee' - no funee_lib_path configured"),
+            Self::Npm(message) => write!(f, "{}", message),
+            Self::InvalidUrl { specifier, base, reason } => {
+                write!(f, "Failed to resolve '{}' from '{}': {}", specifier, base, reason)
+            }
+            Self::InvalidBaseUrl { base, reason } => write!(f, "Invalid base URL '{}': {}", base, reason),
+            Self::InvalidDataUrl { reason } => write!(f, "Invalid data: URL import: {}", reason),
+            Self::InvalidJson { uri, reason, .. } => write!(f, "Failed to parse JSON module '{}': {}", uri, reason),
+            Self::NotExported { package, subpath, conditions, .. } => write!(
+                f,
+                "Package subpath '{}' is not exported by '{}' for conditions [{}]",
+                subpath,
+                package,
+                conditions.join(", ")
+            ),
+            Self::NotFound { name, uri, .. } => write!(f, "Cannot find '{}' in module '{}'", name, uri),
+            Self::AmbiguousExport { name, uri, .. } => {
+                write!(f, "'{}' is ambiguous in module '{}': more than one `export *` provides it", name, uri)
+            }
+            Self::BlockedHost { uri, host, .. } => {
+                write!(f, "Import of '{}' is blocked: host '{}' is not allowed", uri, host)
+            }
+            Self::Unreadable { uri, reason } => write!(f, "Cannot read '{}': {}", uri, reason),
+            Self::IntegrityMismatch { specifier, .. } => write!(f, "Integrity check failed for '{}'", specifier),
+            Self::RawOpsDenied => write!(f, "Direct access to Deno.core is rejected by --no-raw-ops"),
+        }
+    }
+}
+
+impl std::error::Error for ResolveError {}
@@ -0,0 +1,252 @@
+use super::{ordered_json::Json, resolve_error::ResolveError, source_graph::is_http_uri};
+use std::{fs, path::Path};
+
+/// Conditions matched against package.json "exports" when none are configured
+pub fn default_export_conditions() -> Vec<String> {
+    vec!["import".to_string(), "default".to_string()]
+}
+
+/// Check if a specifier is a bare package specifier (e.g. "lodash", "@scope/pkg/sub")
+pub fn is_bare_specifier(specifier: &str) -> bool {
+    !(specifier == "funee"
+        || specifier.starts_with("./")
+        || specifier.starts_with("../")
+        || specifier.starts_with('/')
+        || specifier.contains(':'))
+}
+
+/// Split a bare specifier into the package name and the subpath inside it
+/// e.g. "@scope/pkg/utils" -> ("@scope/pkg", "./utils"), "pkg" -> ("pkg", ".")
+fn split_package_specifier(specifier: &str) -> (&str, String) {
+    let name_segments = if specifier.starts_with('@') { 2 } else { 1 };
+    let name_end = specifier
+        .match_indices('/')
+        .nth(name_segments - 1)
+        .map(|(i, _)| i)
+        .unwrap_or(specifier.len());
+    let (name, rest) = specifier.split_at(name_end);
+    (name, format!(".{}", rest))
+}
+
+/// Resolve a bare specifier against the node_modules directories above `base_uri`
+///
+/// Honors the package.json "exports" map (conditions and `*` patterns) and
+/// falls back to "module"/"main" for packages without one.
+/// Returns None for specifiers that aren't bare or packages that aren't installed,
+/// and an error when the package's "exports" map doesn't cover the subpath.
+pub fn resolve_bare_specifier(specifier: &str, base_uri: &str, conditions: &[String]) -> Result<Option<String>, ResolveError> {
+    if !is_bare_specifier(specifier) || is_http_uri(base_uri) {
+        return Ok(None);
+    }
+
+    let (name, subpath) = split_package_specifier(specifier);
+    let Some(base_dir) = Path::new(base_uri).parent() else {
+        return Ok(None);
+    };
+
+    for dir in base_dir.ancestors() {
+        let package_dir = dir.join("node_modules").join(name);
+        let Ok(manifest) = fs::read_to_string(package_dir.join("package.json")) else {
+            continue;
+        };
+        // Parsed in document order: the package's order decides which condition wins
+        let manifest: Json = serde_json::from_str(&manifest).unwrap_or(Json::Null);
+
+        let target = match manifest.get("exports") {
+            Some(exports) => resolve_package_exports(exports, &subpath, conditions).ok_or_else(|| {
+                ResolveError::NotExported {
+                    package: name.to_string(),
+                    subpath: subpath.clone(),
+                    conditions: conditions.to_vec(),
+                    manifest: package_dir.join("package.json").display().to_string(),
+                }
+            })?,
+            None if subpath == "." => ["module", "main"]
+                .iter()
+                .find_map(|field| manifest.get(*field).and_then(Json::as_str))
+                .unwrap_or("index.js")
+                .to_string(),
+            None => subpath,
+        };
+
+        let target = target.strip_prefix("./").unwrap_or(&target);
+        return Ok(Some(package_dir.join(target).to_string_lossy().to_string()));
+    }
+
+    Ok(None)
+}
+
+/// Resolve `subpath` ("." or "./...") through a package.json "exports" value
+pub fn resolve_package_exports(exports: &Json, subpath: &str, conditions: &[String]) -> Option<String> {
+    // "exports": "./index.js", [...] or { "import": ... } are sugar for { ".": ... }
+    let entries = match exports {
+        Json::Object(entries) if entries.iter().any(|(key, _)| key.starts_with('.')) => entries,
+        _ => {
+            return if subpath == "." {
+                resolve_export_target(exports, None, conditions)
+            } else {
+                None
+            };
+        }
+    };
+
+    if let Some(target) = exports.get(subpath) {
+        return resolve_export_target(target, None, conditions);
+    }
+
+    // Pattern exports ("./*", "./features/*.js"): the longest matching prefix wins
+    entries
+        .iter()
+        .filter_map(|(key, target)| {
+            let (prefix, suffix) = key.split_once('*')?;
+            let matched = subpath.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some((prefix.len(), matched, target))
+        })
+        .max_by_key(|(prefix_len, _, _)| *prefix_len)
+        .and_then(|(_, matched, target)| resolve_export_target(target, Some(matched), conditions))
+}
+
+/// Resolve a single exports target: a path, an array of fallbacks, or a conditions object
+fn resolve_export_target(target: &Json, pattern_match: Option<&str>, conditions: &[String]) -> Option<String> {
+    match target {
+        Json::String(path) if path.starts_with("./") => Some(match pattern_match {
+            Some(matched) => path.replace('*', matched),
+            None => path.clone(),
+        }),
+        Json::Array(fallbacks) => fallbacks
+            .iter()
+            .find_map(|fallback| resolve_export_target(fallback, pattern_match, conditions)),
+        // Conditions are checked in the package's order, not ours
+        Json::Object(entries) => entries
+            .iter()
+            .filter(|(condition, _)| conditions.contains(condition))
+            .find_map(|(_, nested)| resolve_export_target(nested, pattern_match, conditions)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parse "exports" as it would be read from a package.json
+    fn json(text: &str) -> Json {
+        serde_json::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_split_package_specifier() {
+        assert_eq!(split_package_specifier("pkg"), ("pkg", ".".to_string()));
+        assert_eq!(split_package_specifier("pkg/utils"), ("pkg", "./utils".to_string()));
+        assert_eq!(split_package_specifier("@scope/pkg"), ("@scope/pkg", ".".to_string()));
+        assert_eq!(split_package_specifier("@scope/pkg/a/b"), ("@scope/pkg", "./a/b".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_package_exports_conditions() {
+        let exports = json(r#"{
+            ".": {
+                "require": "./dist/index.cjs",
+                "import": "./dist/index.mjs",
+                "default": "./dist/index.js"
+            },
+            "./utils": {
+                "browser": "./dist/utils.browser.mjs",
+                "import": "./dist/utils.mjs"
+            },
+            "./package.json": "./package.json"
+        }"#);
+        let conditions = default_export_conditions();
+
+        assert_eq!(
+            resolve_package_exports(&exports, ".", &conditions),
+            Some("./dist/index.mjs".to_string())
+        );
+        assert_eq!(
+            resolve_package_exports(&exports, "./utils", &conditions),
+            Some("./dist/utils.mjs".to_string())
+        );
+        assert_eq!(
+            resolve_package_exports(&exports, "./utils", &["browser".to_string()]),
+            Some("./dist/utils.browser.mjs".to_string())
+        );
+        assert_eq!(
+            resolve_package_exports(&exports, ".", &["require".to_string()]),
+            Some("./dist/index.cjs".to_string())
+        );
+        assert_eq!(resolve_package_exports(&exports, "./missing", &conditions), None);
+
+        // With several conditions set, the first one the package lists wins
+        let both = ["import".to_string(), "require".to_string()];
+        assert_eq!(
+            resolve_package_exports(&exports, ".", &both),
+            Some("./dist/index.cjs".to_string())
+        );
+    }
+
+    #[test]
+    fn test_resolve_package_exports_sugar_and_patterns() {
+        assert_eq!(
+            resolve_package_exports(&json(r#""./main.js""#), ".", &default_export_conditions()),
+            Some("./main.js".to_string())
+        );
+        assert_eq!(
+            resolve_package_exports(&json(r#"{ "import": "./esm.js" }"#), ".", &default_export_conditions()),
+            Some("./esm.js".to_string())
+        );
+
+        let exports = json(r#"{
+            "./*": "./src/*.ts",
+            "./features/*": { "import": "./dist/features/*.mjs" },
+            "./internal/*": null
+        }"#);
+        let conditions = default_export_conditions();
+        assert_eq!(
+            resolve_package_exports(&exports, "./helpers", &conditions),
+            Some("./src/helpers.ts".to_string())
+        );
+        assert_eq!(
+            resolve_package_exports(&exports, "./features/a", &conditions),
+            Some("./dist/features/a.mjs".to_string())
+        );
+        assert_eq!(resolve_package_exports(&exports, "./internal/secret", &conditions), None);
+    }
+
+    #[test]
+    fn test_resolve_bare_specifier_from_node_modules() {
+        let root = std::env::temp_dir().join(format!("funee-resolve-package-{}", std::process::id()));
+        let package_dir = root.join("node_modules").join("cond-pkg");
+        fs::create_dir_all(&package_dir).unwrap();
+        fs::write(
+            package_dir.join("package.json"),
+            r#"{
+                "name": "cond-pkg",
+                "exports": {
+                    ".": { "require": "./index.cjs", "import": "./index.mjs" },
+                    "./sub": { "import": "./lib/sub.mjs" }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let base = root.join("src").join("main.ts").to_string_lossy().to_string();
+        let conditions = default_export_conditions();
+
+        assert_eq!(
+            resolve_bare_specifier("cond-pkg", &base, &conditions),
+            Ok(Some(package_dir.join("index.mjs").to_string_lossy().to_string()))
+        );
+        assert_eq!(
+            resolve_bare_specifier("cond-pkg/sub", &base, &conditions),
+            Ok(Some(package_dir.join("lib/sub.mjs").to_string_lossy().to_string()))
+        );
+        assert_eq!(resolve_bare_specifier("not-installed", &base, &conditions), Ok(None));
+        assert_eq!(resolve_bare_specifier("./local.ts", &base, &conditions), Ok(None));
+        assert!(matches!(
+            resolve_bare_specifier("cond-pkg/missing", &base, &conditions),
+            Err(ResolveError::NotExported { subpath, .. }) if subpath == "./missing"
+        ));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
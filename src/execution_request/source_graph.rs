@@ -1,11 +1,21 @@
 use super::{
     capture_closure::capture_closure,
+    data_url::{decode_data_url, is_data_uri},
     declaration::Declaration, 
     detect_macro_calls::find_macro_calls,
     get_references_from_declaration::get_references_from_declaration,
-    load_module_declaration::load_declaration,
+    import_map::resolve_import_map,
+    integrity::verify_integrity,
+    get_module_declarations::ModuleDeclaration,
+    load_module_declaration::{load_declaration, load_exports, ModuleCache},
+    namespace_import::{rewrite_namespace_members, NAMESPACE},
+    npm_specifier::{is_npm_uri, resolve_npm_uri},
+    raw_ops_lint::find_raw_ops_access,
+    resolve_error::{ResolveError, SourceLocation},
+    resolve_package::resolve_bare_specifier,
+    virtual_modules::{is_virtual_uri, VirtualFileLoader},
 };
-use crate::funee_identifier::FuneeIdentifier;
+use crate::{diagnostics, funee_identifier::FuneeIdentifier, trace};
 use petgraph::{
     stable_graph::NodeIndex,
     visit::{Dfs, EdgeRef, VisitMap},
@@ -17,8 +27,8 @@ use std::{
     path::Path,
     rc::Rc,
 };
-use swc_common::{FileLoader, FilePathMapping, Globals, Mark, SourceMap, GLOBALS};
-use swc_ecma_ast::Expr;
+use swc_common::{comments::SingleThreadedComments, FileLoader, FilePathMapping, Globals, Mark, SourceMap, Span, SyntaxContext, DUMMY_SP, GLOBALS};
+use swc_ecma_ast::{Expr, Ident, KeyValueProp, ObjectLit, Prop, PropName, PropOrSpread, Str};
 use swc_ecma_transforms_base::resolver;
 use swc_ecma_visit::VisitMutWith;
 use url::Url;
@@ -51,6 +61,8 @@ fn is_js_global(name: &str) -> bool {
         | "queueMicrotask"
         // Console
         | "console"
+        // Runtime namespace (direct use from user modules is linted, see raw_ops_lint)
+        | "Deno"
         // Web APIs commonly available
         | "fetch" | "Request" | "Response" | "Headers" | "URL" | "URLSearchParams"
         | "FormData" | "Blob" | "File" | "FileReader"
@@ -64,7 +76,7 @@ fn is_js_global(name: &str) -> bool {
 }
 
 /// Check if a URI is an HTTP/HTTPS URL
-fn is_http_uri(uri: &str) -> bool {
+pub(super) fn is_http_uri(uri: &str) -> bool {
     uri.starts_with("http://") || uri.starts_with("https://")
 }
 
@@ -73,94 +85,137 @@ fn is_host_uri(uri: &str) -> bool {
     uri.starts_with("host://")
 }
 
+/// `host` or `host:port` of an HTTP URL
+fn import_host(uri: &str) -> Option<String> {
+    let url = Url::parse(uri).ok()?;
+    let host = url.host_str()?;
+    Some(match url.port() {
+        Some(port) => format!("{}:{}", host, port),
+        None => host.to_string(),
+    })
+}
+
+/// Check whether the module at `importer` may import the HTTP module at `uri`
+///
+/// Local modules may import from any host. Remote modules may only import
+/// from their own origin, the entry's origin, or a host listed in
+/// `allowed_hosts` (as `host`, matching any port, or `host:port`).
+/// Returns the blocked host otherwise.
+fn check_import_host(uri: &str, importer: &str, entry: &str, allowed_hosts: &[String]) -> Result<(), String> {
+    if !is_http_uri(uri) || !is_http_uri(importer) {
+        return Ok(());
+    }
+    let Some(host) = import_host(uri) else {
+        return Ok(());
+    };
+    let bare_host = host.rsplit_once(':').map_or(host.as_str(), |(name, _)| name);
+    let allowed = import_host(importer).as_deref() == Some(host.as_str())
+        || import_host(entry).as_deref() == Some(host.as_str())
+        || allowed_hosts.iter().any(|allowed| allowed == &host || allowed == bare_host);
+    if allowed {
+        Ok(())
+    } else {
+        Err(host)
+    }
+}
+
 /// Resolve an import URI against the current module's URI
 /// 
 /// Handles:
 /// - "funee" -> funee-lib path
 /// - "host://*" -> host module URIs (returned as-is)
+/// - "virtual:*" -> in-memory virtual modules (returned as-is)
+/// - "data:*" -> modules carried in the URL itself (returned as-is once they decode)
+/// - "npm:pkg@range/sub" -> CDN URL of the highest matching published version
 /// - HTTP URLs (absolute) -> used as-is
 /// - Relative paths from HTTP URLs -> resolved against base URL
 /// - Absolute paths (/) from HTTP URLs -> resolved against HTTP server root
 /// - Relative paths from file paths -> resolved against base path
 /// - Absolute file paths -> used as-is
-fn resolve_import_uri(import_uri: &str, base_uri: &str, funee_lib_path: &Option<String>) -> String {
+fn resolve_import_uri(import_uri: &str, base_uri: &str, funee_lib_path: &Option<String>) -> Result<String, ResolveError> {
     // Handle bare "funee" specifier
     if import_uri == "funee" {
-        return funee_lib_path.clone().unwrap_or_else(|| {
-            eprintln!("error: Cannot resolve 'funee' - no funee_lib_path configured");
-            std::process::exit(1);
-        });
+        return funee_lib_path.clone().ok_or(ResolveError::NoFuneeLib);
+    }
+
+    // Handle host:// and virtual: URIs - return as-is
+    if is_host_uri(import_uri) || is_virtual_uri(import_uri) {
+        return Ok(import_uri.to_string());
+    }
+
+    if is_data_uri(import_uri) {
+        return decode_data_url(import_uri)
+            .map(|_| import_uri.to_string())
+            .map_err(|reason| ResolveError::InvalidDataUrl { reason });
     }
 
-    // Handle host:// URIs - return as-is
-    if is_host_uri(import_uri) {
-        return import_uri.to_string();
+    if is_npm_uri(import_uri) {
+        return resolve_npm_uri(import_uri).map_err(ResolveError::Npm);
     }
 
     // If import is already an absolute HTTP URL, use it directly
     if is_http_uri(import_uri) {
-        return import_uri.to_string();
+        return Ok(import_uri.to_string());
     }
 
-    // If import starts with '/', behavior depends on base URI type
-    if import_uri.starts_with('/') {
-        if is_http_uri(base_uri) {
-            // Base is HTTP URL - resolve absolute path against server root
-            // e.g., "/lodash-es@4.17.21/add.mjs" from "https://esm.sh/lodash-es"
-            //       -> "https://esm.sh/lodash-es@4.17.21/add.mjs"
-            match Url::parse(base_uri) {
-                Ok(base_url) => {
-                    match base_url.join(import_uri) {
-                        Ok(resolved) => return resolved.to_string(),
-                        Err(e) => {
-                            eprintln!("error: Failed to resolve '{}' from '{}': {}", import_uri, base_uri, e);
-                            std::process::exit(1);
-                        }
-                    }
-                }
-                Err(e) => {
-                    eprintln!("error: Invalid base URL '{}': {}", base_uri, e);
-                    std::process::exit(1);
-                }
-            }
-        } else {
-            // Base is file path - treat as filesystem absolute path
-            return import_uri.to_string();
-        }
+    // A data: URL module has no location for paths to be relative to
+    if is_data_uri(base_uri) {
+        return Err(ResolveError::InvalidUrl {
+            specifier: import_uri.to_string(),
+            base: "data: URL module".to_string(),
+            reason: "paths can't be imported from a data: URL; use an absolute URL".to_string(),
+        });
     }
 
-    // Relative import - resolve against base
+    // A path against an HTTP base is resolved as a URL: absolute paths
+    // against the server root, e.g. "/lodash-es@4.17.21/add.mjs" from
+    // "https://esm.sh/lodash-es" -> "https://esm.sh/lodash-es@4.17.21/add.mjs"
     if is_http_uri(base_uri) {
-        // Base is HTTP URL - resolve relative URL
-        match Url::parse(base_uri) {
-            Ok(base_url) => {
-                match base_url.join(import_uri) {
-                    Ok(resolved) => resolved.to_string(),
-                    Err(e) => {
-                        eprintln!("error: Failed to resolve '{}' from '{}': {}", import_uri, base_uri, e);
-                        std::process::exit(1);
-                    }
-                }
-            }
-            Err(e) => {
-                eprintln!("error: Invalid base URL '{}': {}", base_uri, e);
-                std::process::exit(1);
-            }
-        }
-    } else {
-        // Base is file path - resolve relative path
-        let relative_path = RelativePath::new(import_uri);
-        let current_dir = Path::new(base_uri)
-            .parent()
-            .unwrap_or(Path::new(""))
-            .to_str()
-            .unwrap_or("");
-        relative_path
-            .to_logical_path(current_dir)
-            .to_str()
-            .unwrap_or(import_uri)
-            .to_string()
+        let base_url = Url::parse(base_uri).map_err(|e| ResolveError::InvalidBaseUrl {
+            base: base_uri.to_string(),
+            reason: e.to_string(),
+        })?;
+        return base_url
+            .join(import_uri)
+            .map(|resolved| resolved.to_string())
+            .map_err(|e| ResolveError::InvalidUrl {
+                specifier: import_uri.to_string(),
+                base: base_uri.to_string(),
+                reason: e.to_string(),
+            });
+    }
+
+    // Base is file path - absolute paths are used as-is
+    if import_uri.starts_with('/') {
+        return Ok(import_uri.to_string());
+    }
+
+    // Relative path - resolve against the base's directory
+    let relative_path = RelativePath::new(import_uri);
+    let current_dir = Path::new(base_uri)
+        .parent()
+        .unwrap_or(Path::new(""))
+        .to_str()
+        .unwrap_or("");
+    Ok(relative_path
+        .to_logical_path(current_dir)
+        .to_str()
+        .unwrap_or(import_uri)
+        .to_string())
+}
+
+/// Where `span` points in the module at `uri`, unless the span is synthetic
+fn source_location(source_map: &SourceMap, uri: &str, span: Span) -> Option<SourceLocation> {
+    if span.is_dummy() {
+        return None;
     }
+    let loc = source_map.lookup_char_pos(span.lo);
+    Some(SourceLocation {
+        file: uri.to_string(),
+        line: loc.line,
+        col: loc.col_display,
+        source: loc.file.src.to_string(),
+    })
 }
 
 pub struct ReferencesMark {
@@ -175,6 +230,16 @@ pub struct SourceGraph {
     pub references_mark: ReferencesMark,
     /// Set of FuneeIdentifiers that are macro functions (created via createMacro)
     pub macro_functions: HashSet<FuneeIdentifier>,
+    /// Node of each loaded declaration, keyed both by the references that led
+    /// to it and by its own (module URI, name)
+    pub definitions_index: HashMap<FuneeIdentifier, NodeIndex>,
+    /// Comments of every loaded module, collected when comments are preserved
+    pub comments: Option<SingleThreadedComments>,
+    /// Emit throwing stubs for host capabilities (see `LoadParams::sandbox`)
+    pub sandbox: bool,
+    /// Resolution settings and caches, kept so macro expansion can load
+    /// declarations that only a macro's output refers to
+    loader: Loader,
 }
 
 pub struct LoadParams {
@@ -184,13 +249,49 @@ pub struct LoadParams {
     pub file_loader: Box<dyn FileLoader + Sync + Send>,
     /// Path to the funee standard library (funee-lib/index.ts)
     pub funee_lib_path: Option<String>,
+    /// Conditions used to pick package.json "exports" entries for bare specifiers
+    pub export_conditions: Vec<String>,
+    /// In-memory module sources keyed by name, served before the file loader
+    pub virtual_modules: HashMap<String, String>,
+    /// Keep comments (at least license banners) in the emitted code
+    pub preserve_comments: bool,
+    /// Fail instead of warning when a user module accesses `Deno.core`
+    pub deny_raw_ops: bool,
+    /// Replace fs, network and process host modules (and the fetch, serve and
+    /// spawn globals) with stubs that throw when called
+    pub sandbox: bool,
+    /// Hosts remote modules may import from besides their own and the entry's
+    pub allowed_import_hosts: Vec<String>,
+    /// Import map consulted before any other resolution
+    pub import_map: HashMap<String, String>,
+}
+
+/// The parts of `LoadParams` used to resolve references, plus per-run caches
+struct Loader {
+    scope: String,
+    host_functions: HashSet<FuneeIdentifier>,
+    funee_lib_path: Option<String>,
+    /// funee-lib talks to the host directly; everything else should go through imports
+    funee_lib_dir: Option<String>,
+    export_conditions: Vec<String>,
+    deny_raw_ops: bool,
+    allowed_import_hosts: Vec<String>,
+    import_map: HashMap<String, String>,
+    module_cache: ModuleCache,
+    /// (module URI, integrity) pairs already checked
+    verified_integrity: HashSet<(String, String)>,
 }
 
 impl SourceGraph {
-    pub fn load(params: LoadParams) -> Self {
+    /// Load everything the entry expression references, following imports
+    /// from `params.scope`
+    pub fn load(params: LoadParams) -> Result<Self, ResolveError> {
         let globals = Globals::default();
         let cm = Rc::new(SourceMap::with_file_loader(
-            params.file_loader,
+            Box::new(VirtualFileLoader {
+                modules: params.virtual_modules,
+                inner: params.file_loader,
+            }),
             FilePathMapping::empty(),
         ));
         let unresolved_mark = GLOBALS.set(&globals, || Mark::new());
@@ -203,161 +304,433 @@ impl SourceGraph {
             root_expr.visit_mut_with(resolver_pass);
         });
         
-        let mut definitions_index = HashMap::new();
         let mut graph = Graph::new();
-        let mut macro_functions: HashSet<FuneeIdentifier> = HashSet::new();
-        let root_node = graph.add_node((params.scope, Declaration::Expr(root_expr)));
-        let mut dfs = Dfs::new(&graph, root_node);
-        while let Some(nx) = dfs.next(&graph) {
-            let (t, declaration) = &mut graph[nx];
+        let root_node = graph.add_node((params.scope.clone(), Declaration::Expr(root_expr)));
+        let funee_lib_dir = params.funee_lib_path
+            .as_ref()
+            .and_then(|path| Path::new(path).parent())
+            .map(|dir| dir.to_string_lossy().to_string());
+
+        let mut instance = Self {
+            graph,
+            source_map: cm,
+            references_mark: ReferencesMark {
+                mark: unresolved_mark,
+                globals,
+            },
+            root: root_node,
+            macro_functions: HashSet::new(),
+            definitions_index: HashMap::new(),
+            comments: params.preserve_comments.then(SingleThreadedComments::default),
+            sandbox: params.sandbox,
+            loader: Loader {
+                scope: params.scope,
+                host_functions: params.host_functions,
+                funee_lib_path: params.funee_lib_path,
+                funee_lib_dir,
+                export_conditions: params.export_conditions,
+                deny_raw_ops: params.deny_raw_ops,
+                allowed_import_hosts: params.allowed_import_hosts,
+                import_map: params.import_map,
+                module_cache: ModuleCache::new(),
+                verified_integrity: HashSet::new(),
+            },
+        };
+
+        let raw_ops_found = instance.load_from(root_node)?;
+        if raw_ops_found && instance.loader.deny_raw_ops {
+            return Err(ResolveError::RawOpsDenied);
+        }
+
+        // Step 2: Process macro calls now that the graph is fully built
+        instance.process_macro_calls();
+
+        Ok(instance)
+    }
+
+    /// Load everything reachable from `start` that isn't in the graph yet
+    /// Returns whether a user module accesses `Deno.core` directly
+    fn load_from(&mut self, start: NodeIndex) -> Result<bool, ResolveError> {
+        let mut raw_ops_found = false;
+        let mut dfs = Dfs::new(&self.graph, start);
+        while let Some(nx) = dfs.next(&self.graph) {
+            let (t, declaration) = &mut self.graph[nx];
             let source_uri = t.clone(); // Clone early for error messages
-            let references = match declaration {
+            let is_funee_lib = self.loader.funee_lib_dir.as_ref().is_some_and(|dir| source_uri.starts_with(dir.as_str()));
+            if !is_funee_lib {
+                for span in find_raw_ops_access(declaration) {
+                    raw_ops_found = true;
+                    let message = "Direct access to Deno.core bypasses funee's host imports";
+                    if self.loader.deny_raw_ops {
+                        diagnostics::error(message);
+                    } else {
+                        diagnostics::warning(message);
+                    }
+                    let loc = self.source_map.lookup_char_pos(span.lo);
+                    diagnostics::note(format!("{}:{}:{}", source_uri, loc.line, loc.col_display + 1));
+                }
+            }
+            // (local name, what it refers to, span of its first use)
+            let references: Vec<(String, FuneeIdentifier, Span)> = match declaration {
                 Declaration::FuneeIdentifier(identifier) => {
-                    HashMap::from([(t.clone(), identifier.clone())])
+                    vec![(t.clone(), identifier.clone(), DUMMY_SP)]
                 }
-                _ => get_references_from_declaration(declaration, (&globals, unresolved_mark))
+                _ => get_references_from_declaration(declaration, (&self.references_mark.globals, self.references_mark.mark))
                     .into_iter()
-                    .map(|x| {
+                    .map(|(x, span)| {
                         (
                             x.clone(),
                             FuneeIdentifier {
-                                name: x.clone(),
+                                name: x,
                                 uri: t.clone(),
                             },
+                            span,
                         )
                     })
                     .collect(),
             };
+            let references = self.expand_namespace_references(nx, references)?;
 
-            for reference in references {
+            for (local_name, identifier, span) in references {
                 // Skip JavaScript globals - they're provided by the runtime
-                if is_js_global(&reference.0) {
+                if is_js_global(&local_name) {
                     continue;
                 }
 
-                // Resolve the reference to a declaration and track the final URI
-                // This is important for import chains: entry.ts -> a.ts -> b.ts
-                // When we resolve levelOne from entry.ts, we follow the import to a.ts
-                // The node should have a.ts as its URI so references within levelOne resolve correctly
-                let (declaration, resolved_uri) = if params.host_functions.contains(&reference.1) {
-                    (
-                        Declaration::HostFn(
-                            params
-                                .host_functions
-                                .get(&reference.1)
-                                .unwrap()
-                                .name
-                                .clone(),
-                        ),
-                        reference.1.uri.clone(), // Host functions don't need real URI
-                    )
-                } else {
-                    let mut current_identifier = reference.1.clone();
-                    loop {
-                        // Check for host:// URIs - these are built-in host modules
-                        if is_host_uri(&current_identifier.uri) {
-                            let namespace = current_identifier.uri
-                                .strip_prefix("host://")
-                                .unwrap()
-                                .to_string();
-                            break (
-                                Declaration::HostModule(namespace, current_identifier.name.clone()),
-                                current_identifier.uri.clone(),
-                            );
-                        }
-
-                        let err_source = source_uri.clone();
-                        let err_name = current_identifier.name.clone();
-                        let err_module = current_identifier.uri.clone();
-                        let declaration = load_declaration(&cm, &current_identifier)
-                            .unwrap_or_else(|| {
-                                eprintln!("error: Cannot find '{}' in module '{}'", 
-                                    err_name, err_module);
-                                eprintln!("  --> Referenced from: {}", err_source);
-                                std::process::exit(1);
-                            })
-                            .declaration;
-
-                        if let Declaration::FuneeIdentifier(i) = declaration {
-                            if params.host_functions.contains(&i) {
-                                break (
-                                    Declaration::HostFn(
-                                        params.host_functions.get(&i).unwrap().name.clone(),
-                                    ),
-                                    current_identifier.uri.clone(),
-                                );
-                            }
-                            // Resolve the import URI
-                            let resolved_uri = resolve_import_uri(
-                                &i.uri, 
-                                &current_identifier.uri,
-                                &params.funee_lib_path
-                            );
-                            current_identifier = FuneeIdentifier {
-                                name: i.name,
-                                uri: resolved_uri,
-                            };
-                        } else {
-                            break (declaration, current_identifier.uri.clone());
-                        }
-                    }
-                };
-
-                if !definitions_index.contains_key(&reference.1) {
-                    // Track macro functions for later macro expansion
-                    // Use the resolved_uri, not the original reference URI
-                    if matches!(&declaration, Declaration::Macro(_)) {
-                        macro_functions.insert(FuneeIdentifier {
-                            name: reference.1.name.clone(),
-                            uri: resolved_uri.clone(),
-                        });
-                    }
-                    
-                    let node_index = graph.add_node((resolved_uri, declaration));
-                    graph.add_edge(nx, node_index, reference.0);
-                    definitions_index.insert(reference.1, node_index);
-
+                if let Some(node_index) = self.add_reference(nx, local_name, identifier, span)? {
                     if !dfs.discovered.is_visited(&node_index) {
-                        dfs.discovered.grow(graph.node_count());
+                        dfs.discovered.grow(self.graph.node_count());
                         dfs.stack.push(node_index);
                     }
-                } else {
-                    let node_index = definitions_index.get(&reference.1).unwrap();
-                    graph.add_edge(nx, *node_index, reference.0);
                 }
             }
         }
+        Ok(raw_ops_found)
+    }
 
-        let mut instance = Self {
-            graph,
-            source_map: cm,
-            references_mark: ReferencesMark {
-                mark: unresolved_mark,
-                globals,
-            },
-            root: root_node,
-            macro_functions,
+    /// Replace the namespace imports (`import * as ns`) among the references
+    /// of the declaration at `nx` with the members it accesses, rewriting
+    /// `ns.member` in its AST to match. `ns` itself stays a reference only
+    /// where it's used as a value, so unused members aren't bundled.
+    fn expand_namespace_references(
+        &mut self,
+        nx: NodeIndex,
+        references: Vec<(String, FuneeIdentifier, Span)>,
+    ) -> Result<Vec<(String, FuneeIdentifier, Span)>, ResolveError> {
+        if matches!(self.graph[nx].1, Declaration::FuneeIdentifier(_)) {
+            return Ok(references);
+        }
+        let mut expanded = Vec::with_capacity(references.len());
+        for (local_name, identifier, span) in references {
+            let specifier = match load_declaration(&self.source_map, &mut self.loader.module_cache, self.comments.as_ref(), &identifier)? {
+                Some(ModuleDeclaration { declaration: Declaration::FuneeIdentifier(i), .. }) if i.name == NAMESPACE => i.uri,
+                _ => {
+                    expanded.push((local_name, identifier, span));
+                    continue;
+                }
+            };
+            let found = rewrite_namespace_members(
+                &mut self.graph[nx].1,
+                &local_name,
+                (&self.references_mark.globals, self.references_mark.mark),
+            );
+            let target = self.resolve_import(&specifier, &identifier.uri)?;
+            for (member_local_name, (name, member_span)) in found.members {
+                // Host functions are keyed by the specifier, e.g. ("log", "funee")
+                let mut member = FuneeIdentifier { name, uri: specifier.clone() };
+                if !self.loader.host_functions.contains(&member) {
+                    member.uri = target.clone();
+                }
+                expanded.push((member_local_name, member, member_span));
+            }
+            if found.used_as_value {
+                expanded.push((local_name, identifier, span));
+            }
+        }
+        Ok(expanded)
+    }
+
+    /// Add an edge named `local_name` from `from` to the declaration `identifier`
+    /// refers to, resolving and adding that declaration first if it isn't indexed
+    /// yet. Returns the node when it was newly added (its own references still
+    /// need loading). `span` is where `from` uses the name, if known.
+    fn add_reference(&mut self, from: NodeIndex, local_name: String, identifier: FuneeIdentifier, span: Span) -> Result<Option<NodeIndex>, ResolveError> {
+        if let Some(node_index) = self.definitions_index.get(&identifier) {
+            self.graph.add_edge(from, *node_index, local_name);
+            return Ok(None);
+        }
+
+        let source_uri = self.graph[from].0.clone();
+        let (declaration, canonical) = self.resolve_reference(&identifier, &source_uri, span)?;
+        // Reached before under another name (e.g. imported into two modules)
+        if let Some(node_index) = self.definitions_index.get(&canonical).copied() {
+            self.definitions_index.insert(identifier, node_index);
+            self.graph.add_edge(from, node_index, local_name);
+            return Ok(None);
+        }
+
+        // Track macro functions for later macro expansion
+        // Use the resolved URI, not the original reference URI
+        if matches!(&declaration, Declaration::Macro(_)) {
+            self.macro_functions.insert(FuneeIdentifier {
+                name: identifier.name.clone(),
+                uri: canonical.uri.clone(),
+            });
+        }
+
+        let node_index = self.graph.add_node((canonical.uri.clone(), declaration));
+        self.graph.add_edge(from, node_index, local_name);
+        self.definitions_index.insert(identifier, node_index);
+        self.definitions_index.insert(canonical, node_index);
+        Ok(Some(node_index))
+    }
+
+    /// Follow `reference` (a name as seen from `source_uri`) through imports and
+    /// re-exports to its declaration
+    ///
+    /// Returns the declaration and the identifier it is declared under. This
+    /// is important for import chains: entry.ts -> a.ts -> b.ts
+    /// When we resolve levelOne from entry.ts, we follow the import to a.ts
+    /// The node should have a.ts as its URI so references within levelOne resolve correctly
+    ///
+    /// A name that can't be found is reported at the import (or re-export)
+    /// that asked for it, or at `span` in `source_uri` if it was never imported.
+    fn resolve_reference(&mut self, reference: &FuneeIdentifier, source_uri: &str, span: Span) -> Result<(Declaration, FuneeIdentifier), ResolveError> {
+        if let Some(host_fn) = self.loader.host_functions.get(reference) {
+            // Host functions don't need real URI
+            return Ok((Declaration::HostFn(host_fn.name.clone()), reference.clone()));
+        }
+
+        let mut current_identifier = reference.clone();
+        // The module and span that asked for `current_identifier`
+        let mut requested_at = (source_uri.to_string(), span);
+        loop {
+            // Check for host:// URIs - these are built-in host modules
+            if is_host_uri(&current_identifier.uri) {
+                let namespace = current_identifier.uri
+                    .strip_prefix("host://")
+                    .unwrap()
+                    .to_string();
+                return Ok((
+                    Declaration::HostModule(namespace, current_identifier.name.clone()),
+                    current_identifier,
+                ));
+            }
+
+            if current_identifier.name == NAMESPACE {
+                let namespace = self.namespace_object(&current_identifier.uri)?;
+                return Ok((namespace, current_identifier));
+            }
+
+            let Some(module_declaration) = load_declaration(&self.source_map, &mut self.loader.module_cache, self.comments.as_ref(), &current_identifier)? else {
+                // Not declared here; look behind the module's `export *` re-exports
+                let location = source_location(&self.source_map, &requested_at.0, requested_at.1);
+                let mut sources = self.star_export_sources(&current_identifier, &mut HashSet::new())?;
+                if sources.len() > 1 {
+                    return Err(ResolveError::AmbiguousExport {
+                        name: current_identifier.name,
+                        uri: current_identifier.uri,
+                        sources: sources.into_iter().map(|(uri, _)| uri).collect(),
+                        location,
+                    });
+                }
+                let Some((declared_in, star_span)) = sources.pop() else {
+                    return Err(ResolveError::NotFound {
+                        name: current_identifier.name,
+                        uri: current_identifier.uri,
+                        referenced_from: source_uri.to_string(),
+                        location,
+                    });
+                };
+                requested_at = (current_identifier.uri, star_span);
+                current_identifier = FuneeIdentifier {
+                    name: current_identifier.name,
+                    uri: declared_in,
+                };
+                continue;
+            };
+
+            let i = match module_declaration.declaration {
+                Declaration::FuneeIdentifier(i) => i,
+                declaration => return Ok((declaration, current_identifier)),
+            };
+            if let Some(host_fn) = self.loader.host_functions.get(&i) {
+                return Ok((Declaration::HostFn(host_fn.name.clone()), i));
+            }
+            let resolved_uri = self.resolve_import(&i.uri, &current_identifier.uri)?;
+            requested_at = (current_identifier.uri, module_declaration.span);
+            current_identifier = FuneeIdentifier {
+                name: i.name,
+                uri: resolved_uri,
+            };
+        }
+    }
+
+    /// Resolve `specifier` as imported by the module at `importer`, then apply
+    /// the import's attributes to the resolved module: `type: "json"` marks it
+    /// as JSON and `integrity` is checked against its contents
+    fn resolve_import(&mut self, specifier: &str, importer: &str) -> Result<String, ResolveError> {
+        let loader = &mut self.loader;
+        let resolved_uri = {
+            let _span = trace::span("resolve");
+            match resolve_import_map(specifier, &loader.import_map) {
+                Some(mapped) => mapped,
+                None => match resolve_bare_specifier(specifier, importer, &loader.export_conditions)? {
+                    Some(package_path) => package_path,
+                    None => resolve_import_uri(specifier, importer, &loader.funee_lib_path)?,
+                },
+            }
         };
+        if let Err(host) = check_import_host(&resolved_uri, importer, &loader.scope, &loader.allowed_import_hosts) {
+            return Err(ResolveError::BlockedHost {
+                uri: resolved_uri,
+                host,
+                importer: importer.to_string(),
+            });
+        }
+        let import_type = loader.module_cache.import_attribute(importer, specifier, "type");
+        if import_type.is_some_and(|import_type| import_type == "json") {
+            loader.module_cache.mark_json(&resolved_uri);
+        }
+        let integrity = loader.module_cache
+            .import_attribute(importer, specifier, "integrity")
+            .filter(|_| !is_host_uri(&resolved_uri));
+        if let Some(integrity) = integrity {
+            let key = (resolved_uri.clone(), integrity.clone());
+            if !loader.verified_integrity.contains(&key) {
+                let source = self.source_map.load_file(Path::new(&resolved_uri)).map_err(|e| ResolveError::Unreadable {
+                    uri: resolved_uri.clone(),
+                    reason: e.to_string(),
+                })?;
+                if let Err(actual) = verify_integrity(&source.src, integrity) {
+                    return Err(ResolveError::IntegrityMismatch {
+                        specifier: specifier.to_string(),
+                        importer: importer.to_string(),
+                        expected: integrity.clone(),
+                        actual,
+                    });
+                }
+                loader.verified_integrity.insert(key);
+            }
+        }
+        Ok(resolved_uri)
+    }
 
-        // Step 2: Process macro calls now that the graph is fully built
-        instance.process_macro_calls(&mut definitions_index, &mut dfs);
+    /// The namespace object of the module at `uri`, for a namespace import
+    /// used as a value: `{ "name": name, ... }` over its exports, including
+    /// those forwarded by `export *` unless they're ambiguous
+    fn namespace_object(&mut self, uri: &str) -> Result<Declaration, ResolveError> {
+        let mut props = vec![];
+        for name in self.export_names(uri, &mut HashSet::new())? {
+            let identifier = FuneeIdentifier { name, uri: uri.to_string() };
+            let declared = load_declaration(&self.source_map, &mut self.loader.module_cache, self.comments.as_ref(), &identifier)?
+                .is_some_and(|declaration| declaration.exported);
+            if !declared && self.star_export_sources(&identifier, &mut HashSet::new())?.len() != 1 {
+                continue;
+            }
+            props.push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                key: PropName::Str(Str {
+                    span: DUMMY_SP,
+                    value: swc_atoms::Atom::from(identifier.name.as_str()).into(),
+                    raw: None,
+                }),
+                value: Box::new(Expr::Ident(Ident::new(identifier.name.into(), DUMMY_SP, SyntaxContext::empty()))),
+            }))));
+        }
+        Ok(Declaration::VarInit(Expr::Object(ObjectLit { span: DUMMY_SP, props })))
+    }
+
+    /// Names the module at `uri` exports, itself or through `export *`
+    fn export_names(&mut self, uri: &str, visited: &mut HashSet<String>) -> Result<Vec<String>, ResolveError> {
+        if !visited.insert(uri.to_string()) {
+            return Ok(vec![]);
+        }
+        let mut names = load_exports(&self.source_map, &mut self.loader.module_cache, self.comments.as_ref(), uri)?;
+        for (specifier, _) in self.loader.module_cache.star_exports(uri).to_vec() {
+            let target = self.resolve_import(&specifier, uri)?;
+            if is_host_uri(&target) {
+                continue;
+            }
+            for name in self.export_names(&target, visited)? {
+                if name != "default" && !names.contains(&name) {
+                    names.push(name);
+                }
+            }
+        }
+        Ok(names)
+    }
 
-        instance
+    /// Modules that export `identifier.name` through the `export * from`
+    /// re-exports of `identifier.uri`, searched transitively, each with the
+    /// span of the `export *` in `identifier.uri` that leads to it
+    ///
+    /// `export *` never forwards "default". Sources whose names resolve to the
+    /// same binding (e.g. two modules that both `export { x } from "./x.ts"`)
+    /// are counted once, so they aren't ambiguous.
+    fn star_export_sources(&mut self, identifier: &FuneeIdentifier, visited: &mut HashSet<String>) -> Result<Vec<(String, Span)>, ResolveError> {
+        if identifier.name == "default" || !visited.insert(identifier.uri.clone()) {
+            return Ok(vec![]);
+        }
+        let mut sources: Vec<(String, Span)> = vec![];
+        let mut bindings: Vec<FuneeIdentifier> = vec![];
+        for (specifier, span) in self.loader.module_cache.star_exports(&identifier.uri).to_vec() {
+            let target = FuneeIdentifier {
+                name: identifier.name.clone(),
+                uri: self.resolve_import(&specifier, &identifier.uri)?,
+            };
+            // Host modules can't be enumerated, so they're skipped
+            if is_host_uri(&target.uri) {
+                continue;
+            }
+            let found = match load_declaration(&self.source_map, &mut self.loader.module_cache, self.comments.as_ref(), &target)? {
+                Some(declaration) if declaration.exported => vec![target.uri],
+                _ => self
+                    .star_export_sources(&target, visited)?
+                    .into_iter()
+                    .map(|(uri, _)| uri)
+                    .collect(),
+            };
+            for uri in found {
+                let candidate = FuneeIdentifier { name: identifier.name.clone(), uri };
+                let (_, binding) = self.resolve_reference(&candidate, &identifier.uri, span)?;
+                if !bindings.contains(&binding) {
+                    bindings.push(binding);
+                    sources.push((candidate.uri, span));
+                }
+            }
+        }
+        Ok(sources)
+    }
+
+    /// Link a reference introduced by a macro's output: `local_name` in the
+    /// expanded node `from` refers to export `identifier`. Declarations that
+    /// aren't in the graph yet are loaded along with their own references.
+    pub(super) fn add_macro_reference(&mut self, from: NodeIndex, local_name: String, identifier: FuneeIdentifier) -> Result<(), ResolveError> {
+        if let Some(node_index) = self.add_reference(from, local_name, identifier, DUMMY_SP)? {
+            if self.load_from(node_index)? && self.loader.deny_raw_ops {
+                return Err(ResolveError::RawOpsDenied);
+            }
+        }
+        Ok(())
     }
 
     /// Process macro calls in the graph after it's fully constructed
     /// This needs to be a second pass because macros might be defined later in the module tree
-    fn process_macro_calls(
-        &mut self,
-        definitions_index: &mut HashMap<FuneeIdentifier, NodeIndex>,
-        dfs: &mut Dfs<NodeIndex, <petgraph::stable_graph::StableGraph<(String, Declaration), String> as petgraph::visit::Visitable>::Map>,
-    ) {
+    fn process_macro_calls(&mut self) {
         let globals = &self.references_mark.globals;
         let unresolved_mark = self.references_mark.mark;
 
         // Collect nodes to process (to avoid borrow issues)
         let nodes_to_process: Vec<_> = self.graph.node_indices().collect();
 
+        // The name each declaration has in its own module, so captured references
+        // name the export rather than whatever a module imported it as
+        let declared_names: HashMap<NodeIndex, String> = self.definitions_index
+            .iter()
+            .filter(|(identifier, node)| self.graph[**node].0 == identifier.uri)
+            .map(|(identifier, node)| (*node, identifier.name.clone()))
+            .collect();
+
         for nx in nodes_to_process {
             // Clone the data we need
             let (source_uri, mut declaration_clone) = {
@@ -384,11 +757,11 @@ impl SourceGraph {
                     
                     // Get the export name - for most declarations it's the same as local name
                     // but we extract it from the target node's declaration
-                    let export_name = match target_decl {
+                    let export_name = declared_names.get(&target_node).cloned().unwrap_or_else(|| match target_decl {
                         Declaration::FnDecl(fn_decl) => fn_decl.ident.sym.to_string(),
                         Declaration::HostFn(name) => name.clone(),
                         _ => local_name.clone(),
-                    };
+                    });
                     
                     current_scope_refs.insert(
                         local_name,
@@ -419,19 +792,14 @@ impl SourceGraph {
                         };
 
                         // Add the Closure as a node in the graph
-                        if !definitions_index.contains_key(&closure_identifier) {
+                        // (its references are already edges of this node, so it needs no loading)
+                        if !self.definitions_index.contains_key(&closure_identifier) {
                             let closure_node = self.graph.add_node((
                                 source_uri.clone(),
                                 Declaration::ClosureValue(closure),
                             ));
                             self.graph.add_edge(nx, closure_node, closure_name.clone());
-                            definitions_index.insert(closure_identifier, closure_node);
-
-                            // Add to DFS stack to process closure's references
-                            if !dfs.discovered.is_visited(&closure_node) {
-                                dfs.discovered.grow(self.graph.node_count());
-                                dfs.stack.push(closure_node);
-                            }
+                            self.definitions_index.insert(closure_identifier, closure_node);
                         }
                     }
                 }
@@ -444,6 +812,47 @@ impl SourceGraph {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_check_import_host() {
+        let entry = "/project/main.ts";
+        // Local modules may import from anywhere
+        assert!(check_import_host("https://b.example/mod.ts", entry, entry, &[]).is_ok());
+        // Remote modules may import from their own origin
+        assert!(check_import_host("https://a.example/other.ts", "https://a.example/mod.ts", entry, &[]).is_ok());
+        // ...but not from another host, unless it's allowlisted
+        assert_eq!(
+            check_import_host("https://b.example/x.ts", "https://a.example/mod.ts", entry, &[]),
+            Err("b.example".to_string())
+        );
+        assert!(check_import_host(
+            "https://b.example/x.ts",
+            "https://a.example/mod.ts",
+            entry,
+            &["b.example".to_string()]
+        )
+        .is_ok());
+        // A different port is a different origin; "host" allows any port
+        assert_eq!(
+            check_import_host("http://a.example:8080/x.ts", "http://a.example/mod.ts", entry, &[]),
+            Err("a.example:8080".to_string())
+        );
+        assert!(check_import_host(
+            "http://a.example:8080/x.ts",
+            "http://b.example/mod.ts",
+            entry,
+            &["a.example".to_string()]
+        )
+        .is_ok());
+        // The entry's origin is always allowed
+        assert!(check_import_host(
+            "https://entry.example/lib.ts",
+            "https://a.example/mod.ts",
+            "https://entry.example/main.ts",
+            &[]
+        )
+        .is_ok());
+    }
+
     #[test]
     fn test_resolve_import_uri_absolute_path_from_http() {
         // This is the esm.sh case: importing "/lodash-es@4.17.21/es2022/add.mjs"
@@ -452,7 +861,8 @@ mod tests {
             "/lodash-es@4.17.21/es2022/add.mjs",
             "https://esm.sh/lodash-es@4.17.21/add",
             &None,
-        );
+        )
+        .unwrap();
         assert_eq!(result, "https://esm.sh/lodash-es@4.17.21/es2022/add.mjs");
     }
 
@@ -463,7 +873,8 @@ mod tests {
             "/lib/utils.ts",
             "https://example.com/packages/my-lib/index.ts",
             &None,
-        );
+        )
+        .unwrap();
         assert_eq!(result, "https://example.com/lib/utils.ts");
     }
 
@@ -474,7 +885,8 @@ mod tests {
             "/usr/local/lib/module.ts",
             "/home/user/project/main.ts",
             &None,
-        );
+        )
+        .unwrap();
         assert_eq!(result, "/usr/local/lib/module.ts");
     }
 
@@ -484,7 +896,8 @@ mod tests {
             "./utils.ts",
             "https://example.com/lib/mod.ts",
             &None,
-        );
+        )
+        .unwrap();
         assert_eq!(result, "https://example.com/lib/utils.ts");
     }
 
@@ -494,7 +907,8 @@ mod tests {
             "../other.ts",
             "https://example.com/lib/nested/mod.ts",
             &None,
-        );
+        )
+        .unwrap();
         assert_eq!(result, "https://example.com/lib/other.ts");
     }
 
@@ -504,7 +918,8 @@ mod tests {
             "https://cdn.example.com/lodash.js",
             "https://esm.sh/lodash-es",
             &None,
-        );
+        )
+        .unwrap();
         assert_eq!(result, "https://cdn.example.com/lodash.js");
     }
 
@@ -514,7 +929,8 @@ mod tests {
             "funee",
             "/some/path/module.ts",
             &Some("/path/to/funee-lib/index.ts".to_string()),
-        );
+        )
+        .unwrap();
         assert_eq!(result, "/path/to/funee-lib/index.ts");
     }
 
@@ -524,7 +940,8 @@ mod tests {
             "./utils.ts",
             "/home/user/project/src/main.ts",
             &None,
-        );
+        )
+        .unwrap();
         assert_eq!(result, "/home/user/project/src/utils.ts");
     }
 
@@ -535,7 +952,8 @@ mod tests {
             "host://fs",
             "/home/user/project/main.ts",
             &None,
-        );
+        )
+        .unwrap();
         assert_eq!(result, "host://fs");
     }
 
@@ -546,10 +964,37 @@ mod tests {
             "host://http/server",
             "/home/user/project/main.ts",
             &None,
-        );
+        )
+        .unwrap();
         assert_eq!(result, "host://http/server");
     }
 
+    #[test]
+    fn test_resolve_import_uri_data_url() {
+        let data_url = "data:text/typescript;base64,ZXhwb3J0IGNvbnN0IHggPSAxOw==";
+        assert_eq!(resolve_import_uri(data_url, "/home/user/project/main.ts", &None).as_deref(), Ok(data_url));
+        assert!(matches!(
+            resolve_import_uri("data:text/typescript;base64", "/home/user/project/main.ts", &None),
+            Err(ResolveError::InvalidDataUrl { .. })
+        ));
+        assert!(matches!(
+            resolve_import_uri("./sibling.ts", data_url, &None),
+            Err(ResolveError::InvalidUrl { .. })
+        ));
+    }
+
+    #[test]
+    fn test_resolve_import_uri_errors() {
+        assert_eq!(
+            resolve_import_uri("funee", "/home/user/project/main.ts", &None),
+            Err(ResolveError::NoFuneeLib)
+        );
+        assert!(matches!(
+            resolve_import_uri("./utils.ts", "https://[invalid/mod.ts", &None),
+            Err(ResolveError::InvalidBaseUrl { base, .. }) if base == "https://[invalid/mod.ts"
+        ));
+    }
+
     #[test]
     fn test_is_host_uri() {
         assert!(is_host_uri("host://fs"));
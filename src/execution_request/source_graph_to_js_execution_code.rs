@@ -1,28 +1,63 @@
 use super::{
     declaration::Declaration,
-    get_inline_source_map::get_inline_source_map,
+    get_inline_source_map::{build_source_map, get_inline_source_map},
     get_references_from_declaration::rename_references_in_declaration,
     macro_runtime::{MacroClosure, MacroRuntime},
+    resolve_error::ResolveError,
     source_graph::SourceGraph,
 };
-use crate::emit_module::emit_module;
+use crate::{diagnostics, emit_module::{emit_module, take_license_comments}, funee_identifier::FuneeIdentifier, trace};
 use petgraph::{
     stable_graph::NodeIndex,
     visit::{DfsPostOrder, EdgeRef},
     Direction::Outgoing,
 };
-use std::collections::HashMap;
-use swc_common::{Mark, GLOBALS};
-use swc_ecma_ast::{CallExpr, Callee, Expr, Module, ModuleItem};
+use std::collections::{HashMap, HashSet};
+use swc_common::{comments::Comments, Mark, SyntaxContext, DUMMY_SP, GLOBALS};
+use swc_ecma_ast::{
+    AssignExpr, AssignOp, AssignTarget, CallExpr, Callee, Expr, ExprStmt, Ident, IdentName, MemberExpr, MemberProp, Module,
+    ModuleItem, SimpleAssignTarget, Stmt,
+};
 use swc_ecma_codegen::{text_writer::JsWriter, Emitter};
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsSyntax};
 use swc_ecma_transforms_base::resolver;
 use swc_ecma_visit::VisitMutWith;
 
+/// Module format of an emitted bundle
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EmitFormat {
+    /// Declarations followed by the entry expression as a statement; what funee runs
+    #[default]
+    Esm,
+    /// CommonJS: the entry expression's value is assigned to `module.exports`
+    Cjs,
+}
+
 impl SourceGraph {
-    pub fn into_js_execution_code(mut self) -> String {
+    /// Emit the bundle with its source map inlined as a trailing data: URL
+    pub fn into_js_execution_code(self) -> Result<String, ResolveError> {
+        let (code, srcmap) = self.into_js_execution_code_with_map()?;
+        Ok(format!("{}{}", code, get_inline_source_map(&srcmap)))
+    }
+
+    /// Emit the bundle and its source map separately
+    pub fn into_js_execution_code_with_map(self) -> Result<(String, sourcemap::SourceMap), ResolveError> {
+        self.into_js_code_with_map(EmitFormat::Esm)
+    }
+
+    /// Emit the bundle in `format`, with its source map separately
+    ///
+    /// The bundle has no import or export statements of its own (host modules
+    /// are inlined in the preamble), so only the entry expression differs
+    /// between formats. Fails if a macro's output refers to a declaration
+    /// that can't be loaded.
+    pub fn into_js_code_with_map(mut self, format: EmitFormat) -> Result<(String, sourcemap::SourceMap), ResolveError> {
         // First, expand all macro calls in the graph
-        self.expand_macros();
+        let expanded_closures = {
+            let _span = trace::span("macro-expand");
+            self.expand_macros()?
+        };
+        let _span = trace::span("emit");
         
         // Collect all host modules used in the graph
         let mut host_namespaces: std::collections::HashSet<String> = std::collections::HashSet::new();
@@ -42,8 +77,9 @@ impl SourceGraph {
                 continue;
             }
             
-            // Skip closure values - they're internal representations
-            if matches!(declaration, Declaration::ClosureValue(_)) {
+            // Skip closure values consumed by an expanded macro call; the ones
+            // whose call survives to runtime are emitted as Closure objects
+            if matches!(declaration, Declaration::ClosureValue(_)) && expanded_closures.contains(&nx) {
                 continue;
             }
             
@@ -58,32 +94,53 @@ impl SourceGraph {
                 })
                 .collect();
             let mut declaration = self.graph[nx].1.clone();
-            rename_references_in_declaration(
-                &mut declaration,
-                to_replace,
-                (&self.references_mark.globals, self.references_mark.mark),
-            );
-            module_items.push(
-                declaration.into_module_item(format!("declaration_{}", nx.index())),
-            );
+            // A closure's source keeps its local names, which are the keys of its references
+            if !matches!(declaration, Declaration::ClosureValue(_)) {
+                rename_references_in_declaration(
+                    &mut declaration,
+                    to_replace,
+                    (&self.references_mark.globals, self.references_mark.mark),
+                );
+            }
+            let item = declaration.into_module_item(format!("declaration_{}", nx.index()), &self.source_map);
+            module_items.push(if format == EmitFormat::Cjs && nx == self.root {
+                assign_to_module_exports(item)
+            } else {
+                item
+            });
         }
         let module = Module {
             body: module_items,
             shebang: None,
             span: Default::default(),
         };
-        let (mut srcmap, buf) = emit_module(self.source_map.clone(), module);
+        // License banners go first; any other preserved comments stay attached to their code
+        let license_banner: String = self.comments
+            .as_ref()
+            .map(take_license_comments)
+            .unwrap_or_default()
+            .iter()
+            .map(|text| format!("{}\n", text))
+            .collect();
+        let (mut srcmap, buf) = emit_module(
+            self.source_map.clone(),
+            module,
+            self.comments.as_ref().map(|c| c as &dyn Comments),
+        );
         let code = String::from_utf8(buf).expect("failed to convert to utf8");
-        let srcmap_str = get_inline_source_map(&self.source_map, &mut srcmap);
         
         // Generate host module preamble if any host modules are used
-        let preamble = generate_host_module_preamble(&host_namespaces);
+        let preamble = generate_host_module_preamble(&host_namespaces, self.sandbox);
+        
+        let header = format!("{}\n{}{}\n{}", PREAMBLE_BEGIN, preamble, PREAMBLE_END, license_banner);
+        let srcmap = build_source_map(&self.source_map, &mut srcmap, header.matches('\n').count() as u32);
         
-        format!("{}{}{}", preamble, code, srcmap_str)
+        Ok((format!("{}{}", header, code), srcmap))
     }
 
     /// Expand all macro calls in the graph before emitting
-    fn expand_macros(&mut self) {
+    /// Returns the ClosureValue nodes captured for the calls that were expanded
+    fn expand_macros(&mut self) -> Result<HashSet<NodeIndex>, ResolveError> {
         // Build a map from edge labels (original identifier names) to their target node indices
         let mut edge_targets: HashMap<(NodeIndex, String), NodeIndex> = HashMap::new();
         
@@ -110,6 +167,7 @@ impl SourceGraph {
         let nodes: Vec<NodeIndex> = self.graph.node_indices().collect();
         
         let mut runtime = MacroRuntime::new();
+        let mut expanded_closures = HashSet::new();
         
         for nx in nodes {
             let declaration = self.graph[nx].1.clone();
@@ -135,38 +193,27 @@ impl SourceGraph {
                                         &all_macros,
                                         &mut runtime,
                                     ) {
-                                        // Process references from the macro result
-                                        // These are dependencies the macro introduced - find existing nodes for them
+                                        // Link the references the macro attached to its result
+                                        // to exactly the declarations they name, loading any
+                                        // that nothing else in the bundle uses
                                         for (local_name, (uri, export_name)) in macro_refs.iter() {
-                                            // Look for an existing node that provides this export
-                                            // It might be under any edge name, so check all edges
-                                            let mut found_target: Option<NodeIndex> = None;
-                                            
-                                            // Search through all edges to find one that points to a node
-                                            // that resolves to this canonical identifier
-                                            for ((_src, _edge_name), tgt) in edge_targets.iter() {
-                                                let (node_uri, _decl) = &self.graph[*tgt];
-                                                // Check if this node's URI matches our reference
-                                                // (simplified check - in practice we'd need to resolve funee specifier)
-                                                if node_uri.ends_with("funee-lib/index.ts") || 
-                                                   node_uri.ends_with("funee-lib/core.ts") ||
-                                                   node_uri == "funee" {
-                                                    // Check if this edge is for our export name
-                                                    found_target = Some(*tgt);
-                                                    break;
-                                                }
-                                            }
-                                            
-                                            if let Some(target_node) = found_target {
-                                                // Add edge from our node to the existing definition
-                                                self.graph.add_edge(nx, target_node, local_name.clone());
-                                            }
+                                            self.add_macro_reference(
+                                                nx,
+                                                local_name.clone(),
+                                                FuneeIdentifier {
+                                                    name: export_name.clone(),
+                                                    uri: uri.clone(),
+                                                },
+                                            )?;
                                         }
                                         
                                         // Add edges for identifiers in the result expression
                                         // so they get renamed correctly during emission
                                         let result_idents = self.extract_identifiers(&result_expr);
                                         for ident_name in result_idents {
+                                            if macro_refs.contains_key(&ident_name) {
+                                                continue;
+                                            }
                                             // Find if any node in the graph is referenced by this name
                                             // Check all existing edges to find what this name resolves to
                                             for ((_src, edge_name), tgt) in edge_targets.iter() {
@@ -178,6 +225,14 @@ impl SourceGraph {
                                             }
                                         }
                                         
+                                        // The argument closures were consumed by the expansion
+                                        for arg_idx in 0..call_expr.args.len() {
+                                            let closure_edge_name = format!("{}_arg{}", callee_name, arg_idx);
+                                            if let Some(closure_node) = edge_targets.get(&(nx, closure_edge_name)) {
+                                                expanded_closures.insert(*closure_node);
+                                            }
+                                        }
+                                        
                                         // Replace the VarInit with the result
                                         self.graph[nx].1 = Declaration::VarInit(result_expr);
                                     }
@@ -188,6 +243,8 @@ impl SourceGraph {
                 }
             }
         }
+        
+        Ok(expanded_closures)
     }
 
     /// Execute a macro call and return the result expression plus any new references
@@ -279,7 +336,7 @@ impl SourceGraph {
                 self.parse_expr(&result.expression).map(|expr| (expr, result.references))
             }
             Err(e) => {
-                eprintln!("Macro execution failed: {}", e);
+                diagnostics::error(format!("Macro execution failed: {}", e));
                 None
             }
         }
@@ -361,24 +418,76 @@ impl SourceGraph {
                 Some(*expr)
             },
             Err(e) => {
-                eprintln!("Failed to parse macro result '{}': {:?}", code, e);
+                diagnostics::error(format!("Failed to parse macro result '{}': {:?}", code, e));
                 None
             }
         }
     }
 }
 
+/// Turn the entry's `expr;` statement into `module.exports = expr;`
+fn assign_to_module_exports(item: ModuleItem) -> ModuleItem {
+    let ModuleItem::Stmt(Stmt::Expr(ExprStmt { span, expr })) = item else {
+        return item;
+    };
+    let module_exports = MemberExpr {
+        span: DUMMY_SP,
+        obj: Box::new(Expr::Ident(Ident::new("module".into(), DUMMY_SP, SyntaxContext::empty()))),
+        prop: MemberProp::Ident(IdentName::new("exports".into(), DUMMY_SP)),
+    };
+    ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+        span,
+        expr: Box::new(Expr::Assign(AssignExpr {
+            span,
+            op: AssignOp::Assign,
+            left: AssignTarget::Simple(SimpleAssignTarget::Member(module_exports)),
+            right: expr,
+        })),
+    }))
+}
+
+/// First line of the runtime preamble section in the emitted bundle
+pub const PREAMBLE_BEGIN: &str = "// ==== funee runtime preamble: begin ====";
+
+/// Last line of the runtime preamble section; user code starts after it
+pub const PREAMBLE_END: &str = "// ==== funee runtime preamble: end ====";
+
+/// Host modules that stay available in sandbox mode: they can't touch the
+/// filesystem, network or other processes
+const SANDBOX_HOST_MODULES: &[&str] = &["compress", "console", "crypto", "test", "time"];
+
+/// Sandbox mode preamble: the capability globals throw, and `__sandboxed(namespace)`
+/// builds a host module whose every export throws when called
+const SANDBOX_PREAMBLE: &str = r#"var __sandbox_error = (what) => new Error(`${what} is not available in sandbox mode (--sandbox / FUNEE_NO_HOST)`);
+var __sandboxed = (namespace) => new Proxy({}, {
+    get: (_, name) => typeof name === "string"
+        ? () => { throw __sandbox_error(`${name} from host://${namespace}`); }
+        : undefined
+});
+for (const name of ["fetch", "serve", "spawn"]) {
+    globalThis[name] = () => { throw __sandbox_error(name); };
+}
+"#;
+
 /// Generate JavaScript code that defines host module objects
 /// These are inlined in the bundle preamble for modules like host://fs, host://http, etc.
-fn generate_host_module_preamble(namespaces: &std::collections::HashSet<String>) -> String {
-    if namespaces.is_empty() {
-        return String::new();
-    }
+/// Namespaces are emitted in sorted order so the output is deterministic.
+/// In sandbox mode, modules outside SANDBOX_HOST_MODULES become throwing stubs.
+fn generate_host_module_preamble(namespaces: &std::collections::HashSet<String>, sandbox: bool) -> String {
+    let mut sorted: Vec<&String> = namespaces.iter().collect();
+    sorted.sort();
 
     let mut preamble = String::new();
+    if sandbox {
+        preamble.push_str(SANDBOX_PREAMBLE);
+    }
 
-    for namespace in namespaces {
+    for namespace in sorted {
         let var_name = format!("__host_{}", namespace.replace('/', "_"));
+        if sandbox && !SANDBOX_HOST_MODULES.contains(&namespace.as_str()) {
+            preamble.push_str(&format!("var {} = __sandboxed({:?});\n", var_name, namespace));
+            continue;
+        }
         let module_code = get_host_module_code(namespace);
         preamble.push_str(&format!("var {} = {};\n", var_name, module_code));
     }
@@ -389,16 +498,52 @@ fn generate_host_module_preamble(namespaces: &std::collections::HashSet<String>)
 /// Get the JavaScript object implementation for a host module namespace
 fn get_host_module_code(namespace: &str) -> &'static str {
     match namespace {
+        "env" => r#"(() => {
+    const unwrap = (json) => {
+        const result = JSON.parse(json);
+        if (result.type === "error") {
+            throw new Error(result.error);
+        }
+        return result.value;
+    };
+    return {
+        get: (name) => unwrap(Deno.core.ops.op_envGet(name)) ?? undefined,
+        set: (name, value) => {
+            unwrap(Deno.core.ops.op_envSet(name, String(value)));
+        },
+        delete: (name) => {
+            unwrap(Deno.core.ops.op_envDelete(name));
+        },
+        toObject: () => JSON.parse(Deno.core.ops.op_envAll())
+    };
+})()"#,
+
         "fs" => r#"({
     readFile: (path) => Deno.core.ops.op_fsReadFile(path),
     readFileBinary: (path) => Deno.core.ops.op_fsReadFileBinary(path),
     writeFile: (path, content) => Deno.core.ops.op_fsWriteFile(path, content),
     writeFileBinary: (path, contentBase64) => Deno.core.ops.op_fsWriteFileBinary(path, contentBase64),
+    appendFile: (path, content) => Deno.core.ops.op_fsAppendFile(path, content),
+    appendFileBinary: (path, contentBase64) => Deno.core.ops.op_fsAppendFileBinary(path, contentBase64),
+    writeFiles: (files) => Deno.core.ops.op_fsWriteFiles(JSON.stringify(files)),
+    copyFile: (source, dest) => Deno.core.ops.op_fsCopyFile(source, dest),
+    rename: (from, to) => Deno.core.ops.op_fsRename(from, to),
+    realpath: (path) => Deno.core.ops.op_fsRealpath(path),
     isFile: (path) => Deno.core.ops.op_fsIsFile(path),
     exists: (path) => Deno.core.ops.op_fsExists(path),
     lstat: (path) => Deno.core.ops.op_fsLstat(path),
+    stat: (path) => Deno.core.ops.op_fsStat(path),
+    chmod: (path, mode) => Deno.core.ops.op_fsChmod(path, mode),
+    chown: (path, uid, gid) => Deno.core.ops.op_fsChown(path, uid, gid),
+    truncate: (path, len) => Deno.core.ops.op_fsTruncate(path, len ?? 0),
+    utimes: (path, atimeMs, mtimeMs) => Deno.core.ops.op_fsUtimes(path, atimeMs, mtimeMs),
     mkdir: (path, recursive) => Deno.core.ops.op_fsMkdir(path, recursive ?? false),
+    mkdtemp: (prefix) => Deno.core.ops.op_fsMkdtemp(prefix ?? ""),
+    remove: (path, recursive) => Deno.core.ops.op_fsRemove(path, recursive ?? false),
     readdir: (path) => Deno.core.ops.op_fsReaddir(path),
+    readdirTyped: (path) => Deno.core.ops.op_fsReaddirTyped(path),
+    walk: (root, maxDepth) => Deno.core.ops.op_fsWalk(root, maxDepth ?? 0),
+    glob: (pattern, includeHidden) => Deno.core.ops.op_fsGlob(pattern, includeHidden ?? false),
     tmpdir: () => Deno.core.ops.op_tmpdir()
 })"#,
 
@@ -408,38 +553,247 @@ fn get_host_module_code(namespace: &str) -> &'static str {
 
         "http/server" => r#"({
     serve: globalThis.serve,
+    activeConnections: (serverId) => Deno.core.ops.op_serverActiveConnections(serverId),
     createResponse: (body, init) => new Response(body, init),
     createJsonResponse: (data, init) => Response.json(data, init)
 })"#,
 
         "process" => r#"({
-    spawn: globalThis.spawn
+    spawn: globalThis.spawn,
+    exec: (commandOrOptions, args) => globalThis[Symbol.for("funee.exec")](commandOrOptions, args),
+    startTime: () => Deno.core.ops.op_processStartTime(),
+    uptime: () => Deno.core.ops.op_processUptime(),
+    isatty: (stream) => Deno.core.ops.op_isatty(stream),
+    onSignal: (signal) => {
+        // Deliveries seen so far; -1 until the first one, so earlier deliveries
+        // to another listener for the same signal aren't replayed
+        let seen = -1;
+        let closed = false;
+        // This iterator's own subscription, so closing it leaves other
+        // listeners for the signal alone
+        let id = null;
+        const close = () => {
+            if (!closed) {
+                closed = true;
+                if (id !== null) {
+                    Deno.core.ops.op_signalUnlisten(id);
+                }
+            }
+        };
+        return {
+            async next() {
+                if (closed) {
+                    return { done: true, value: undefined };
+                }
+                if (id === null) {
+                    id = Deno.core.ops.op_signalSubscribe(signal);
+                }
+                const count = JSON.parse(await Deno.core.ops.op_signalListen(id, seen));
+                if (count === null || closed) {
+                    closed = true;
+                    return { done: true, value: undefined };
+                }
+                seen = count;
+                return { done: false, value: signal };
+            },
+            async return() {
+                close();
+                return { done: true, value: undefined };
+            },
+            [Symbol.asyncIterator]() {
+                return this;
+            }
+        };
+    }
 })"#,
 
+        "os" => r#"(() => {
+    const unwrap = (json) => {
+        const result = JSON.parse(json);
+        if (result.type === "error") {
+            throw new Error(result.error);
+        }
+        return result.value;
+    };
+    return {
+        platform: () => Deno.core.ops.op_osPlatform(),
+        arch: () => Deno.core.ops.op_osArch(),
+        hostname: () => unwrap(Deno.core.ops.op_osHostname()),
+        homedir: () => unwrap(Deno.core.ops.op_osHomedir()),
+        cpus: () => Deno.core.ops.op_osCpus(),
+        networkInterfaces: () => unwrap(Deno.core.ops.op_osNetworkInterfaces())
+    };
+})()"#,
+
         "time" => r#"({
     setTimeout: globalThis.setTimeout,
     clearTimeout: globalThis.clearTimeout,
     setInterval: globalThis.setInterval,
-    clearInterval: globalThis.clearInterval
+    clearInterval: globalThis.clearInterval,
+    monotonicNanos: () => BigInt(Deno.core.ops.op_hrtime())
 })"#,
 
         "watch" => r#"({
-    watchStart: (path, recursive) => Deno.core.ops.op_watchStart(path, recursive),
-    watchPoll: (watcherId) => Deno.core.ops.op_watchPoll(watcherId),
-    watchStop: (watcherId) => Deno.core.ops.op_watchStop(watcherId)
-})"#,
-
-        "crypto" => r#"({
-    randomBytes: (length) => {
-        const hex = Deno.core.ops.op_randomBytes(length);
-        const bytes = new Uint8Array(length);
-        for (let i = 0; i < length; i++) {
-            bytes[i] = parseInt(hex.substr(i * 2, 2), 16);
+    watchStart: (path, recursive, maxQueue) => Deno.core.ops.op_watchStart(path, recursive, maxQueue ?? 0),
+    watchPoll: (watcherId, cursor) => Deno.core.ops.op_watchPoll(watcherId, cursor ?? 0),
+    watchNext: (watcherId, cursor) => Deno.core.ops.op_watchNext(watcherId, cursor ?? 0),
+    watchStop: (watcherId) => Deno.core.ops.op_watchStop(watcherId),
+    watch: (path, options) => {
+        const opts = options ?? {};
+        const started = JSON.parse(Deno.core.ops.op_watchStart(path, opts.recursive ?? false, opts.maxQueue ?? 0));
+        if (started.type === "error") {
+            throw new Error(started.error);
+        }
+        const watcherId = started.value;
+        const ignore = opts.ignore ?? [];
+        const debounce = opts.debounce ?? 0;
+        const isIgnored = (eventPath) => ignore.some((pattern) =>
+            pattern instanceof RegExp ? pattern.test(eventPath) : eventPath.includes(pattern)
+        );
+        let cursor = 0;
+        let closed = false;
+        const close = () => {
+            if (!closed) {
+                closed = true;
+                Deno.core.ops.op_watchStop(watcherId);
+            }
+        };
+        const collect = (json) => {
+            if (json === "null") return null;
+            const batch = JSON.parse(json);
+            cursor = batch.nextCursor;
+            return batch.events;
+        };
+        async function* iterate() {
+            try {
+                while (!closed) {
+                    let events = collect(await Deno.core.ops.op_watchNext(watcherId, cursor));
+                    if (events === null) break;
+                    if (debounce > 0) {
+                        // Gather the rest of the burst, keeping the last event per kind + path
+                        await new Promise((resolve) => setTimeout(resolve, debounce));
+                        events = events.concat(collect(Deno.core.ops.op_watchPoll(watcherId, cursor)) ?? []);
+                        const latest = new Map();
+                        for (const event of events) {
+                            const key = `${event.kind}:${event.path}`;
+                            latest.delete(key);
+                            latest.set(key, event);
+                        }
+                        events = [...latest.values()];
+                    }
+                    for (const event of events) {
+                        if (closed) return;
+                        if (!isIgnored(event.path)) yield event;
+                    }
+                }
+            } finally {
+                close();
+            }
         }
-        return bytes;
+        return {
+            [Symbol.asyncIterator]: iterate,
+            close,
+            pending: started.pending,
+        };
     }
 })"#,
 
+        "crypto" => r#"(() => {
+    const { base64Encode, base64Decode } = globalThis[Symbol.for("funee.bytes")];
+    const toBytes = (data) => typeof data === "string" ? Deno.core.encode(data) : data;
+    return {
+        randomBytes: (length) => {
+            const hex = Deno.core.ops.op_randomBytes(length);
+            const bytes = new Uint8Array(length);
+            for (let i = 0; i < length; i++) {
+                bytes[i] = parseInt(hex.substr(i * 2, 2), 16);
+            }
+            return bytes;
+        },
+        pbkdf2: (password, salt, iterations, keyLength, digest) => base64Decode(
+            Deno.core.ops.op_cryptoPbkdf2(password, base64Encode(toBytes(salt)), iterations, keyLength, digest ?? "sha256")
+        ),
+        argon2Hash: (password) => Deno.core.ops.op_cryptoArgon2Hash(password),
+        argon2Verify: (password, hash) => Deno.core.ops.op_cryptoArgon2Verify(password, hash),
+        digest: (algorithm, data) => Deno.core.ops.op_cryptoDigest(algorithm, base64Encode(toBytes(data))),
+        hmac: (algorithm, key, data) => Deno.core.ops.op_cryptoHmac(algorithm, base64Encode(toBytes(key)), base64Encode(toBytes(data))),
+        timingSafeEqual: (a, b) => Deno.core.ops.op_cryptoTimingSafeEqual(a, b),
+        randomUUID: () => Deno.core.ops.op_cryptoRandomUuid()
+    };
+})()"#,
+
+        "compress" => r#"(() => {
+    const toBytes = (data) => typeof data === "string" ? Deno.core.encode(data) : data;
+    return {
+        gzip: (data, level) => Deno.core.ops.op_gzipCompress(toBytes(data), level ?? 6),
+        gunzip: (data, options) => Deno.core.ops.op_gzipDecompress(data, options?.maxBytes ?? 0)
+    };
+})()"#,
+
+        "test" => r#"(() => {
+    const queue = [];
+    let running = false;
+    // Tests run one at a time, after the code registering them has returned
+    const runQueued = async () => {
+        while (queue.length > 0) {
+            const { id, fn } = queue.shift();
+            Deno.core.ops.op_testStart(id);
+            try {
+                await fn();
+                Deno.core.ops.op_testPass(id);
+            } catch (error) {
+                Deno.core.ops.op_testFail(id, error?.name === "AssertionError" ? error.message : String(error));
+            }
+        }
+        running = false;
+    };
+    const fail = (message) => {
+        const error = new Error(message);
+        error.name = "AssertionError";
+        throw error;
+    };
+    const show = (value) => {
+        try {
+            return JSON.stringify(value) ?? String(value);
+        } catch {
+            return String(value);
+        }
+    };
+    const equal = (a, b) => {
+        if (Object.is(a, b)) return true;
+        if (typeof a !== "object" || typeof b !== "object" || a === null || b === null) return false;
+        if (Array.isArray(a) !== Array.isArray(b)) return false;
+        const keys = Object.keys(a);
+        return keys.length === Object.keys(b).length
+            && keys.every((key) => Object.hasOwn(b, key) && equal(a[key], b[key]));
+    };
+    return {
+        test: (name, fn) => {
+            queue.push({ id: Deno.core.ops.op_testRegister(String(name)), fn });
+            if (!running) {
+                running = true;
+                setTimeout(runQueued, 0);
+            }
+        },
+        assert: (condition, message) => {
+            if (!condition) fail(message ?? "Assertion failed");
+        },
+        assertEqual: (actual, expected, message) => {
+            if (!equal(actual, expected)) {
+                fail(message ?? `Expected ${show(expected)}, got ${show(actual)}`);
+            }
+        },
+        assertThrows: (fn, message) => {
+            try {
+                fn();
+            } catch (error) {
+                return error;
+            }
+            fail(message ?? "Expected function to throw");
+        }
+    };
+})()"#,
+
         "console" => r#"({
     log: (...args) => console.log(...args),
     debug: (...args) => console.debug(...args)
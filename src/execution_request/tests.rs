@@ -1,4 +1,5 @@
 use crate::{execution_request::ExecutionRequest, funee_identifier::FuneeIdentifier};
+use super::source_graph_to_js_execution_code::{EmitFormat, PREAMBLE_BEGIN, PREAMBLE_END};
 use ast::{CallExpr, Callee};
 use deno_core::{op2, OpDecl};
 use std::collections::HashMap;
@@ -87,6 +88,13 @@ fn it_works() {
             ]),
         }),
         funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
     };
     assert_eq!(request.execute().unwrap(), ());
 }
@@ -122,7 +130,7 @@ export const closure = createMacro(<T>(input: T) => {
     ));
 
     // Load the module and get declarations
-    let module = load_module(&cm, PathBuf::from("/test/macro-lib.ts"));
+    let module = load_module(&cm, PathBuf::from("/test/macro-lib.ts"), None);
     let declarations = get_module_declarations(module);
 
     // Verify that 'closure' is detected as a Macro
@@ -201,8 +209,16 @@ export const closure = createMacro(<T>(input: T) => {
         }),
         host_functions: HashSet::new(),
         funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
         file_loader,
-    });
+    })
+    .unwrap();
 
     // Verify that 'closure' is tracked as a macro function
     assert!(
@@ -268,8 +284,16 @@ export const closure = createMacro(<T>(input: T) => {
         }),
         host_functions: HashSet::new(),
         funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
         file_loader,
-    });
+    })
+    .unwrap();
 
     // Look for ClosureValue nodes in the graph
     let mut found_closure = false;
@@ -363,8 +387,16 @@ export default async function() {
         }),
         host_functions: HashSet::new(),
         funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
         file_loader,
-    });
+    })
+    .unwrap();
 
     // Find all HostModule declarations
     let mut host_modules: Vec<(String, String)> = vec![];
@@ -400,7 +432,7 @@ export default async function() {
     println!("   Found modules: {:?}", host_modules);
 
     // Generate the execution code and verify the preamble is included
-    let code = source_graph.into_js_execution_code();
+    let code = source_graph.into_js_execution_code().unwrap();
     
     // Check that host module preambles are present
     assert!(
@@ -416,6 +448,637 @@ export default async function() {
         "Expected __host_http_server in generated code (http/server -> http_server). Code: {}", &code[..1000.min(code.len())]
     );
 
+    // The preamble is a single delimited section, in sorted order, ahead of user code
+    assert!(code.starts_with(PREAMBLE_BEGIN));
+    let preamble_end = code.find(PREAMBLE_END).expect("Expected preamble end banner");
+    assert_eq!(code.matches(PREAMBLE_BEGIN).count(), 1);
+    let console_at = code.find("var __host_console").unwrap();
+    let fs_at = code.find("var __host_fs").unwrap();
+    let server_at = code.find("var __host_http_server").unwrap();
+    assert!(console_at < fs_at && fs_at < server_at && server_at < preamble_end);
+
     println!("✅ Host module preambles are generated correctly!");
     println!("\nGenerated code preview:\n{}", &code[..500.min(code.len())]);
 }
+
+/// File loader that counts how many times each file is read (and thus parsed)
+struct CountingFileLoader {
+    inner: MockFileLoader,
+    reads: std::sync::Arc<std::sync::Mutex<HashMap<String, usize>>>,
+}
+
+impl FileLoader for CountingFileLoader {
+    fn file_exists(&self, path: &std::path::Path) -> bool {
+        self.inner.file_exists(path)
+    }
+
+    fn abs_path(&self, path: &std::path::Path) -> Option<std::path::PathBuf> {
+        self.inner.abs_path(path)
+    }
+
+    fn read_file(&self, path: &std::path::Path) -> std::io::Result<BytesStr> {
+        *self
+            .reads
+            .lock()
+            .unwrap()
+            .entry(path.to_str().unwrap().to_string())
+            .or_insert(0) += 1;
+        self.inner.read_file(path)
+    }
+}
+
+#[test]
+fn test_module_parsed_once_per_run() {
+    use crate::execution_request::source_graph::{LoadParams, SourceGraph};
+    use std::collections::HashSet;
+
+    let reads = std::sync::Arc::new(std::sync::Mutex::new(HashMap::new()));
+    let file_loader = Box::new(CountingFileLoader {
+        inner: MockFileLoader {
+            files: HashMap::from([
+                (
+                    "/test/entry.ts".to_string(),
+                    r#"
+import { one, two, three } from "./lib.ts";
+
+export default function() {
+    return one() + two() + three();
+}
+                    "#
+                    .to_string(),
+                ),
+                (
+                    "/test/lib.ts".to_string(),
+                    r#"
+export function one() { return 1; }
+export function two() { return one() + 1; }
+export const three = () => two() + 1;
+                    "#
+                    .to_string(),
+                ),
+            ]),
+        },
+        reads: reads.clone(),
+    });
+
+    let source_graph = SourceGraph::load(LoadParams {
+        scope: "/test/entry.ts".to_string(),
+        expression: ast::Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(ast::Expr::Ident(ident("default")))),
+            type_args: None,
+            args: vec![],
+        }),
+        host_functions: HashSet::new(),
+        funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
+        file_loader,
+    })
+    .unwrap();
+
+    // entry.ts default export, the import of lib.ts and its three exports
+    assert!(source_graph.graph.node_count() >= 4);
+
+    let reads = reads.lock().unwrap();
+    assert_eq!(reads.get("/test/entry.ts"), Some(&1));
+    assert_eq!(reads.get("/test/lib.ts"), Some(&1));
+}
+
+#[test]
+fn test_virtual_module_import() {
+    use crate::execution_request::source_graph::{LoadParams, SourceGraph};
+    use std::collections::HashSet;
+
+    let source_graph = SourceGraph::load(LoadParams {
+        scope: "/test/entry.ts".to_string(),
+        expression: ast::Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(ast::Expr::Ident(ident("default")))),
+            type_args: None,
+            args: vec![],
+        }),
+        host_functions: HashSet::new(),
+        funee_lib_path: None,
+        export_conditions: vec![],
+        // Only the entry exists in the file loader; the import is served from memory
+        virtual_modules: HashMap::from([(
+            "greeting".to_string(),
+            "export const greeting = () => 'hi from memory';".to_string(),
+        )]),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
+        file_loader: Box::new(MockFileLoader {
+            files: HashMap::from([(
+                "/test/entry.ts".to_string(),
+                r#"
+import { greeting } from "virtual:greeting";
+
+export default function() {
+    return greeting();
+}
+                "#
+                .to_string(),
+            )]),
+        }),
+    })
+    .unwrap();
+
+    let code = source_graph.into_js_execution_code().unwrap();
+    assert!(code.contains("hi from memory"), "Virtual module missing from bundle: {}", code);
+}
+
+#[test]
+fn test_star_reexports() {
+    use crate::execution_request::ResolveError;
+
+    let request = |entry: &str| ExecutionRequest {
+        expression: ast::Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(ast::Expr::Ident(ident("default")))),
+            type_args: None,
+            args: vec![],
+        }),
+        scope: "/test/entry.ts".to_string(),
+        host_functions: HashMap::new(),
+        file_loader: Box::new(MockFileLoader {
+            files: HashMap::from([
+                ("/test/entry.ts".to_string(), entry.to_string()),
+                (
+                    "/test/index.ts".to_string(),
+                    "export * from './a.ts';\nexport * from './b.ts';".to_string(),
+                ),
+                (
+                    "/test/a.ts".to_string(),
+                    "export const fromA = () => 'value from a';\nexport const shared = 1;\nexport { common } from './c.ts';".to_string(),
+                ),
+                (
+                    "/test/b.ts".to_string(),
+                    "export const fromB = () => 'value from b';\nexport const shared = 2;\nexport { common } from './c.ts';".to_string(),
+                ),
+                (
+                    "/test/c.ts".to_string(),
+                    "export const common = () => 'value from c';".to_string(),
+                ),
+            ]),
+        }),
+        funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
+    };
+
+    let code = request("import { fromA, fromB } from './index.ts';\nexport default function() { return fromA() + fromB(); }")
+        .emit()
+        .unwrap();
+    assert!(code.contains("value from a"), "a.ts export missing from bundle: {}", code);
+    assert!(code.contains("value from b"), "b.ts export missing from bundle: {}", code);
+
+    let error = request("import { shared } from './index.ts';\nexport default function() { return shared; }")
+        .emit()
+        .err()
+        .expect("emit should fail on an ambiguous star export");
+    let ResolveError::AmbiguousExport { name, uri, sources, .. } = error else {
+        panic!("Expected AmbiguousExport, got {:?}", error);
+    };
+    assert_eq!((name.as_str(), uri.as_str()), ("shared", "/test/index.ts"));
+    assert_eq!(sources, ["/test/a.ts", "/test/b.ts"]);
+
+    // Both star targets forward the same binding, so it isn't ambiguous
+    let code = request("import { common } from './index.ts';\nexport default function() { return common(); }")
+        .emit()
+        .unwrap();
+    assert!(code.contains("value from c"), "c.ts export missing from bundle: {}", code);
+}
+
+#[test]
+fn test_invalid_json_module_is_returned_as_error() {
+    use crate::execution_request::ResolveError;
+
+    let request = ExecutionRequest {
+        expression: ast::Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(ast::Expr::Ident(ident("default")))),
+            type_args: None,
+            args: vec![],
+        }),
+        scope: "/test/entry.ts".to_string(),
+        host_functions: HashMap::new(),
+        file_loader: Box::new(MockFileLoader {
+            files: HashMap::from([
+                (
+                    "/test/entry.ts".to_string(),
+                    "import config from './config.json';\nexport default function() { return config; }".to_string(),
+                ),
+                ("/test/config.json".to_string(), "{\n  \"name\": \"funee\",\n}".to_string()),
+            ]),
+        }),
+        funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
+    };
+
+    let error = request.emit().err().expect("emit should fail on invalid JSON");
+    let ResolveError::InvalidJson { uri, reason, location } = error else {
+        panic!("Expected InvalidJson, got {:?}", error);
+    };
+    assert_eq!(uri, "/test/config.json");
+    assert_eq!(reason, "trailing comma");
+    // Points at the closing brace after the trailing comma
+    let location = location.expect("InvalidJson should carry the parse position");
+    assert_eq!(location.to_string(), "/test/config.json:3:1");
+}
+
+#[test]
+fn test_namespace_import() {
+    let request = ExecutionRequest {
+        expression: ast::Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(ast::Expr::Ident(ident("default")))),
+            type_args: None,
+            args: vec![],
+        }),
+        scope: "/test/entry.ts".to_string(),
+        host_functions: HashMap::new(),
+        file_loader: Box::new(MockFileLoader {
+            files: HashMap::from([
+                (
+                    "/test/entry.ts".to_string(),
+                    r#"
+import * as utils from "./utils.ts";
+import * as fs from "host://fs";
+
+export default function() {
+    return fs.readFile(utils.path()) + utils["suffix"];
+}
+                "#
+                    .to_string(),
+                ),
+                (
+                    "/test/utils.ts".to_string(),
+                    r#"
+export const path = () => "/tmp/namespace.txt";
+export const suffix = "!";
+export const unusedMember = () => "never accessed";
+                "#
+                    .to_string(),
+                ),
+            ]),
+        }),
+        funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
+    };
+
+    let code = request.emit().unwrap();
+    assert!(code.contains("/tmp/namespace.txt"), "utils.path missing from bundle: {}", code);
+    assert!(code.contains("__host_fs.readFile"), "fs.readFile not resolved to the host module: {}", code);
+    assert!(!code.contains("never accessed"), "Unused namespace member was bundled: {}", code);
+    assert!(!code.contains("utils."), "Namespace member access left in bundle: {}", code);
+}
+
+#[test]
+fn test_json_module_import() {
+    use crate::execution_request::source_graph::{LoadParams, SourceGraph};
+    use std::collections::HashSet;
+
+    let source_graph = SourceGraph::load(LoadParams {
+        scope: "/test/entry.ts".to_string(),
+        expression: ast::Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(ast::Expr::Ident(ident("default")))),
+            type_args: None,
+            args: vec![],
+        }),
+        host_functions: HashSet::new(),
+        funee_lib_path: None,
+        export_conditions: vec![],
+        // No extension, so only the `type` attribute makes it JSON
+        virtual_modules: HashMap::from([(
+            "defaults".to_string(),
+            r#"{ "greeting": "hi from json" }"#.to_string(),
+        )]),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
+        file_loader: Box::new(MockFileLoader {
+            files: HashMap::from([
+                (
+                    "/test/entry.ts".to_string(),
+                    r#"
+import config, { name } from "./config.json";
+import defaults from "virtual:defaults" with { type: "json" };
+
+export default function() {
+    return [config.version, name, defaults.greeting];
+}
+                "#
+                    .to_string(),
+                ),
+                ("/test/config.json".to_string(), r#"{ "name": "funee-json", "version": 2 }"#.to_string()),
+            ]),
+        }),
+    })
+    .unwrap();
+
+    let code = source_graph.into_js_execution_code().unwrap();
+    assert!(code.contains("funee-json"), "JSON module missing from bundle: {}", code);
+    assert!(code.contains("hi from json"), "JSON-typed virtual module missing from bundle: {}", code);
+}
+
+#[test]
+fn test_retained_closure_value_emitted_as_closure_object() {
+    use crate::execution_request::source_graph::{LoadParams, SourceGraph};
+    use std::collections::HashSet;
+
+    // The macro call is nested in a function, so it isn't expanded and its
+    // captured argument survives to the output
+    let source_graph = SourceGraph::load(LoadParams {
+        scope: "/test/entry.ts".to_string(),
+        expression: ast::Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(ast::Expr::Ident(ident("default")))),
+            type_args: None,
+            args: vec![],
+        }),
+        host_functions: HashSet::new(),
+        funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
+        file_loader: Box::new(MockFileLoader {
+            files: HashMap::from([
+                (
+                    "/test/entry.ts".to_string(),
+                    r#"
+import { closure } from "./macro-lib.ts";
+
+const add = (a: number, b: number) => a + b;
+const getAddClosure = () => closure(add);
+
+export default function() {
+    return getAddClosure;
+}
+                    "#
+                    .to_string(),
+                ),
+                (
+                    "/test/macro-lib.ts".to_string(),
+                    r#"
+export function createMacro<T, R>(fn: (closure: T) => R): (value: T) => R {
+    throw new Error("Macro not expanded");
+}
+
+export const closure = createMacro(<T>(input: T) => {
+    return input;
+});
+                    "#
+                    .to_string(),
+                ),
+            ]),
+        }),
+    })
+    .unwrap();
+
+    let code = source_graph.into_js_execution_code().unwrap();
+    assert!(code.contains("expression: \"add\""), "Closure expression missing: {}", code);
+    assert!(code.contains("references: new Map("), "Closure references missing: {}", code);
+    assert!(code.contains("uri: \"/test/entry.ts\""), "Closure reference uri missing: {}", code);
+    assert!(code.contains("name: \"add\""), "Closure reference name missing: {}", code);
+}
+
+#[test]
+fn test_macro_reference_to_sibling_helper_is_linked() {
+    use crate::execution_request::source_graph::{LoadParams, SourceGraph};
+    use std::collections::HashSet;
+
+    // `triple` lives next to the macro and is only referenced by the macro's
+    // output, so expansion has to load it and link the call to it
+    let source_graph = SourceGraph::load(LoadParams {
+        scope: "/test/entry.ts".to_string(),
+        expression: ast::Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(ast::Expr::Ident(ident("default")))),
+            type_args: None,
+            args: vec![],
+        }),
+        host_functions: HashSet::new(),
+        funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
+        file_loader: Box::new(MockFileLoader {
+            files: HashMap::from([
+                (
+                    "/test/entry.ts".to_string(),
+                    r#"
+import { tripled } from "./macros.ts";
+
+const result = tripled(14);
+
+export default function() {
+    return result;
+}
+                    "#
+                    .to_string(),
+                ),
+                (
+                    "/test/macros.ts".to_string(),
+                    r#"
+export function createMacro<T, R>(fn: (closure: T) => R): (value: T) => R {
+    throw new Error("Macro not expanded");
+}
+
+const triple = (n: number) => n * 3;
+
+export const tripled = createMacro((x: any) => ({
+    expression: `triple(${x.expression})`,
+    references: new Map([["triple", { uri: "/test/macros.ts", name: "triple" }]]),
+}));
+                    "#
+                    .to_string(),
+                ),
+            ]),
+        }),
+    })
+    .unwrap();
+
+    let code = source_graph.into_js_execution_code().unwrap();
+    let helper = code
+        .lines()
+        .find(|line| line.contains("n * 3"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or_else(|| panic!("Helper missing from bundle: {}", code));
+    assert!(code.contains(&format!("{}(14)", helper)), "Macro output not linked to {}: {}", helper, code);
+}
+
+#[test]
+fn test_emit_with_map_resolves_output_to_source() {
+    let entry = r#"
+export default function() {
+    return "mapped greeting";
+}
+"#;
+    let request = ExecutionRequest {
+        expression: ast::Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(ast::Expr::Ident(ident("default")))),
+            type_args: None,
+            args: vec![],
+        }),
+        scope: "/test/entry.ts".to_string(),
+        host_functions: HashMap::new(),
+        file_loader: Box::new(MockFileLoader {
+            files: HashMap::from([("/test/entry.ts".to_string(), entry.to_string())]),
+        }),
+        funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
+    };
+
+    let emitted = request.emit_with_map().unwrap();
+    assert!(!emitted.code.contains("sourceMappingURL"), "Map should not be inlined: {}", emitted.code);
+
+    // Find the string literal in the output and map it back to the entry file
+    let (out_line, out_col) = emitted
+        .code
+        .lines()
+        .enumerate()
+        .find_map(|(line, text)| text.find("\"mapped greeting\"").map(|col| (line as u32, col as u32)))
+        .expect("literal missing from output");
+    let src_line = entry.lines().position(|line| line.contains("mapped greeting")).unwrap() as u32;
+
+    let token = emitted
+        .source_map
+        .lookup_token(out_line, out_col)
+        .expect("no mapping for output position");
+    assert!(token.get_source().unwrap().ends_with("entry.ts"), "Wrong source: {:?}", token.get_source());
+    assert_eq!(token.get_src_line(), src_line);
+}
+
+#[test]
+fn test_emit_as_cjs_assigns_entry_to_module_exports() {
+    let request = ExecutionRequest {
+        expression: ast::Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(ast::Expr::Ident(ident("default")))),
+            type_args: None,
+            args: vec![],
+        }),
+        scope: "/test/entry.ts".to_string(),
+        host_functions: HashMap::new(),
+        file_loader: Box::new(MockFileLoader {
+            files: HashMap::from([(
+                "/test/entry.ts".to_string(),
+                "export default function() { return 42; }".to_string(),
+            )]),
+        }),
+        funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
+    };
+
+    let code = request.emit_with_map_as(EmitFormat::Cjs).unwrap().code;
+    let entry_line = code
+        .lines()
+        .find(|line| line.contains("module.exports"))
+        .unwrap_or_else(|| panic!("module.exports missing: {}", code));
+    assert!(entry_line.starts_with("module.exports = declaration_"), "Unexpected entry: {}", entry_line);
+    assert!(entry_line.ends_with("();"), "Entry should still call the default export: {}", entry_line);
+}
+
+#[test]
+fn test_missing_export_is_returned_as_error() {
+    use crate::execution_request::ResolveError;
+
+    let request = ExecutionRequest {
+        expression: ast::Expr::Call(CallExpr {
+            span: Default::default(),
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(ast::Expr::Ident(ident("default")))),
+            type_args: None,
+            args: vec![],
+        }),
+        scope: "/test/entry.ts".to_string(),
+        host_functions: HashMap::new(),
+        file_loader: Box::new(MockFileLoader {
+            files: HashMap::from([
+                (
+                    "/test/entry.ts".to_string(),
+                    "import { missing } from './lib.ts';\nexport default function() { return missing(); }".to_string(),
+                ),
+                ("/test/lib.ts".to_string(), "export const present = 1;".to_string()),
+            ]),
+        }),
+        funee_lib_path: None,
+        export_conditions: vec![],
+        virtual_modules: HashMap::new(),
+        preserve_comments: false,
+        deny_raw_ops: false,
+        sandbox: false,
+        allowed_import_hosts: vec![],
+        import_map: HashMap::new(),
+    };
+
+    let error = request.emit().err().expect("emit should fail on a missing export");
+    let ResolveError::NotFound { name, uri, referenced_from, location } = error else {
+        panic!("Expected NotFound, got {:?}", error);
+    };
+    assert_eq!((name.as_str(), uri.as_str()), ("missing", "/test/lib.ts"));
+    assert_eq!(referenced_from, "/test/entry.ts");
+    // Points at the `missing` specifier of the import statement
+    let location = location.expect("NotFound should carry the import's location");
+    assert_eq!(location.to_string(), "/test/entry.ts:1:10");
+}
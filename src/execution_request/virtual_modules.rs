@@ -0,0 +1,61 @@
+use super::data_url::{decode_data_url, is_data_uri};
+use bytes_str::BytesStr;
+use std::{
+    collections::HashMap,
+    io,
+    path::{Path, PathBuf},
+};
+use swc_common::FileLoader;
+
+/// Scheme for in-memory modules, e.g. `import { x } from "virtual:config"`
+pub const VIRTUAL_SCHEME: &str = "virtual:";
+
+/// Check if a URI refers to a virtual module
+pub fn is_virtual_uri(uri: &str) -> bool {
+    uri.starts_with(VIRTUAL_SCHEME)
+}
+
+/// File loader that serves virtual modules from memory before consulting `inner`
+///
+/// Modules are keyed by name without the scheme: `"config"` is imported as
+/// `"virtual:config"`. `data:` URLs are decoded here too, so they never reach
+/// the filesystem or the network.
+pub struct VirtualFileLoader {
+    pub modules: HashMap<String, String>,
+    pub inner: Box<dyn FileLoader + Sync + Send>,
+}
+
+impl VirtualFileLoader {
+    fn lookup(&self, path: &Path) -> Option<&String> {
+        let name = path.to_str()?.strip_prefix(VIRTUAL_SCHEME)?;
+        self.modules.get(name)
+    }
+}
+
+impl FileLoader for VirtualFileLoader {
+    fn file_exists(&self, path: &Path) -> bool {
+        self.lookup(path).is_some() || is_data_uri(&path.to_string_lossy()) || self.inner.file_exists(path)
+    }
+
+    fn abs_path(&self, path: &Path) -> Option<PathBuf> {
+        if self.lookup(path).is_some() || is_data_uri(&path.to_string_lossy()) {
+            Some(path.to_path_buf())
+        } else {
+            self.inner.abs_path(path)
+        }
+    }
+
+    fn read_file(&self, path: &Path) -> io::Result<BytesStr> {
+        match self.lookup(path) {
+            Some(source) => Ok(BytesStr::from(source.clone())),
+            None if is_virtual_uri(&path.to_string_lossy()) => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("No virtual module registered as '{}'", path.display()),
+            )),
+            None if is_data_uri(&path.to_string_lossy()) => decode_data_url(&path.to_string_lossy())
+                .map(BytesStr::from)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            None => self.inner.read_file(path),
+        }
+    }
+}
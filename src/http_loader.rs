@@ -5,6 +5,7 @@
 //! import { x } from "https://example.com/mod.ts"
 //! ```
 
+use crate::{diagnostics, trace};
 use bytes_str::BytesStr;
 use reqwest::blocking::Client;
 use serde::{Deserialize, Serialize};
@@ -12,7 +13,7 @@ use sha2::{Digest, Sha256};
 use std::fs;
 use std::io::{self, ErrorKind};
 use std::path::{Path, PathBuf};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 use swc_common::FileLoader;
 use url::Url;
 
@@ -85,9 +86,13 @@ impl HttpFileLoader {
         })
     }
 
-    /// Create a new HTTP file loader with force reload enabled (bypasses cache freshness)
-    pub fn with_force_reload(force_reload: bool) -> io::Result<Self> {
-        let mut loader = Self::new()?;
+    /// Create a new HTTP file loader with an optional custom cache directory
+    /// and force reload (bypasses cache freshness)
+    pub fn with_options(cache_dir: Option<PathBuf>, force_reload: bool) -> io::Result<Self> {
+        let mut loader = match cache_dir {
+            Some(cache_dir) => Self::with_cache_dir(cache_dir)?,
+            None => Self::new()?,
+        };
         loader.force_reload = force_reload;
         Ok(loader)
     }
@@ -157,12 +162,13 @@ impl HttpFileLoader {
         }
 
         // Fetch from network
+        let started = Instant::now();
         match self.http_client.get(url).send() {
             Ok(response) => {
                 if !response.status().is_success() {
                     // Try stale cache on HTTP error
                     if cache_path.exists() {
-                        eprintln!("⚠ HTTP {} for {}, using stale cache", response.status(), url);
+                        diagnostics::warning(format!("HTTP {} for {}, using stale cache", response.status(), url));
                         return fs::read_to_string(&cache_path);
                     }
                     return Err(io::Error::new(
@@ -189,13 +195,14 @@ impl HttpFileLoader {
                     let _ = fs::write(&metadata_path, json);
                 }
 
+                trace::fetched(url, started.elapsed());
                 eprintln!("✓ Fetched: {}", url);
                 Ok(content)
             }
             Err(e) => {
                 // Fallback to stale cache on network error
                 if cache_path.exists() {
-                    eprintln!("⚠ Network error for {}, using stale cache: {}", url, e);
+                    diagnostics::warning(format!("Network error for {}, using stale cache: {}", url, e));
                     fs::read_to_string(&cache_path)
                 } else {
                     Err(io::Error::new(
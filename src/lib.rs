@@ -1,6 +1,9 @@
+mod diagnostics;
 mod emit_module;
 pub mod execution_request;
 mod funee_identifier;
 pub mod http_loader;
 mod load_module;
+mod log_locations;
 mod run_js;
+mod trace;
@@ -1,20 +1,40 @@
+use crate::{diagnostics, trace};
 use std::rc::Rc;
-use swc_common::{Globals, Mark, SourceMap, GLOBALS};
+use swc_common::{comments::{Comments, SingleThreadedComments}, Globals, Mark, SourceMap, GLOBALS};
 use swc_ecma_ast::{EsVersion, Program};
 use swc_ecma_parser::{parse_file_as_module, Syntax, TsSyntax};
 use swc_ecma_transforms_typescript::strip;
 
-pub fn load_module(cm: &Rc<SourceMap>, path: std::path::PathBuf) -> swc_ecma_ast::Module {
+/// Read, parse and type-strip a module
+///
+/// When `comments` is given, the module's comments are collected into it so
+/// they can be emitted again (`--preserve-comments`).
+pub fn load_module(
+    cm: &Rc<SourceMap>,
+    path: std::path::PathBuf,
+    comments: Option<&SingleThreadedComments>,
+) -> swc_ecma_ast::Module {
+    let fm = cm.load_file(&path).unwrap_or_else(|e| {
+        diagnostics::error(format!("Cannot read '{}': {}", path.display(), e));
+        std::process::exit(1);
+    });
+    let _span = trace::span("parse");
     let m = parse_file_as_module(
-        &*cm.load_file(&path).unwrap(),
+        &fm,
         Syntax::Typescript(TsSyntax {
             ..Default::default()
         }),
         EsVersion::latest(),
-        None,
+        comments.map(|c| c as &dyn Comments),
         &mut vec![],
     )
-    .expect("failed to parse input as a module");
+    .unwrap_or_else(|e| {
+        let loc = cm.lookup_char_pos(e.span().lo);
+        diagnostics::error(format!("Failed to parse module: {}", e.kind().msg()));
+        diagnostics::note(format!("{}:{}:{}", path.display(), loc.line, loc.col_display + 1));
+        diagnostics::code_frame(&fm.src, loc.line, loc.col_display);
+        std::process::exit(1);
+    });
 
     let globals = Globals::default();
     GLOBALS.set(&globals, || {
@@ -0,0 +1,80 @@
+//! Source locations for console output (`--log-locations`)
+//!
+//! When enabled, the runtime wraps `console.log`/`console.debug` (and the
+//! `log`/`debug` ops) so each call passes its stack to `op_logLocation`. The
+//! first frame in the bundle that has a mapping is resolved through the
+//! bundle's source map and printed as `file:line` ahead of the message.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+
+/// Script name the bundle is executed under (see `run_js`)
+const BUNDLE_SCRIPT: &str = "[funee:runtime.js]";
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static SOURCE_MAP: LazyLock<Mutex<Option<sourcemap::SourceMap>>> = LazyLock::new(|| Mutex::new(None));
+
+/// Turn on log locations (set from `--log-locations`)
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Use `source_map` for the bundle about to run
+pub fn set_source_map(source_map: sourcemap::SourceMap) {
+    *SOURCE_MAP.lock().unwrap() = Some(source_map);
+}
+
+/// The original `file:line` of the innermost bundle frame in `stack` that has
+/// a mapping, with the file relative to the working directory when inside it
+pub fn locate(stack: &str) -> Option<String> {
+    let source_map = SOURCE_MAP.lock().unwrap();
+    let source_map = source_map.as_ref()?;
+    let cwd = std::env::current_dir().ok();
+
+    stack.lines().filter_map(bundle_position).find_map(|(line, col)| {
+        // V8 positions are 1-based; the source map's are 0-based
+        let (line, col) = (line.checked_sub(1)?, col.saturating_sub(1));
+        let token = source_map.lookup_token(line, col)?;
+        // Lookups fall back to the nearest earlier token, which for generated
+        // code (host module wrappers) is some unrelated line
+        if token.get_dst_line() != line {
+            return None;
+        }
+        let source = token.get_source()?;
+        let source = source.strip_prefix("file://").unwrap_or(source);
+        let display = cwd
+            .as_deref()
+            .and_then(|cwd| Path::new(source).strip_prefix(cwd).ok())
+            .map(|relative| relative.display().to_string())
+            .unwrap_or_else(|| source.to_string());
+        Some(format!("{}:{}", display, token.get_src_line() + 1))
+    })
+}
+
+/// Line and column of a stack frame such as `at f ([funee:runtime.js]:12:5)`,
+/// if it points into the bundle
+fn bundle_position(frame: &str) -> Option<(u32, u32)> {
+    let start = frame.find(BUNDLE_SCRIPT)? + BUNDLE_SCRIPT.len();
+    let mut parts = frame[start..].strip_prefix(':')?.trim_end_matches(')').splitn(2, ':');
+    let line = parts.next()?.parse().ok()?;
+    let col = parts.next()?.parse().ok()?;
+    Some((line, col))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_position() {
+        assert_eq!(bundle_position("    at main ([funee:runtime.js]:12:5)"), Some((12, 5)));
+        assert_eq!(bundle_position("    at [funee:runtime.js]:3:17"), Some((3, 17)));
+        assert_eq!(bundle_position("    at locate ([funee:log_locations.js]:4:9)"), None);
+        assert_eq!(bundle_position("Error"), None);
+    }
+}
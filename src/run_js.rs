@@ -1,4 +1,64 @@
+use crate::log_locations;
 use deno_core::{error::AnyError, Extension, FastString, OpDecl, PollEventLoopOptions};
+use sha2::{Digest, Sha256};
+use std::{collections::HashMap, env, fs, path::{Path, PathBuf}, process::{Command, Stdio}, sync::{LazyLock, Mutex, OnceLock}};
+
+/// Bootstrap JavaScript with the byte helpers the other bootstrap scripts and
+/// the host modules share, under `globalThis[Symbol.for("funee.bytes")]`
+const BYTES_BOOTSTRAP: &str = r#"
+(() => {
+    const base64Chars = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    const base64Lookup = new Uint8Array(128);
+    for (let i = 0; i < base64Chars.length; i++) {
+        base64Lookup[base64Chars.charCodeAt(i)] = i;
+    }
+    
+    // Bytes as base64; a string is encoded as UTF-8 first
+    const base64Encode = (data) => {
+        const bytes = typeof data === "string" ? Deno.core.encode(data) : data;
+        let result = "";
+        for (let i = 0; i < bytes.length; i += 3) {
+            const b1 = bytes[i];
+            const b2 = i + 1 < bytes.length ? bytes[i + 1] : 0;
+            const b3 = i + 2 < bytes.length ? bytes[i + 2] : 0;
+            result += base64Chars[b1 >> 2] + base64Chars[((b1 & 3) << 4) | (b2 >> 4)];
+            result += i + 1 < bytes.length ? base64Chars[((b2 & 15) << 2) | (b3 >> 6)] : "=";
+            result += i + 2 < bytes.length ? base64Chars[b3 & 63] : "=";
+        }
+        return result;
+    };
+    
+    // Base64 (padded or not) as a Uint8Array
+    const base64Decode = (text) => {
+        const clean = text.replace(/=+$/, "");
+        const bytes = new Uint8Array(Math.floor(clean.length * 3 / 4));
+        let byteIndex = 0;
+        for (let i = 0; i < clean.length; i += 4) {
+            const a = base64Lookup[clean.charCodeAt(i)];
+            const b = base64Lookup[clean.charCodeAt(i + 1)];
+            const c = base64Lookup[clean.charCodeAt(i + 2)];
+            const d = base64Lookup[clean.charCodeAt(i + 3)];
+            bytes[byteIndex++] = (a << 2) | (b >> 4);
+            if (i + 2 < clean.length) bytes[byteIndex++] = ((b & 15) << 4) | (c >> 2);
+            if (i + 3 < clean.length) bytes[byteIndex++] = ((c & 3) << 6) | d;
+        }
+        return bytes;
+    };
+    
+    // A body chunk (string, ArrayBuffer or typed array) as a Uint8Array
+    const chunkToBytes = (chunk) => {
+        if (typeof chunk === "string") return Deno.core.encode(chunk);
+        if (chunk instanceof Uint8Array) return chunk;
+        if (chunk instanceof ArrayBuffer) return new Uint8Array(chunk);
+        if (ArrayBuffer.isView(chunk)) return new Uint8Array(chunk.buffer, chunk.byteOffset, chunk.byteLength);
+        throw new TypeError("Body chunks must be strings, ArrayBuffers or typed arrays");
+    };
+    
+    Object.defineProperty(globalThis, Symbol.for("funee.bytes"), {
+        value: Object.freeze({ base64Encode, base64Decode, chunkToBytes }),
+    });
+})();
+"#;
 
 /// Bootstrap JavaScript that sets up timer globals (setTimeout, setInterval, etc.)
 const TIMER_BOOTSTRAP: &str = r#"
@@ -33,38 +93,28 @@ const TIMER_BOOTSTRAP: &str = r#"
         Deno.core.ops.op_timerCancel(timerId);
     };
     
-    // setInterval implementation
+    // setInterval implementation: the host keeps the schedule, so ticks
+    // don't drift with callback run time
     globalThis.setInterval = (callback, delay = 0, ...args) => {
-        let active = true;
-        let currentTimerId;
-        
-        const scheduleNext = async () => {
-            while (active) {
-                currentTimerId = Deno.core.ops.op_timerStart();
-                const completed = await Deno.core.ops.op_timerWait(currentTimerId, delay);
-                if (completed && active) {
+        const intervalId = Deno.core.ops.op_intervalStart(delay);
+        timerCallbacks.set(intervalId, { type: 'interval' });
+        
+        (async () => {
+            try {
+                // A tick pending when the interval is cleared resolves to false
+                while (await Deno.core.ops.op_intervalTick(intervalId)) {
+                    if (!timerCallbacks.has(intervalId)) {
+                        break;
+                    }
                     callback(...args);
-                } else {
-                    break;
                 }
+            } finally {
+                // Also reached when the callback throws, which would otherwise
+                // leave the host-side interval behind
+                timerCallbacks.delete(intervalId);
+                Deno.core.ops.op_intervalCancel(intervalId);
             }
-        };
-        
-        // Start the interval loop
-        scheduleNext();
-        
-        // Return an object that tracks the interval
-        // We use a unique ID for clearInterval
-        const intervalId = Deno.core.ops.op_timerStart();
-        timerCallbacks.set(intervalId, { 
-            type: 'interval',
-            cancel: () => { 
-                active = false; 
-                if (currentTimerId !== undefined) {
-                    Deno.core.ops.op_timerCancel(currentTimerId);
-                }
-            } 
-        });
+        })();
         
         return intervalId;
     };
@@ -73,8 +123,8 @@ const TIMER_BOOTSTRAP: &str = r#"
     globalThis.clearInterval = (intervalId) => {
         const interval = timerCallbacks.get(intervalId);
         if (interval && interval.type === 'interval') {
-            interval.cancel();
             timerCallbacks.delete(intervalId);
+            Deno.core.ops.op_intervalCancel(intervalId);
         }
     };
 })();
@@ -258,8 +308,11 @@ const FETCH_BOOTSTRAP: &str = r#"
             } else if (init instanceof Headers || (init && typeof init.get === 'function' && typeof init.set === 'function')) {
                 // Copy from another Headers or Headers-like object
                 // Check for get/set methods instead of entries() to avoid matching arrays
+                // (entries() yields each Set-Cookie separately, so append keeps them apart)
                 for (const [name, value] of init.entries()) {
-                    _headers.set(normalizeName(name), [normalizeValue(value)]);
+                    const key = normalizeName(name);
+                    if (!_headers.has(key)) _headers.set(key, []);
+                    _headers.get(key).push(normalizeValue(value));
                 }
             } else if (typeof init === 'object') {
                 // Plain object
@@ -299,10 +352,19 @@ const FETCH_BOOTSTRAP: &str = r#"
             _headers.get(key).push(normalizeValue(value));
         };
         
-        // entries() - iterator of [name, value] pairs
+        // getSetCookie() - every Set-Cookie value, which can't be combined with commas
+        this.getSetCookie = () => {
+            return [...(_headers.get('set-cookie') || [])];
+        };
+        
+        // entries() - iterator of [name, value] pairs; Set-Cookie values stay separate
         this.entries = function* () {
             for (const [name, values] of _headers) {
-                yield [name, values.join(', ')];
+                if (name === 'set-cookie') {
+                    for (const value of values) yield [name, value];
+                } else {
+                    yield [name, values.join(', ')];
+                }
             }
         };
         
@@ -315,15 +377,15 @@ const FETCH_BOOTSTRAP: &str = r#"
         
         // values() - iterator of header values
         this.values = function* () {
-            for (const values of _headers.values()) {
-                yield values.join(', ');
+            for (const [, value] of this.entries()) {
+                yield value;
             }
         };
         
         // forEach(callback)
         this.forEach = (callback) => {
-            for (const [name, values] of _headers) {
-                callback(values.join(', '), name, this);
+            for (const [name, value] of this.entries()) {
+                callback(value, name, this);
             }
         };
         
@@ -331,11 +393,60 @@ const FETCH_BOOTSTRAP: &str = r#"
         this[Symbol.iterator] = this.entries;
     }
     
+    // ========================================================================
+    // Host-side response bodies
+    // ========================================================================
+    
+    // Key for passing a host-side fetch body to Response (see op_fetchStart).
+    // The body is shared by a response and its clones: { handle, refs, signal, onClose }
+    const kFetchBody = Symbol('fetchBody');
+    
+    const releaseFetchBody = (fetchBody) => {
+        fetchBody.refs--;
+        if (fetchBody.refs === 0) {
+            if (fetchBody.onClose) fetchBody.onClose();
+            Deno.core.ops.op_fetchClose(fetchBody.handle);
+        }
+    };
+    
+    // Releases the share of a response that was dropped without reading its body
+    const fetchBodyRegistry = new FinalizationRegistry(releaseFetchBody);
+    
+    const { base64Encode, base64Decode, chunkToBytes } = globalThis[Symbol.for("funee.bytes")];
+    
+    // ========================================================================
+    // Streaming request bodies
+    // ========================================================================
+    
+    // Async iterables (e.g. async generators) and ReadableStream-like objects
+    // are uploaded chunk by chunk instead of being buffered
+    const isStreamingBody = (body) =>
+        body != null && typeof body !== 'string' &&
+        (typeof body[Symbol.asyncIterator] === 'function' || typeof body.getReader === 'function');
+    
+    async function* readStreamingBody(body) {
+        if (typeof body[Symbol.asyncIterator] === 'function') {
+            yield* body;
+            return;
+        }
+        const reader = body.getReader();
+        try {
+            while (true) {
+                const { done, value } = await reader.read();
+                if (done) return;
+                yield value;
+            }
+        } finally {
+            reader.releaseLock?.();
+        }
+    }
+    
     // ========================================================================
     // Response class - Web-standard Response implementation
     // ========================================================================
     function Response(body, init) {
         const _init = init || {};
+        const _fetchBody = _init[kFetchBody] || null;
         const _status = _init.status !== undefined ? _init.status : 200;
         const _statusText = _init.statusText !== undefined ? _init.statusText : '';
         const _headers = _init.headers instanceof Headers 
@@ -368,14 +479,79 @@ const FETCH_BOOTSTRAP: &str = r#"
             return _body;
         };
         
+        // This response's share of a host-side body is released after a read,
+        // on body.cancel(), or when the response is garbage collected unread
+        const _releaseToken = {};
+        let _released = false;
+        const releaseOnce = () => {
+            if (_released) return;
+            _released = true;
+            fetchBodyRegistry.unregister(_releaseToken);
+            releaseFetchBody(_fetchBody);
+        };
+        if (_fetchBody) fetchBodyRegistry.register(this, _fetchBody, _releaseToken);
+        
+        // Helper to consume a host-side body; releases it once every clone is done
+        const consumeFetchBody = async (read) => {
+            consumeBody();
+            try {
+                return await read(_fetchBody.handle);
+            } catch (error) {
+                const signal = _fetchBody.signal;
+                if (signal && signal.aborted) throw signal.reason;
+                throw error;
+            } finally {
+                releaseOnce();
+            }
+        };
+        
+        // body - for fetch responses, a minimal ReadableStream stand-in: reading
+        // yields the whole body as one chunk, and cancel() releases it unread
+        const _stream = !_fetchBody ? null : {
+            get locked() {
+                return _bodyUsed;
+            },
+            cancel: async () => {
+                if (_bodyUsed) return;
+                consumeBody();
+                releaseOnce();
+            },
+            getReader: () => {
+                if (_bodyUsed) {
+                    throw new TypeError('Body has already been consumed');
+                }
+                let read = false;
+                return {
+                    read: async () => {
+                        if (read) return { done: true, value: undefined };
+                        read = true;
+                        return { done: false, value: await this.bytes() };
+                    },
+                    cancel: () => _stream.cancel(),
+                    releaseLock: () => {},
+                };
+            },
+            [Symbol.asyncIterator]: async function* () {
+                const { value } = await _stream.getReader().read();
+                yield value;
+            },
+        };
+        Object.defineProperty(this, 'body', { get: () => _stream, enumerable: true });
+        
         // text() - get body as string
         this.text = async () => {
+            if (_fetchBody) {
+                return consumeFetchBody(Deno.core.ops.op_fetchBodyText);
+            }
             const body = consumeBody();
             return body || '';
         };
         
         // json() - parse body as JSON
         this.json = async () => {
+            if (_fetchBody) {
+                return consumeFetchBody(Deno.core.ops.op_fetchBodyJson);
+            }
             const body = consumeBody();
             if (!body) throw new SyntaxError('Unexpected end of JSON input');
             return JSON.parse(body);
@@ -383,13 +559,14 @@ const FETCH_BOOTSTRAP: &str = r#"
         
         // arrayBuffer() - get body as ArrayBuffer
         this.arrayBuffer = async () => {
-            const body = consumeBody();
-            const encoder = new TextEncoder();
-            return encoder.encode(body || '').buffer;
+            return (await this.bytes()).buffer;
         };
         
         // bytes() - get body as Uint8Array
         this.bytes = async () => {
+            if (_fetchBody) {
+                return base64Decode(await consumeFetchBody(Deno.core.ops.op_fetchBodyBytes));
+            }
             const body = consumeBody();
             const encoder = new TextEncoder();
             return encoder.encode(body || '');
@@ -397,27 +574,60 @@ const FETCH_BOOTSTRAP: &str = r#"
         
         // blob() - get body as Blob-like object
         this.blob = async () => {
-            const body = consumeBody();
-            const data = new TextEncoder().encode(body || '');
+            const [data, text] = _fetchBody
+                ? await consumeFetchBody(async (handle) => [
+                    base64Decode(await Deno.core.ops.op_fetchBodyBytes(handle)),
+                    await Deno.core.ops.op_fetchBodyText(handle),
+                ])
+                : (() => {
+                    const body = consumeBody() || '';
+                    return [new TextEncoder().encode(body), body];
+                })();
             return {
                 size: data.length,
                 type: _headers.get('content-type') || '',
                 arrayBuffer: async () => data.buffer,
-                text: async () => body || '',
+                text: async () => text,
             };
         };
         
-        // clone() - create a copy
+        // toFile(path, { onProgress, progressIntervalMs }) - funee extension: stream a
+        // fetch body straight into a file, resolving with the number of bytes written.
+        // onProgress gets { bytesDownloaded, totalBytes } at most every progressIntervalMs
+        // (default 100) and once at the end; totalBytes is null without a Content-Length
+        this.toFile = async (path, options) => {
+            if (!_fetchBody) {
+                throw new TypeError('toFile() is only supported for fetch responses');
+            }
+            const onProgress = options?.onProgress;
+            const intervalMs = onProgress ? Math.max(1, options?.progressIntervalMs ?? 100) : 0;
+            return consumeFetchBody(async (handle) => {
+                const written = Deno.core.ops.op_fetchToFile(handle, String(path), intervalMs);
+                if (onProgress) {
+                    let report;
+                    while ((report = JSON.parse(await Deno.core.ops.op_fetchProgress(handle))) !== null) {
+                        onProgress(report);
+                    }
+                }
+                return await written;
+            });
+        };
+        
+        // clone() - create a copy; fetch responses share the buffered host-side body
         this.clone = () => {
             if (_bodyUsed) {
                 throw new TypeError('Cannot clone a Response whose body has been consumed');
             }
+            if (_fetchBody) {
+                _fetchBody.refs++;
+            }
             return new Response(_body, {
                 status: _status,
                 statusText: _statusText,
                 headers: new Headers(_headers),
                 url: _url,
-                redirected: _redirected
+                redirected: _redirected,
+                [kFetchBody]: _fetchBody,
             });
         };
     }
@@ -452,6 +662,187 @@ const FETCH_BOOTSTRAP: &str = r#"
         });
     };
     
+    // ========================================================================
+    // AbortController / AbortSignal
+    // ========================================================================
+    
+    // Key for creating and aborting signals; AbortSignal can't be constructed directly
+    const kAbort = Symbol('abort');
+    
+    const namedError = (name, message) => {
+        const error = new Error(message);
+        error.name = name;
+        return error;
+    };
+    
+    function AbortSignal(key) {
+        if (key !== kAbort) throw new TypeError('Illegal constructor');
+        const listeners = [];
+        let aborted = false;
+        let reason = undefined;
+        
+        Object.defineProperties(this, {
+            aborted: { get: () => aborted, enumerable: true },
+            reason: { get: () => reason, enumerable: true },
+        });
+        this.onabort = null;
+        
+        this.addEventListener = (type, listener, options) => {
+            if (type !== 'abort' || !listener || listeners.some((entry) => entry.listener === listener)) return;
+            listeners.push({ listener, once: !!(options && options.once) });
+        };
+        
+        this.removeEventListener = (type, listener) => {
+            const index = listeners.findIndex((entry) => entry.listener === listener);
+            if (type === 'abort' && index !== -1) listeners.splice(index, 1);
+        };
+        
+        this.throwIfAborted = () => {
+            if (aborted) throw reason;
+        };
+        
+        this[kAbort] = (why) => {
+            if (aborted) return;
+            aborted = true;
+            reason = why !== undefined ? why : namedError('AbortError', 'This operation was aborted');
+            const event = { type: 'abort', target: this };
+            const handlers = this.onabort ? [{ listener: this.onabort }] : [];
+            for (const entry of [...handlers, ...listeners]) {
+                if (entry.once) this.removeEventListener('abort', entry.listener);
+                try {
+                    if (typeof entry.listener === 'function') entry.listener.call(this, event);
+                    else entry.listener.handleEvent(event);
+                } catch (error) {
+                    // A throwing listener doesn't stop the others (or abort() itself)
+                    queueMicrotask(() => { throw error; });
+                }
+            }
+        };
+    }
+    
+    AbortSignal.abort = (reason) => {
+        const signal = new AbortSignal(kAbort);
+        signal[kAbort](reason);
+        return signal;
+    };
+    
+    AbortSignal.timeout = (ms) => {
+        const signal = new AbortSignal(kAbort);
+        // Unlike setTimeout, the timer doesn't keep the run alive once
+        // nothing else is pending
+        const timerId = Deno.core.ops.op_timerStart();
+        const wait = Deno.core.ops.op_timerWait(timerId, ms);
+        Deno.core.unrefOpPromise(wait);
+        wait.then((completed) => {
+            if (completed) signal[kAbort](namedError('TimeoutError', 'The operation timed out'));
+        });
+        return signal;
+    };
+    
+    function AbortController() {
+        const signal = new AbortSignal(kAbort);
+        Object.defineProperty(this, 'signal', { value: signal, enumerable: true });
+        this.abort = (reason) => signal[kAbort](reason);
+    }
+    
+    // ========================================================================
+    // FormData - multipart/form-data request bodies
+    // ========================================================================
+    
+    // Key for the async method listing a FormData's parts for the host (see op_fetchStart)
+    const kFormDataParts = Symbol('formDataParts');
+    
+    function FormData() {
+        // Entries in order: { name, value, filename }; value is a string, or
+        // for files bytes (Uint8Array, ArrayBuffer, typed array) or a Blob-like
+        // object with arrayBuffer() (and optionally name and type)
+        const _entries = [];
+        
+        const toEntry = (name, value, filename) => {
+            if (typeof value === 'string' || typeof value !== 'object' || value === null) {
+                return { name: String(name), value: String(value), filename: undefined };
+            }
+            return { name: String(name), value, filename: filename !== undefined ? String(filename) : value.name };
+        };
+        
+        // append(name, value, filename?) - add an entry (names may repeat)
+        this.append = (name, value, filename) => {
+            _entries.push(toEntry(name, value, filename));
+        };
+        
+        // set(name, value, filename?) - replace every entry with this name
+        this.set = (name, value, filename) => {
+            const entry = toEntry(name, value, filename);
+            const index = _entries.findIndex((e) => e.name === entry.name);
+            if (index === -1) {
+                _entries.push(entry);
+                return;
+            }
+            _entries[index] = entry;
+            for (let i = _entries.length - 1; i > index; i--) {
+                if (_entries[i].name === entry.name) _entries.splice(i, 1);
+            }
+        };
+        
+        this.get = (name) => {
+            const entry = _entries.find((e) => e.name === String(name));
+            return entry ? entry.value : null;
+        };
+        
+        this.getAll = (name) => _entries.filter((e) => e.name === String(name)).map((e) => e.value);
+        
+        this.has = (name) => _entries.some((e) => e.name === String(name));
+        
+        this.delete = (name) => {
+            for (let i = _entries.length - 1; i >= 0; i--) {
+                if (_entries[i].name === String(name)) _entries.splice(i, 1);
+            }
+        };
+        
+        this.entries = function* () {
+            for (const entry of _entries) {
+                yield [entry.name, entry.value];
+            }
+        };
+        
+        this.keys = function* () {
+            for (const entry of _entries) yield entry.name;
+        };
+        
+        this.values = function* () {
+            for (const entry of _entries) yield entry.value;
+        };
+        
+        this.forEach = (callback) => {
+            for (const entry of _entries) {
+                callback(entry.value, entry.name, this);
+            }
+        };
+        
+        this[Symbol.iterator] = this.entries;
+        
+        // The parts the host builds the multipart body from
+        this[kFormDataParts] = async () => {
+            const parts = [];
+            for (const { name, value, filename } of _entries) {
+                if (typeof value === 'string') {
+                    parts.push({ name, value });
+                    continue;
+                }
+                const bytes = typeof value.arrayBuffer === 'function'
+                    ? new Uint8Array(await value.arrayBuffer())
+                    : chunkToBytes(value);
+                parts.push({
+                    name,
+                    filename: filename || 'blob',
+                    contentType: value.type || 'application/octet-stream',
+                    data: base64Encode(bytes),
+                });
+            }
+            return parts;
+        };
+    }
+    
     // ========================================================================
     // fetch() - Web-standard fetch implementation
     // ========================================================================
@@ -479,40 +870,133 @@ const FETCH_BOOTSTRAP: &str = r#"
         
         const options = init || {};
         const method = options.method || 'GET';
-        const body = options.body || '';
+        const formData = options.body instanceof FormData ? options.body : null;
+        const body = formData ? '' : (options.body || '');
         const followRedirects = options.redirect !== 'error' && options.redirect !== 'manual';
         
-        // Build headers JSON
-        let headersObj = {};
+        // Headers go to the host as [name, value] pairs, so a name given more
+        // than once (e.g. two Accept headers) is sent more than once
+        let headersList = [];
         if (options.headers) {
             if (options.headers instanceof Headers) {
-                for (const [name, value] of options.headers.entries()) {
-                    headersObj[name] = value;
-                }
+                headersList = [...options.headers.entries()];
             } else if (Array.isArray(options.headers)) {
-                for (const [name, value] of options.headers) {
-                    headersObj[name] = value;
-                }
+                headersList = options.headers.map(([name, value]) => [name, String(value)]);
             } else {
-                headersObj = options.headers;
+                headersList = Object.entries(options.headers).map(([name, value]) => [name, String(value)]);
             }
         }
-        const headersJson = JSON.stringify(headersObj);
+        if (formData) {
+            // The host sets Content-Type along with the multipart boundary
+            headersList = headersList.filter(([name]) => name.toLowerCase() !== 'content-type');
+        }
+        const headersJson = "{}";
+        const headersListJson = JSON.stringify(headersList);
+        const multipartJson = formData ? JSON.stringify(await formData[kFormDataParts]()) : '';
+        
+        // funee extension: { tls: { cert, key, ca } } for mTLS and private CAs (PEM strings),
+        // and { tls: { insecureSkipVerify: true } } under --allow-insecure-fetch
+        const tls = options.tls || {};
+        // funee extension: { timeout: ms } for the whole exchange, body included
+        const timeoutMs = options.timeout || 0;
+        // funee extension: { maxResponseBytes: n } fails reading a body once it
+        // grows past n bytes
+        const maxResponseBytes = Number(options.maxResponseBytes) || 0;
+        // funee extension: { proxy: url } for every scheme; by default HTTP_PROXY,
+        // HTTPS_PROXY and NO_PROXY from the environment apply
+        const proxy = options.proxy || '';
+        
+        // An aborted signal drops the request on the host and rejects with its reason
+        const signal = options.signal || null;
+        if (signal) signal.throwIfAborted();
+        const requestId = signal ? Deno.core.ops.op_fetchRequestId() : 0;
+        // Set once the response arrives: an aborted body can't be read any
+        // more, so the host lets go of it straight away
+        let responseHandle = null;
+        const onAbort = () => {
+            Deno.core.ops.op_fetchAbort(requestId);
+            if (responseHandle !== null) Deno.core.ops.op_fetchClose(responseHandle);
+        };
+        if (signal) signal.addEventListener('abort', onAbort);
         
-        // Call the Rust op
-        const resultJson = await Deno.core.ops.op_fetch(
-            method,
-            url,
-            headersJson,
-            body,
-            followRedirects
-        );
+        // Call the Rust op - the body stays on the host until it's read
+        let resultJson;
+        try {
+            if (isStreamingBody(body)) {
+                const stream = Deno.core.ops.op_fetchStreamBodyStart(
+                    method,
+                    url,
+                    headersJson,
+                    headersListJson,
+                    followRedirects,
+                    tls.cert || '',
+                    tls.key || '',
+                    tls.ca || '',
+                    !!tls.insecureSkipVerify,
+                    maxResponseBytes,
+                    timeoutMs,
+                    proxy,
+                    requestId
+                );
+                try {
+                    for await (const chunk of readStreamingBody(body)) {
+                        const encoded = base64Encode(chunkToBytes(chunk));
+                        try {
+                            await Deno.core.ops.op_fetchStreamBodyWrite(stream, encoded);
+                        } catch {
+                            // The request has ended (e.g. the server responded early)
+                            break;
+                        }
+                    }
+                } catch (error) {
+                    // Don't let a half-written body look complete to the server
+                    Deno.core.ops.op_fetchStreamBodyAbort(stream);
+                    throw error;
+                }
+                // If the request failed, this rejects with its error
+                resultJson = await Deno.core.ops.op_fetchStreamBodyEnd(stream);
+            } else {
+                resultJson = await Deno.core.ops.op_fetchStart(
+                    method,
+                    url,
+                    headersJson,
+                    headersListJson,
+                    body,
+                    multipartJson,
+                    followRedirects,
+                    tls.cert || '',
+                    tls.key || '',
+                    tls.ca || '',
+                    !!tls.insecureSkipVerify,
+                    maxResponseBytes,
+                    timeoutMs,
+                    proxy,
+                    requestId
+                );
+            }
+        } catch (error) {
+            if (signal) signal.removeEventListener('abort', onAbort);
+            if (signal && signal.aborted) throw signal.reason;
+            throw error;
+        }
         
         // Parse result
         const result = JSON.parse(resultJson);
+        responseHandle = result.handle;
+        // The signal keeps covering the body until the response is released
+        const detachSignal = () => {
+            if (signal) signal.removeEventListener('abort', onAbort);
+        };
+        if (signal && signal.aborted) {
+            detachSignal();
+            Deno.core.ops.op_fetchClose(result.handle);
+            throw signal.reason;
+        }
         
         // Handle redirect: "error" - should have thrown if redirect happened with followRedirects=false
         if (options.redirect === 'error' && result.redirected) {
+            detachSignal();
+            Deno.core.ops.op_fetchClose(result.handle);
             throw new TypeError('Redirect not allowed');
         }
         
@@ -522,18 +1006,22 @@ const FETCH_BOOTSTRAP: &str = r#"
         }
         
         // Build Response object
-        return new Response(result.body, {
+        return new Response(null, {
             status: result.status,
             statusText: result.statusText,
             headers: result.headers,
             url: result.url,
-            redirected: result.redirected
+            redirected: result.redirected,
+            [kFetchBody]: { handle: result.handle, refs: 1, signal, onClose: detachSignal },
         });
     }
     
     // Expose globals
     globalThis.Headers = Headers;
     globalThis.Response = Response;
+    globalThis.AbortController = AbortController;
+    globalThis.AbortSignal = AbortSignal;
+    globalThis.FormData = FormData;
     globalThis.fetch = fetch;
 })();
 "#;
@@ -545,26 +1033,57 @@ const SERVER_BOOTSTRAP: &str = r#"
     // serve() - Deno-style HTTP server
     // ========================================================================
     
+    // Set on a request once it has been answered outside the handler's return value
+    const kResponded = Symbol("responded");
+    const kProcessId = Symbol.for("funee.processId");
+    // Ends a request's streamed body (see respondStream), aborting it when
+    // passed true
+    const kEndStream = Symbol("endStream");
+    
+    const { base64Decode, chunkToBytes } = globalThis[Symbol.for("funee.bytes")];
+    
+    /**
+     * Convert a Headers instance to a plain object for the host
+     */
+    function headersToObject(headers) {
+        const headersObj = {};
+        for (const [name, value] of headers.entries()) {
+            headersObj[name] = value;
+        }
+        return headersObj;
+    }
+    
     /**
      * Create server-side Request from raw request info
      */
-    function createServerRequest(raw, port) {
+    function createServerRequest(raw, serverId, port, scheme) {
         // raw.url already contains the path+query, e.g. "/test?foo=bar"
-        const fullUrl = `http://127.0.0.1:${port}${raw.url}`;
+        const fullUrl = `${scheme}://127.0.0.1:${port}${raw.url}`;
         
         // Build headers
         const headers = new Headers();
         for (const [name, value] of raw.headers) {
-            headers.set(name, value);
+            headers.append(name, value);
         }
         
         // Body reading state
         let bodyRead = false;
         let cachedBody = null;
         
-        const getBody = () => {
+        // Clients that sent "Expect: 100-continue" wait for the interim
+        // response before sending the body
+        let continued = !raw.expect_continue;
+        const sendContinue = async () => {
+            if (!continued) {
+                continued = true;
+                await Deno.core.ops.op_serverContinue(serverId, raw.request_id);
+            }
+        };
+        
+        const getBody = async () => {
             if (!bodyRead) {
-                cachedBody = Deno.core.ops.op_serverReadBody(raw.request_id);
+                await sendContinue();
+                cachedBody = Deno.core.ops.op_serverReadBody(serverId, raw.request_id);
                 bodyRead = true;
             }
             return cachedBody || "";
@@ -578,6 +1097,132 @@ const SERVER_BOOTSTRAP: &str = r#"
             body: raw.has_body ? {} : null,
             bodyUsed: false,
             
+            /**
+             * Send "100 Continue" to a client that is waiting for it.
+             * Reading the body does this automatically; responding without
+             * reading the body rejects the upload.
+             */
+            sendContinue,
+            
+            [kResponded]: false,
+            
+            /**
+             * Respond with a child process's stdout, streamed by the host
+             * without passing each chunk through JavaScript. The process must
+             * be spawned with stdout: "piped". The handler's return value is
+             * ignored once this has been called.
+             */
+            async respondFromProcess(process, init = {}) {
+                const processId = process?.[kProcessId];
+                if (processId === undefined) {
+                    throw new TypeError("respondFromProcess() requires a process returned by spawn()");
+                }
+                if (this[kResponded]) {
+                    throw new TypeError("Response has already been sent");
+                }
+                this[kResponded] = true;
+                await Deno.core.ops.op_serverRespondFromProcess(
+                    serverId,
+                    raw.request_id,
+                    processId,
+                    init.status ?? 200,
+                    JSON.stringify(headersToObject(new Headers(init.headers ?? {})))
+                );
+            },
+            
+            /**
+             * Respond with a file, streamed from disk by the host. With the
+             * default 200 status the request's Range header is honored
+             * (206 Partial Content, or 416 if the range is unsatisfiable).
+             */
+            async respondFile(path, init = {}) {
+                if (this[kResponded]) {
+                    throw new TypeError("Response has already been sent");
+                }
+                await Deno.core.ops.op_serverRespondFile(
+                    serverId,
+                    raw.request_id,
+                    String(path),
+                    init.status ?? 200,
+                    JSON.stringify(headersToObject(new Headers(init.headers ?? {})))
+                );
+                this[kResponded] = true;
+            },
+            
+            /**
+             * Start a response whose body is written in chunks: the status
+             * and headers go out right away, and each write() is flushed to
+             * the client as it arrives (e.g. for server-sent events or large
+             * downloads). Resolves with { write(chunk), close() }; the
+             * handler's return value is ignored once this has been called.
+             * The body ends when the handler settles, if close() wasn't
+             * called first, and is aborted if the handler threw.
+             */
+            async respondStream(init = {}) {
+                if (this[kResponded]) {
+                    throw new TypeError("Response has already been sent");
+                }
+                const streamId = Deno.core.ops.op_serverRespondStream(
+                    serverId,
+                    raw.request_id,
+                    init.status ?? 200,
+                    JSON.stringify(headersToObject(new Headers(init.headers ?? {})))
+                );
+                this[kResponded] = true;
+                let closed = false;
+                this[kEndStream] = (aborted) => {
+                    if (!closed) {
+                        closed = true;
+                        Deno.core.ops.op_serverStreamEnd(streamId, aborted);
+                    }
+                };
+                return {
+                    async write(chunk) {
+                        if (closed) {
+                            throw new TypeError("Response stream is closed");
+                        }
+                        const bytes = chunkToBytes(chunk);
+                        if (bytes.length > 0) {
+                            await Deno.core.ops.op_serverStreamWrite(streamId, bytes);
+                        }
+                    },
+                    close: async () => this[kEndStream](false),
+                };
+            },
+            
+            /**
+             * Answer a request that asked for a protocol upgrade (an Upgrade
+             * header with Connection: upgrade) with 101 Switching Protocols
+             * and take over its connection, e.g. for WebSockets. Resolves
+             * with { read(maxBytes), write(chunk), close() } once the 101
+             * response is sent; the handler's return value is ignored
+             * afterwards.
+             */
+            async upgrade(init = {}) {
+                if (this[kResponded]) {
+                    throw new TypeError("Response has already been sent");
+                }
+                const connectionId = await Deno.core.ops.op_serverUpgrade(
+                    serverId,
+                    raw.request_id,
+                    JSON.stringify(headersToObject(new Headers(init.headers ?? {})))
+                );
+                this[kResponded] = true;
+                return {
+                    async read(maxBytes = 65536) {
+                        const chunk = JSON.parse(await Deno.core.ops.op_serverUpgradedRead(connectionId, maxBytes));
+                        return { data: base64Decode(chunk.data_base64), done: chunk.eof };
+                    },
+                    async write(chunk) {
+                        const bytes = chunkToBytes(chunk);
+                        if (bytes.length > 0) {
+                            await Deno.core.ops.op_serverUpgradedWrite(connectionId, bytes);
+                        }
+                    },
+                    close: () => Deno.core.ops.op_serverUpgradedClose(connectionId),
+                };
+            },
+            
             async text() {
                 if (this.bodyUsed) {
                     throw new TypeError("Body has already been consumed");
@@ -603,11 +1248,7 @@ const SERVER_BOOTSTRAP: &str = r#"
      * Send response to client
      */
     async function sendResponse(serverId, requestId, response) {
-        // Extract headers
-        const headersObj = {};
-        for (const [name, value] of response.headers.entries()) {
-            headersObj[name] = value;
-        }
+        const headersObj = headersToObject(response.headers);
         
         // Get body (may have already been read for cloned responses)
         let body = "";
@@ -631,12 +1272,21 @@ const SERVER_BOOTSTRAP: &str = r#"
     /**
      * Handle a single request
      */
-    async function handleRequest(serverId, port, raw, handler, onError) {
+    async function handleRequest(serverId, port, scheme, raw, handler, onError) {
+        let request = null;
+        let threw = false;
         try {
-            const request = createServerRequest(raw, port);
+            request = createServerRequest(raw, serverId, port, scheme);
             const response = await handler(request);
-            await sendResponse(serverId, raw.request_id, response);
+            if (!request[kResponded]) {
+                await sendResponse(serverId, raw.request_id, response);
+            }
         } catch (error) {
+            threw = true;
+            if (request?.[kResponded]) {
+                // The response is already streaming; nothing left to answer
+                return;
+            }
             let errorResponse;
             if (onError) {
                 try {
@@ -648,6 +1298,9 @@ const SERVER_BOOTSTRAP: &str = r#"
                 errorResponse = new Response("Internal Server Error", { status: 500 });
             }
             await sendResponse(serverId, raw.request_id, errorResponse);
+        } finally {
+            // A streamed body ends with its handler, cut short if it threw
+            request?.[kEndStream]?.(threw);
         }
     }
     
@@ -661,13 +1314,31 @@ const SERVER_BOOTSTRAP: &str = r#"
         const hostname = options.hostname || "127.0.0.1";
         const onListen = options.onListen;
         const onError = options.onError;
+        // { certFile, keyFile }: PEM file paths to serve HTTPS
+        const tls = options.tls || null;
+        // Larger request bodies get 413; 0/unset means the host default
+        const maxBodyBytes = Number(options.maxBodyBytes) || 0;
+        // Persistent connections unless explicitly turned off
+        const keepAlive = options.keepAlive !== false;
+        // Connections waiting this long for a request are closed; 0/unset
+        // means the host default
+        const idleTimeoutMs = Number(options.idleTimeoutMs) || 0;
+        const scheme = tls ? "https" : "http";
         
         let isShuttingDown = false;
         let pendingRequests = 0;
         let shutdownResolve = null;
         
         // Start server synchronously - port is available immediately
-        const resultJson = Deno.core.ops.op_serverStart(port, hostname);
+        const resultJson = Deno.core.ops.op_serverStart(
+            port,
+            hostname,
+            tls ? String(tls.certFile || '') : '',
+            tls ? String(tls.keyFile || '') : '',
+            maxBodyBytes,
+            keepAlive,
+            idleTimeoutMs
+        );
         const result = JSON.parse(resultJson);
         const serverId = result.server_id;
         const actualPort = result.port;
@@ -682,7 +1353,7 @@ const SERVER_BOOTSTRAP: &str = r#"
         const handleRequestWithTracking = async (serverId, port, raw, handler, onError) => {
             pendingRequests++;
             try {
-                await handleRequest(serverId, port, raw, handler, onError);
+                await handleRequest(serverId, port, scheme, raw, handler, onError);
             } finally {
                 pendingRequests--;
                 // Check if we're shutting down and all requests are done
@@ -692,16 +1363,20 @@ const SERVER_BOOTSTRAP: &str = r#"
             }
         };
         
-        // Track if accept loop is waiting
-        let acceptLoopStopped = false;
+        // Resolves once the accept loop has exited (exposed as server.finished)
         let acceptLoopResolve = null;
+        const finished = new Promise((resolve) => {
+            acceptLoopResolve = resolve;
+        });
         
         // Start accept loop asynchronously
         const acceptLoop = async () => {
             while (!isShuttingDown) {
                 const requestJson = await Deno.core.ops.op_serverAccept(serverId);
                 
-                if (requestJson === null || requestJson === "null" || isShuttingDown) {
+                // A request accepted as shutdown begins is still handled,
+                // so its connection gets a response
+                if (requestJson === null || requestJson === "null") {
                     break;
                 }
                 
@@ -710,16 +1385,18 @@ const SERVER_BOOTSTRAP: &str = r#"
                 // Handle request concurrently (don't await)
                 handleRequestWithTracking(serverId, actualPort, raw, handler, onError).catch(() => {});
             }
-            acceptLoopStopped = true;
-            if (acceptLoopResolve) acceptLoopResolve();
         };
         
         // Start accept loop (don't await - runs in background)
-        acceptLoop().catch(() => {});
+        acceptLoop().catch(() => {}).finally(() => acceptLoopResolve());
         
-        // Shutdown function
-        const shutdown = async () => {
+        // Shutdown function: resolves to true once in-flight requests have
+        // drained, or false if drainTimeoutMs (default 30s, 0 = no limit)
+        // elapsed first
+        const shutdown = async (options = {}) => {
             isShuttingDown = true;
+            const drainTimeoutMs = Math.max(0, options.drainTimeoutMs ?? 30000);
+            const deadline = Date.now() + drainTimeoutMs;
             
             // Wait for pending requests to complete
             if (pendingRequests > 0) {
@@ -731,148 +1408,113 @@ const SERVER_BOOTSTRAP: &str = r#"
                         }
                         resolve();
                     };
-                    // Also set a timeout just in case
-                    timeoutId = setTimeout(shutdownResolve, 30000);
+                    if (drainTimeoutMs > 0) {
+                        timeoutId = setTimeout(shutdownResolve, drainTimeoutMs);
+                    }
                 });
             }
             
-            await Deno.core.ops.op_serverStop(serverId);
+            // The host gets whatever is left of the budget for open connections
+            // (at least 1ms, since 0 tells it to wait forever)
+            const remainingMs = drainTimeoutMs === 0 ? 0 : Math.max(1, deadline - Date.now());
+            const result = JSON.parse(await Deno.core.ops.op_serverStop(serverId, remainingMs));
+            return result.drained;
         };
         
         // Return server handle
         return {
+            get serverId() {
+                return serverId;
+            },
             get port() {
                 return actualPort;
             },
             get hostname() {
                 return actualHostname;
             },
+            finished,
             shutdown,
-            [Symbol.asyncDispose]: shutdown
+            [Symbol.asyncDispose]: async () => {
+                await shutdown();
+            }
         };
     };
 })();
 "#;
 
-const SUBPROCESS_BOOTSTRAP: &str = r#"
+/// Bootstrap JavaScript for `--log-locations`: prefixes console output (and the
+/// raw log ops) with the original `file:line` of the call
+const LOG_LOCATIONS_BOOTSTRAP: &str = r#"
 (() => {
-    // Base64 encode/decode helpers (without using btoa/atob which aren't available)
-    const base64Chars = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    const ops = Deno.core.ops;
+    const withLocation = (print) => (...args) => {
+        const location = ops.op_logLocation(new Error().stack ?? "");
+        return location ? print(location, ...args) : print(...args);
+    };
     
-    const base64Encode = (data) => {
-        const bytes = typeof data === "string" 
-            ? utf8Encode(data)
-            : data;
-        
-        let result = "";
-        let i = 0;
-        while (i < bytes.length) {
-            const b1 = bytes[i++];
-            const hasB2 = i < bytes.length;
-            const b2 = hasB2 ? bytes[i++] : 0;
-            const hasB3 = i < bytes.length;
-            const b3 = hasB3 ? bytes[i++] : 0;
-            
-            result += base64Chars.charAt(b1 >> 2);
-            result += base64Chars.charAt(((b1 & 3) << 4) | (b2 >> 4));
-            result += hasB2 ? base64Chars.charAt(((b2 & 15) << 2) | (b3 >> 6)) : "=";
-            result += hasB3 ? base64Chars.charAt(b3 & 63) : "=";
+    console.log = withLocation(console.log.bind(console));
+    console.debug = withLocation(console.debug.bind(console));
+    for (const name of ["op_log", "op_debug"]) {
+        const op = ops[name];
+        if (op) {
+            const print = withLocation((...parts) => op(parts.join(" ")));
+            ops[name] = (message) => print(message);
         }
-        return result;
-    };
+    }
+})();
+"#;
 
-    const base64Decode = (encoded) => {
-        const lookup = {};
-        for (let i = 0; i < base64Chars.length; i++) {
-            lookup[base64Chars.charAt(i)] = i;
-        }
-        
-        const len = encoded.length;
-        let bufferLength = Math.floor(len * 0.75);
-        
-        // Adjust for padding
-        let padding = 0;
-        if (encoded[len - 1] === "=") padding++;
-        if (encoded[len - 2] === "=") padding++;
-        bufferLength -= padding;
-        
-        const bytes = new Uint8Array(bufferLength);
-        let p = 0;
-        
-        for (let i = 0; i < len; i += 4) {
-            const c1 = lookup[encoded.charAt(i)];
-            const c2 = lookup[encoded.charAt(i + 1)];
-            const c3 = encoded.charAt(i + 2) === "=" ? undefined : lookup[encoded.charAt(i + 2)];
-            const c4 = encoded.charAt(i + 3) === "=" ? undefined : lookup[encoded.charAt(i + 3)];
-            
-            if (p < bufferLength) bytes[p++] = (c1 << 2) | (c2 >> 4);
-            if (c3 !== undefined && p < bufferLength) bytes[p++] = ((c2 & 15) << 4) | (c3 >> 2);
-            if (c4 !== undefined && p < bufferLength) bytes[p++] = ((c3 & 3) << 6) | c4;
-        }
-        
-        return bytes;
-    };
+/// Bootstrap JavaScript that sets up spawn() for subprocesses
+const SUBPROCESS_BOOTSTRAP: &str = r#"
+(() => {
+    const { base64Encode, base64Decode } = globalThis[Symbol.for("funee.bytes")];
 
-    // Simple UTF-8 decoder
-    const utf8Decode = (bytes) => {
+    // UTF-8 decoder: invalid sequences become U+FFFD (as on the host), or
+    // with strict throw a TypeError naming the byte offset
+    const utf8Decode = (bytes, strict = false) => {
         let result = "";
         let i = 0;
         while (i < bytes.length) {
+            const start = i;
             const byte1 = bytes[i++];
             if (byte1 < 0x80) {
                 result += String.fromCharCode(byte1);
-            } else if (byte1 < 0xE0) {
-                const byte2 = bytes[i++];
-                result += String.fromCharCode(((byte1 & 0x1F) << 6) | (byte2 & 0x3F));
-            } else if (byte1 < 0xF0) {
-                const byte2 = bytes[i++];
-                const byte3 = bytes[i++];
-                result += String.fromCharCode(((byte1 & 0x0F) << 12) | ((byte2 & 0x3F) << 6) | (byte3 & 0x3F));
-            } else {
-                const byte2 = bytes[i++];
-                const byte3 = bytes[i++];
-                const byte4 = bytes[i++];
-                let codePoint = ((byte1 & 0x07) << 18) | ((byte2 & 0x3F) << 12) | ((byte3 & 0x3F) << 6) | (byte4 & 0x3F);
-                codePoint -= 0x10000;
-                result += String.fromCharCode(0xD800 + (codePoint >> 10));
-                result += String.fromCharCode(0xDC00 + (codePoint & 0x3FF));
+                continue;
             }
-        }
-        return result;
-    };
-
-    // Simple UTF-8 encoder  
-    const utf8Encode = (str) => {
-        const bytes = [];
-        for (let i = 0; i < str.length; i++) {
-            let code = str.charCodeAt(i);
-            
-            // Handle surrogate pairs
-            if (code >= 0xD800 && code <= 0xDBFF && i + 1 < str.length) {
-                const next = str.charCodeAt(i + 1);
-                if (next >= 0xDC00 && next <= 0xDFFF) {
-                    code = 0x10000 + ((code & 0x3FF) << 10) + (next & 0x3FF);
-                    i++;
+            // Sequence length and the allowed range of the second byte
+            // (excludes overlong forms, surrogates and code points past U+10FFFF)
+            let length = 0, low = 0x80, high = 0xBF;
+            if (byte1 >= 0xC2 && byte1 <= 0xDF) length = 2;
+            else if (byte1 >= 0xE0 && byte1 <= 0xEF) {
+                length = 3;
+                if (byte1 === 0xE0) low = 0xA0;
+                if (byte1 === 0xED) high = 0x9F;
+            } else if (byte1 >= 0xF0 && byte1 <= 0xF4) {
+                length = 4;
+                if (byte1 === 0xF0) low = 0x90;
+                if (byte1 === 0xF4) high = 0x8F;
+            }
+            let codePoint = length === 2 ? byte1 & 0x1F : length === 3 ? byte1 & 0x0F : byte1 & 0x07;
+            let valid = length > 0;
+            for (let n = 1; valid && n < length; n++) {
+                const byte = bytes[i];
+                if (byte === undefined || byte < (n === 1 ? low : 0x80) || byte > (n === 1 ? high : 0xBF)) {
+                    valid = false;
+                    break;
                 }
+                codePoint = (codePoint << 6) | (byte & 0x3F);
+                i++;
             }
-            
-            if (code < 0x80) {
-                bytes.push(code);
-            } else if (code < 0x800) {
-                bytes.push(0xC0 | (code >> 6));
-                bytes.push(0x80 | (code & 0x3F));
-            } else if (code < 0x10000) {
-                bytes.push(0xE0 | (code >> 12));
-                bytes.push(0x80 | ((code >> 6) & 0x3F));
-                bytes.push(0x80 | (code & 0x3F));
-            } else {
-                bytes.push(0xF0 | (code >> 18));
-                bytes.push(0x80 | ((code >> 12) & 0x3F));
-                bytes.push(0x80 | ((code >> 6) & 0x3F));
-                bytes.push(0x80 | (code & 0x3F));
+            if (!valid) {
+                if (strict) {
+                    throw new TypeError(`Invalid UTF-8 sequence at byte ${start}`);
+                }
+                result += "\uFFFD";
+                continue;
             }
+            result += String.fromCodePoint(codePoint);
         }
-        return new Uint8Array(bytes);
+        return result;
     };
 
     // Spawn function that returns a Process handle or Promise<CommandOutput>
@@ -881,6 +1523,8 @@ const SUBPROCESS_BOOTSTRAP: &str = r#"
         
         // Normalize to options object
         const isSimpleForm = typeof commandOrOptions === "string";
+        // Detached children default to null stdio so they don't hold on to ours
+        const defaultOutput = !isSimpleForm && commandOrOptions.detached ? "null" : "piped";
         const options = isSimpleForm
             ? { 
                 cmd: [commandOrOptions, ...(args || [])],
@@ -889,8 +1533,8 @@ const SUBPROCESS_BOOTSTRAP: &str = r#"
                 stdin: "null",
               }
             : {
-                stdout: commandOrOptions.stdout || "piped",
-                stderr: commandOrOptions.stderr || "piped",
+                stdout: commandOrOptions.stdout || defaultOutput,
+                stderr: commandOrOptions.stderr || defaultOutput,
                 stdin: commandOrOptions.stdin || "null",
                 ...commandOrOptions,
               };
@@ -904,6 +1548,7 @@ const SUBPROCESS_BOOTSTRAP: &str = r#"
             options.stdin || "null",
             options.stdout || "piped",
             options.stderr || "piped",
+            options.detached === true,
         );
         
         const result = JSON.parse(resultJson);
@@ -912,17 +1557,20 @@ const SUBPROCESS_BOOTSTRAP: &str = r#"
         
         let stdinClosed = false;
         let statusPromise = null;
+        // Set once statusPromise resolves, for poll()
+        let exitStatus = null;
         
         const getStatus = () => {
             if (!statusPromise) {
                 statusPromise = (async () => {
                     const waitResultJson = await ops.op_processWait(processId);
                     const waitResult = JSON.parse(waitResultJson);
-                    return {
+                    exitStatus = {
                         success: waitResult.success,
                         code: waitResult.code,
                         signal: waitResult.signal,
                     };
+                    return exitStatus;
                 })();
             }
             return statusPromise;
@@ -931,6 +1579,9 @@ const SUBPROCESS_BOOTSTRAP: &str = r#"
         const process = {
             pid,
             
+            // Lets serve() stream this process's stdout into a response
+            [Symbol.for("funee.processId")]: processId,
+            
             get status() {
                 return getStatus();
             },
@@ -939,15 +1590,29 @@ const SUBPROCESS_BOOTSTRAP: &str = r#"
                 ops.op_processKill(processId, signal);
             },
             
+            // Exit status if the process has finished, otherwise null
+            poll() {
+                if (statusPromise) {
+                    // op_processWait owns the child now
+                    return exitStatus;
+                }
+                const result = JSON.parse(ops.op_processStatus(processId));
+                if (result.running) {
+                    return null;
+                }
+                return {
+                    success: result.success,
+                    code: result.code,
+                    signal: result.signal,
+                };
+            },
+            
             async output() {
-                const stdoutPromise = options.stdout === "piped" 
-                    ? ops.op_processReadStdout(processId) 
-                    : Promise.resolve("");
-                const stderrPromise = options.stderr === "piped" 
-                    ? ops.op_processReadStderr(processId) 
-                    : Promise.resolve("");
-                
-                const [stdoutBase64, stderrBase64] = await Promise.all([stdoutPromise, stderrPromise]);
+                // Both pipes are drained together on the host, so a child
+                // filling one while we wait on the other can't hang
+                const pipes = JSON.parse(await ops.op_processReadOutput(processId));
+                const stdoutBase64 = pipes.stdout_base64;
+                const stderrBase64 = pipes.stderr_base64;
                 
                 const status = await getStatus();
                 
@@ -958,11 +1623,54 @@ const SUBPROCESS_BOOTSTRAP: &str = r#"
                     status,
                     stdout,
                     stderr,
-                    stdoutText: () => utf8Decode(stdout),
-                    stderrText: () => utf8Decode(stderr),
+                    stdoutText: ({ strict = false } = {}) => utf8Decode(stdout, strict),
+                    stderrText: ({ strict = false } = {}) => utf8Decode(stderr, strict),
                 };
             },
             
+            // Read all of stdout decoded as "utf-8" or "latin1"
+            async readStdoutText(encoding = "utf-8", { strict = false } = {}) {
+                if (options.stdout !== "piped") {
+                    throw new Error("Cannot read stdout: stdout is not piped");
+                }
+                return await ops.op_processReadStdoutText(processId, encoding, strict);
+            },
+            
+            // Yield stdout line by line, without line terminators, as it arrives
+            async *stdoutLines(encoding = "utf-8", { strict = false } = {}) {
+                if (options.stdout !== "piped") {
+                    throw new Error("Cannot read stdout: stdout is not piped");
+                }
+                while (true) {
+                    const line = JSON.parse(await ops.op_processReadStdoutLine(processId, encoding, strict));
+                    if (line === null) {
+                        return;
+                    }
+                    yield line;
+                }
+            },
+            
+            // Read the stdout written so far (waiting for at least one byte):
+            // { data, done }, with done set and no data once stdout closes
+            async readStdoutChunk(maxBytes = 65536) {
+                if (options.stdout !== "piped") {
+                    throw new Error("Cannot read stdout: stdout is not piped");
+                }
+                const chunk = JSON.parse(await ops.op_processReadStdoutChunk(processId, maxBytes));
+                return { data: base64Decode(chunk.data_base64), done: chunk.eof };
+            },
+            
+            // Yield stdout in chunks as the process writes it
+            async *stdoutChunks(maxBytes = 65536) {
+                while (true) {
+                    const { data, done } = await process.readStdoutChunk(maxBytes);
+                    if (done) {
+                        return;
+                    }
+                    yield data;
+                }
+            },
+            
             async writeInput(data) {
                 if (options.stdin !== "piped") {
                     throw new Error("Cannot write to stdin: stdin is not piped");
@@ -987,31 +1695,238 @@ const SUBPROCESS_BOOTSTRAP: &str = r#"
         // For options form, return Process handle
         return process;
     };
+    
+    // Run a command to completion in a single host call, capturing its
+    // output (exec() from host://process)
+    globalThis[Symbol.for("funee.exec")] = async (commandOrOptions, args) => {
+        const options = typeof commandOrOptions === "string"
+            ? { cmd: [commandOrOptions, ...(args || [])] }
+            : commandOrOptions;
+        
+        const resultJson = await Deno.core.ops.op_processExec(
+            JSON.stringify(options.cmd),
+            options.cwd || "",
+            JSON.stringify(options.env || {}),
+            options.inheritEnv !== false,
+            options.stdin != null ? base64Encode(options.stdin) : "",
+        );
+        const result = JSON.parse(resultJson);
+        
+        const stdout = base64Decode(result.stdout_base64);
+        const stderr = base64Decode(result.stderr_base64);
+        return {
+            status: {
+                success: result.success,
+                code: result.code,
+                signal: result.signal,
+            },
+            stdout,
+            stderr,
+            stdoutText: ({ strict = false } = {}) => utf8Decode(stdout, strict),
+            stderrText: ({ strict = false } = {}) => utf8Decode(stderr, strict),
+        };
+    };
 })();
 "#;
 
-pub async fn run_js(js: &str, ops: Vec<OpDecl>) -> Result<(), AnyError> {
-    let ext = Extension {
+/// Bootstrap scripts run before the bundle, in order: the shared byte
+/// helpers, timers, then fetch/Headers/Response, serve() and spawn()
+const BOOTSTRAP_SCRIPTS: &[(&str, &str)] = &[
+    ("[funee:bytes.js]", BYTES_BOOTSTRAP),
+    ("[funee:timers.js]", TIMER_BOOTSTRAP),
+    ("[funee:fetch.js]", FETCH_BOOTSTRAP),
+    ("[funee:server.js]", SERVER_BOOTSTRAP),
+    ("[funee:subprocess.js]", SUBPROCESS_BOOTSTRAP),
+];
+
+fn runtime_extension(ops: Vec<OpDecl>) -> Extension {
+    Extension {
         ops: std::borrow::Cow::Owned(ops),
         ..Default::default()
+    }
+}
+
+/// V8 flags given with `--v8-flags`; a snapshot is only valid under the
+/// flags it was taken with
+static V8_FLAGS: OnceLock<Vec<String>> = OnceLock::new();
+
+/// Pass `flags` on to V8, returning the ones it doesn't recognize
+pub fn set_v8_flags(flags: Vec<String>) -> Vec<String> {
+    // The first element is the program name; everything after it is unrecognized
+    let unrecognized = deno_core::v8_set_flags(std::iter::once("funee".to_string()).chain(flags.clone()).collect());
+    let _ = V8_FLAGS.set(flags);
+    unrecognized.into_iter().skip(1).collect()
+}
+
+/// Header of a cached snapshot file, followed by the SHA-256 of the snapshot
+/// and the snapshot itself
+const SNAPSHOT_MAGIC: &[u8] = b"FUNEESNAP1";
+
+/// Hidden flag that makes funee write a snapshot instead of running a file:
+/// `--write-snapshot=<path> --snapshot-ops=<op,...> [--v8-flags=<flags>]`
+pub const WRITE_SNAPSHOT_FLAG: &str = "--write-snapshot";
+/// The ops (by OpDecl name) a snapshot written with WRITE_SNAPSHOT_FLAG has
+pub const SNAPSHOT_OPS_FLAG: &str = "--snapshot-ops";
+
+/// Snapshots already loaded by this process, by file. `funee test` and watch
+/// mode start a runtime per file or rerun, and each reuses the same copy
+static LOADED_SNAPSHOTS: LazyLock<Mutex<HashMap<PathBuf, &'static [u8]>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// A V8 startup snapshot taken after the bootstrap scripts ran, so runs don't
+/// re-parse and re-run them (or deno_core's own JS)
+///
+/// The snapshot has to be taken with the exact ops the runtime registers, and
+/// those depend on the run (--sandbox leaves most out), so instead of being
+/// built ahead of time it is created on first use and cached under
+/// `~/.funee/cache/snapshots`. Files are named `<build>-<run>.bin`: the build
+/// part identifies the executable, and snapshots of other builds are deleted
+/// whenever a new one is written; the run part covers the op names, the
+/// bootstrap sources and the V8 flags. A file that fails its checksum is
+/// replaced. FUNEE_NO_SNAPSHOT=1 turns it off
+///
+/// A missing snapshot is written by a child funee process (see
+/// write_snapshot): taking a snapshot starts V8 with `--predictable` and a
+/// fixed random seed for the whole process, which mustn't apply to user code.
+fn startup_snapshot(ops: &[OpDecl]) -> Option<&'static [u8]> {
+    if env::var_os("FUNEE_NO_SNAPSHOT").is_some() {
+        return None;
+    }
+    let dir = dirs::home_dir()?.join(".funee").join("cache").join("snapshots");
+
+    // V8 aborts on a snapshot from another build, so any rebuild gets a new key
+    let exe_path = env::current_exe().ok()?;
+    let exe = fs::metadata(&exe_path).ok()?;
+    let mut build = Sha256::new();
+    build.update(env!("CARGO_PKG_VERSION"));
+    build.update(exe.len().to_le_bytes());
+    if let Some(modified) = exe.modified().ok().and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok()) {
+        build.update(modified.as_nanos().to_le_bytes());
+    }
+    let build = hex::encode(&build.finalize()[..8]);
+
+    let mut run = Sha256::new();
+    for op in ops {
+        run.update(op.name);
+        run.update([0]);
+    }
+    for (name, source) in BOOTSTRAP_SCRIPTS {
+        run.update(name);
+        run.update(source);
+    }
+    for flag in V8_FLAGS.get().into_iter().flatten() {
+        run.update(flag);
+        run.update([0]);
+    }
+    let path = dir.join(format!("{}-{}.bin", build, hex::encode(run.finalize())));
+
+    if let Some(snapshot) = LOADED_SNAPSHOTS.lock().unwrap().get(&path) {
+        return Some(*snapshot);
+    }
+    let load = || fs::read(&path).ok().and_then(|file| verify_snapshot(&file).map(<[u8]>::to_vec));
+    let snapshot = match load() {
+        Some(snapshot) => snapshot,
+        None => {
+            let mut command = Command::new(&exe_path);
+            command
+                .arg(format!("{}={}", WRITE_SNAPSHOT_FLAG, path.display()))
+                .arg(format!("{}={}", SNAPSHOT_OPS_FLAG, ops.iter().map(|op| op.name).collect::<Vec<_>>().join(",")));
+            if let Some(flags) = V8_FLAGS.get().filter(|flags| !flags.is_empty()) {
+                command.arg(format!("--v8-flags={}", flags.join(",")));
+            }
+            let status = command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null()).status();
+            if !status.is_ok_and(|status| status.success()) {
+                return None;
+            }
+            load()?
+        }
     };
-    
+    let snapshot: &'static [u8] = Box::leak(snapshot.into_boxed_slice());
+    LOADED_SNAPSHOTS.lock().unwrap().insert(path, snapshot);
+    Some(snapshot)
+}
+
+/// Take a snapshot with `ops` and cache it at `path`, for startup_snapshot in
+/// a parent process; snapshots of other builds in the same directory are
+/// deleted. Runs in a process of its own (the hidden WRITE_SNAPSHOT_FLAG)
+pub fn write_snapshot(path: &Path, mut ops: Vec<OpDecl>) -> Result<(), AnyError> {
+    ops.sort_by_key(|op| op.name);
+    let snapshot = create_snapshot(&ops)?;
+    let mut file = SNAPSHOT_MAGIC.to_vec();
+    file.extend_from_slice(&Sha256::digest(&*snapshot));
+    file.extend_from_slice(&snapshot);
+
+    let dir = path.parent().ok_or_else(|| AnyError::msg("snapshot path has no directory"))?;
+    fs::create_dir_all(dir)?;
+    // Write then rename, so a concurrent run never loads a partial file
+    let partial = path.with_extension(format!("{}.partial", std::process::id()));
+    fs::write(&partial, &file)?;
+    if let Err(e) = fs::rename(&partial, path) {
+        let _ = fs::remove_file(&partial);
+        return Err(e.into());
+    }
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    if let Some((build, _)) = name.split_once('-') {
+        prune_snapshots(dir, build);
+    }
+    Ok(())
+}
+
+/// The snapshot in a cached snapshot file, if its header and checksum match
+fn verify_snapshot(file: &[u8]) -> Option<&[u8]> {
+    let rest = file.strip_prefix(SNAPSHOT_MAGIC)?;
+    if rest.len() <= 32 {
+        return None;
+    }
+    let (checksum, snapshot) = rest.split_at(32);
+    (Sha256::digest(snapshot).as_slice() == checksum).then_some(snapshot)
+}
+
+/// Delete cached snapshots (and leftover partial writes) of other builds
+fn prune_snapshots(dir: &Path, build: &str) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let prefix = format!("{}-", build);
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !name.starts_with(&prefix) && (name.ends_with(".bin") || name.ends_with(".partial")) {
+            let _ = fs::remove_file(entry.path());
+        }
+    }
+}
+
+fn create_snapshot(ops: &[OpDecl]) -> Result<Box<[u8]>, AnyError> {
+    let mut js_runtime = deno_core::JsRuntimeForSnapshot::new(deno_core::RuntimeOptions {
+        extensions: vec![runtime_extension(ops.to_vec())],
+        ..Default::default()
+    });
+    for (name, source) in BOOTSTRAP_SCRIPTS {
+        js_runtime.execute_script(*name, *source)?;
+    }
+    Ok(js_runtime.snapshot())
+}
+
+pub async fn run_js(js: &str, mut ops: Vec<OpDecl>) -> Result<(), AnyError> {
+    // Ops come from a HashMap; a snapshot needs them registered in the same order
+    ops.sort_by_key(|op| op.name);
+    let startup_snapshot = startup_snapshot(&ops);
+
     let mut js_runtime = deno_core::JsRuntime::new(deno_core::RuntimeOptions {
-        extensions: vec![ext],
+        extensions: vec![runtime_extension(ops)],
+        startup_snapshot,
         ..Default::default()
     });
 
-    // Execute timer bootstrap first to set up setTimeout/setInterval globals
-    js_runtime.execute_script("[funee:timers.js]", TIMER_BOOTSTRAP)?;
-    
-    // Execute fetch bootstrap to set up fetch/Headers/Response globals
-    js_runtime.execute_script("[funee:fetch.js]", FETCH_BOOTSTRAP)?;
-    
-    // Execute server bootstrap to set up serve() function
-    js_runtime.execute_script("[funee:server.js]", SERVER_BOOTSTRAP)?;
+    // Without a snapshot, set up the timer, fetch, server and subprocess globals now
+    if startup_snapshot.is_none() {
+        for (name, source) in BOOTSTRAP_SCRIPTS {
+            js_runtime.execute_script(*name, *source)?;
+        }
+    }
     
-    // Execute subprocess bootstrap to set up spawn() function
-    js_runtime.execute_script("[funee:subprocess.js]", SUBPROCESS_BOOTSTRAP)?;
+    if log_locations::enabled() {
+        js_runtime.execute_script("[funee:log_locations.js]", LOG_LOCATIONS_BOOTSTRAP)?;
+    }
     
     // Then execute user code
     let js_code: FastString = js.to_string().into();
@@ -1020,3 +1935,42 @@ pub async fn run_js(js: &str, ops: Vec<OpDecl>) -> Result<(), AnyError> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_snapshot() {
+        let snapshot = b"not really a snapshot";
+        let mut file = SNAPSHOT_MAGIC.to_vec();
+        file.extend_from_slice(&Sha256::digest(snapshot));
+        file.extend_from_slice(snapshot);
+        assert_eq!(verify_snapshot(&file), Some(&snapshot[..]));
+
+        // Truncated, corrupted or headerless files are rejected
+        assert_eq!(verify_snapshot(&file[..file.len() - 1]), None);
+        let mut corrupted = file.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert_eq!(verify_snapshot(&corrupted), None);
+        assert_eq!(verify_snapshot(snapshot), None);
+        assert_eq!(verify_snapshot(SNAPSHOT_MAGIC), None);
+    }
+
+    #[test]
+    fn test_bootstrap_scripts_are_valid_javascript() {
+        // The scripts live in raw strings, where an escape like "\n" in a
+        // comment becomes a real line break; let node check them if it's there
+        if Command::new("node").arg("--version").output().is_err() {
+            return;
+        }
+        let scripts = BOOTSTRAP_SCRIPTS.iter().chain([&("[funee:log_locations.js]", LOG_LOCATIONS_BOOTSTRAP)]);
+        for (name, source) in scripts {
+            let path = env::temp_dir().join(format!("funee-bootstrap-check-{}-{}.js", std::process::id(), name.trim_matches(['[', ']']).replace(':', "-")));
+            fs::write(&path, source).unwrap();
+            let output = Command::new("node").arg("--check").arg(&path).output().unwrap();
+            let _ = fs::remove_file(&path);
+            assert!(output.status.success(), "{} doesn't parse:\n{}", name, String::from_utf8_lossy(&output.stderr));
+        }
+    }
+}
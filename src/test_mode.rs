@@ -0,0 +1,235 @@
+//! `funee test`: run the tests registered by matching test files
+//!
+//! Each file runs in its own runtime. Its default export registers tests with
+//! `test(name, fn)` from "host://test"; they run once it returns and report
+//! their outcome to the registry here through ops. Per-file results and a
+//! summary are printed to stdout.
+
+use crate::diagnostics;
+use crate::watch_mode::{compile_globs, GLOB_OPTIONS};
+use deno_core::error::AnyError;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Used when `funee test` is given no patterns
+const DEFAULT_PATTERN: &str = "**/*.test.ts";
+
+/// Directories never searched for test files (besides hidden ones)
+const SKIPPED_DIRS: &[&str] = &["node_modules", "target"];
+
+/// Tests registered by the file currently running; ids are indexes
+static REGISTRY: LazyLock<Mutex<Vec<TestCase>>> = LazyLock::new(|| Mutex::new(Vec::new()));
+
+struct TestCase {
+    name: String,
+    started: Option<Instant>,
+    /// How long the test took, and its failure message if it failed
+    outcome: Option<(Duration, Option<String>)>,
+}
+
+/// Record a test registered with `test(name, fn)`; returns its id
+pub fn register(name: &str) -> u32 {
+    let mut registry = REGISTRY.lock().unwrap();
+    registry.push(TestCase {
+        name: name.to_string(),
+        started: None,
+        outcome: None,
+    });
+    (registry.len() - 1) as u32
+}
+
+/// Mark a registered test as running
+pub fn start(id: u32) {
+    if let Some(case) = REGISTRY.lock().unwrap().get_mut(id as usize) {
+        case.started = Some(Instant::now());
+    }
+}
+
+/// Record a test's outcome; `failure` is None if it passed
+pub fn finish(id: u32, failure: Option<String>) {
+    if let Some(case) = REGISTRY.lock().unwrap().get_mut(id as usize) {
+        let elapsed = case.started.map(|started| started.elapsed()).unwrap_or_default();
+        case.outcome = Some((elapsed, failure));
+    }
+}
+
+#[derive(Default)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    /// Registered but never finished, e.g. because the runtime errored first
+    not_run: usize,
+    /// Files that failed to load or threw outside a test
+    failed_files: usize,
+}
+
+impl Summary {
+    fn succeeded(&self) -> bool {
+        self.failed == 0 && self.not_run == 0 && self.failed_files == 0
+    }
+
+    fn format(&self, elapsed: Duration) -> String {
+        let total = self.passed + self.failed + self.not_run;
+        let mut out = format!(
+            "{} test{}: {} passed, {} failed",
+            total,
+            if total == 1 { "" } else { "s" },
+            self.passed,
+            self.failed
+        );
+        if self.not_run > 0 {
+            out.push_str(&format!(", {} not run", self.not_run));
+        }
+        if self.failed_files > 0 {
+            out.push_str(&format!(
+                ", {} file{} failed to run",
+                self.failed_files,
+                if self.failed_files == 1 { "" } else { "s" }
+            ));
+        }
+        out.push_str(&format!(" ({:.2}ms)", elapsed.as_secs_f64() * 1000.0));
+        out
+    }
+}
+
+/// Run every test file matching `patterns` (relative to `cwd`) with `execute`
+///
+/// Returns whether all tests passed.
+pub fn run(
+    patterns: &[String],
+    cwd: &Path,
+    mut execute: impl FnMut(&Path) -> Result<(), AnyError>,
+) -> Result<bool, AnyError> {
+    let files = discover(patterns, cwd)?;
+    if files.is_empty() {
+        diagnostics::error(format!("No test files found matching {}", display_patterns(patterns)));
+        return Ok(false);
+    }
+
+    let started = Instant::now();
+    let mut summary = Summary::default();
+    for file in &files {
+        REGISTRY.lock().unwrap().clear();
+        println!("{}", file.strip_prefix(cwd).unwrap_or(file).display());
+        // Errors have already been reported; the file still counts as failed
+        if execute(file).is_err() {
+            summary.failed_files += 1;
+        }
+
+        for case in std::mem::take(&mut *REGISTRY.lock().unwrap()) {
+            match case.outcome {
+                Some((elapsed, None)) => {
+                    summary.passed += 1;
+                    println!("  ok      {} ({:.2}ms)", case.name, elapsed.as_secs_f64() * 1000.0);
+                }
+                Some((elapsed, Some(failure))) => {
+                    summary.failed += 1;
+                    println!("  FAIL    {} ({:.2}ms)", case.name, elapsed.as_secs_f64() * 1000.0);
+                    for line in failure.lines() {
+                        println!("          {}", line);
+                    }
+                }
+                None => {
+                    summary.not_run += 1;
+                    println!("  NOT RUN {}", case.name);
+                }
+            }
+        }
+    }
+
+    println!();
+    println!("{}", summary.format(started.elapsed()));
+    Ok(summary.succeeded())
+}
+
+fn display_patterns(patterns: &[String]) -> String {
+    if patterns.is_empty() {
+        DEFAULT_PATTERN.to_string()
+    } else {
+        patterns.join(", ")
+    }
+}
+
+/// Test files matching `patterns`, sorted; a pattern naming a file is taken as-is
+fn discover(patterns: &[String], cwd: &Path) -> Result<Vec<PathBuf>, AnyError> {
+    let default = [DEFAULT_PATTERN.to_string()];
+    let patterns = if patterns.is_empty() { &default[..] } else { patterns };
+
+    let mut files = vec![];
+    let mut globs = vec![];
+    for pattern in patterns {
+        let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+        if cwd.join(pattern).is_file() {
+            files.push(cwd.join(pattern));
+        } else {
+            globs.push(pattern);
+        }
+    }
+
+    if !globs.is_empty() {
+        let globs = compile_globs(globs)?;
+        let mut found = vec![];
+        collect_files(cwd, &mut found)?;
+        for path in found {
+            let relative = path.strip_prefix(cwd).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+            if globs.iter().any(|glob| glob.matches_with(&relative, GLOB_OPTIONS)) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// All files under `dir`, skipping hidden and dependency directories
+fn collect_files(dir: &Path, found: &mut Vec<PathBuf>) -> Result<(), AnyError> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if !name.starts_with('.') && !SKIPPED_DIRS.contains(&name.as_ref()) {
+                collect_files(&path, found)?;
+            }
+        } else {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_format() {
+        let summary = Summary {
+            passed: 3,
+            failed: 1,
+            ..Default::default()
+        };
+        assert!(!summary.succeeded());
+        assert_eq!(
+            summary.format(Duration::from_millis(5)),
+            "4 tests: 3 passed, 1 failed (5.00ms)"
+        );
+
+        let summary = Summary {
+            passed: 1,
+            not_run: 1,
+            failed_files: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            summary.format(Duration::from_millis(1)),
+            "2 tests: 1 passed, 0 failed, 1 not run, 2 files failed to run (1.00ms)"
+        );
+
+        assert!(Summary { passed: 2, ..Default::default() }.succeeded());
+    }
+}
@@ -0,0 +1,118 @@
+//! Timing spans for the load + emit pipeline (`--trace`)
+//!
+//! Phases (parse, resolve, fetch, macro-expand, emit, execute) are timed with
+//! plain `Instant`s. Repeated spans of the same phase are summed, remote module
+//! fetches are also listed individually, and `report()` prints the breakdown
+//! to stderr. When tracing is off every call is a cheap no-op.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RECORDS: LazyLock<Mutex<Records>> = LazyLock::new(|| Mutex::new(Records::default()));
+
+#[derive(Default)]
+struct Records {
+    /// Phase name, total time and number of spans, in first-seen order
+    phases: Vec<(String, Duration, u32)>,
+    /// Individual remote module fetches
+    fetches: Vec<(String, Duration)>,
+}
+
+impl Records {
+    fn add_phase(&mut self, name: &str, elapsed: Duration) {
+        match self.phases.iter_mut().find(|(phase, _, _)| phase == name) {
+            Some((_, total, count)) => {
+                *total += elapsed;
+                *count += 1;
+            }
+            None => self.phases.push((name.to_string(), elapsed, 1)),
+        }
+    }
+
+    fn format(&self) -> String {
+        let width = self.phases.iter().map(|(name, _, _)| name.len()).max().unwrap_or(0);
+        let mut out = String::from("trace:\n");
+        for (name, total, count) in &self.phases {
+            out.push_str(&format!(
+                "  {:<width$}  {:>10.3}ms  ({} span{})\n",
+                name,
+                total.as_secs_f64() * 1000.0,
+                count,
+                if *count == 1 { "" } else { "s" },
+                width = width
+            ));
+        }
+        for (url, elapsed) in &self.fetches {
+            out.push_str(&format!("  fetch {}  {:.3}ms\n", url, elapsed.as_secs_f64() * 1000.0));
+        }
+        out
+    }
+}
+
+/// Turn on span recording (set from `--trace`)
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// A running span; its duration is added to `name` when dropped
+pub struct Span {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            RECORDS.lock().unwrap().add_phase(self.name, start.elapsed());
+        }
+    }
+}
+
+/// Start timing a pipeline phase
+pub fn span(name: &'static str) -> Span {
+    Span {
+        name,
+        start: enabled().then(Instant::now),
+    }
+}
+
+/// Record how long fetching one remote module took (also counted under "fetch")
+pub fn fetched(url: &str, elapsed: Duration) {
+    if enabled() {
+        let mut records = RECORDS.lock().unwrap();
+        records.add_phase("fetch", elapsed);
+        records.fetches.push((url.to_string(), elapsed));
+    }
+}
+
+/// Print the breakdown to stderr, if tracing is on
+pub fn report() {
+    if enabled() {
+        eprint!("{}", RECORDS.lock().unwrap().format());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_sum_phases_in_order() {
+        let mut records = Records::default();
+        records.add_phase("parse", Duration::from_millis(2));
+        records.add_phase("emit", Duration::from_millis(1));
+        records.add_phase("parse", Duration::from_millis(3));
+        records.fetches.push(("https://example.com/mod.ts".to_string(), Duration::from_millis(4)));
+
+        assert_eq!(
+            records.format(),
+            "trace:\n  parse       5.000ms  (2 spans)\n  emit        1.000ms  (1 span)\n  fetch https://example.com/mod.ts  4.000ms\n"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+//! `--watch`: re-run the entry whenever a file next to it changes
+//!
+//! The entry's directory is watched recursively. Changed paths matching a
+//! `--watch-ignore` glob are skipped, so a script writing generated output or
+//! logs beside its sources doesn't keep re-triggering itself.
+
+use crate::diagnostics;
+use deno_core::error::AnyError;
+use glob::Pattern;
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// Events arriving this soon after a change are folded into the same re-run
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Run `execute`, then again after every relevant change under `root`
+///
+/// Errors from a run have already been reported; watching carries on so the
+/// next save can fix them. Returns once the watcher shuts down.
+pub fn run(
+    root: &Path,
+    ignore: &[String],
+    mut execute: impl FnMut() -> Result<(), AnyError>,
+) -> Result<(), AnyError> {
+    let ignore = compile_globs(ignore.iter().map(String::as_str))?;
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = RecommendedWatcher::new(tx, Config::default())?;
+    watcher.watch(root, RecursiveMode::Recursive)?;
+    let cwd = std::env::current_dir().unwrap_or_default();
+
+    loop {
+        let _ = execute();
+        diagnostics::note(format!("Watching {} for changes...", root.display()));
+        match wait_for_change(&rx, |path| is_ignored(path, &ignore, &cwd)) {
+            Some(path) => diagnostics::note(format!("{} changed, re-running", path.display())),
+            None => return Ok(()),
+        }
+    }
+}
+
+/// Block until a path that isn't ignored changes; None if the watcher is gone
+fn wait_for_change(
+    rx: &Receiver<notify::Result<Event>>,
+    ignored: impl Fn(&Path) -> bool,
+) -> Option<PathBuf> {
+    let relevant = |event: notify::Result<Event>| -> Option<PathBuf> {
+        let event = event.ok()?;
+        if matches!(event.kind, EventKind::Access(_)) {
+            return None;
+        }
+        event.paths.into_iter().find(|path| !ignored(path))
+    };
+
+    let changed = loop {
+        if let Some(path) = relevant(rx.recv().ok()?) {
+            break path;
+        }
+    };
+    // Swallow the rest of the burst (editors often write a file several times)
+    loop {
+        match rx.recv_timeout(DEBOUNCE) {
+            Ok(_) => continue,
+            Err(RecvTimeoutError::Timeout) => return Some(changed),
+            Err(RecvTimeoutError::Disconnected) => return None,
+        }
+    }
+}
+
+/// Whether `path` matches one of the `--watch-ignore` globs
+///
+/// Patterns containing a `/` are matched against the path as given and
+/// relative to `cwd`; others only against the file name.
+fn is_ignored(path: &Path, ignore: &[Pattern], cwd: &Path) -> bool {
+    let absolute = path.to_string_lossy();
+    let relative = path.strip_prefix(cwd).unwrap_or(path).to_string_lossy();
+    let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    ignore.iter().any(|pattern| {
+        if pattern.as_str().contains('/') {
+            pattern.matches_with(&absolute, GLOB_OPTIONS) || pattern.matches_with(&relative, GLOB_OPTIONS)
+        } else {
+            pattern.matches_with(&file_name, GLOB_OPTIONS)
+        }
+    })
+}
+
+/// How `--watch-ignore` and `funee test` globs match: `*` and `?` stay
+/// within one path segment, `**` spans any number of them
+pub const GLOB_OPTIONS: glob::MatchOptions = glob::MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// Compile glob patterns, naming the one that's invalid
+pub fn compile_globs<'a>(patterns: impl IntoIterator<Item = &'a str>) -> Result<Vec<Pattern>, AnyError> {
+    patterns
+        .into_iter()
+        .map(|pattern| {
+            Pattern::new(pattern).map_err(|e| AnyError::msg(format!("Invalid glob \"{}\": {}", pattern, e)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        let matches = |pattern: &str, path: &str| Pattern::new(pattern).unwrap().matches_with(path, GLOB_OPTIONS);
+        assert!(matches("*.log", "out.log"));
+        assert!(!matches("*.log", "logs/out.log"));
+        assert!(matches("**/*.log", "out.log"));
+        assert!(matches("**/*.log", "logs/nested/out.log"));
+        assert!(matches("dist/**", "dist/a/b.js"));
+        assert!(matches("gen/?.ts", "gen/a.ts"));
+        assert!(!matches("gen/?.ts", "gen/ab.ts"));
+        assert!(!matches("src/*.ts", "src/a/b.ts"));
+        assert!(matches("src/[ab].ts", "src/b.ts"));
+
+        let cwd = Path::new("/project");
+        let ignore = compile_globs(["*.log", "dist/**"]).unwrap();
+        assert!(is_ignored(Path::new("/project/logs/run.log"), &ignore, cwd));
+        assert!(is_ignored(Path::new("/project/dist/index.js"), &ignore, cwd));
+        assert!(!is_ignored(Path::new("/project/src/index.ts"), &ignore, cwd));
+        assert!(compile_globs(["src/[.ts"]).is_err());
+    }
+}
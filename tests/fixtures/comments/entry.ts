@@ -0,0 +1,12 @@
+/**
+ * Test: --preserve-comments keeps license banners
+ * 
+ * licensed-lib.ts starts with a bang banner and a license block; both
+ * must appear in the emitted bundle when comments are preserved.
+ */
+import { log } from "funee";
+import { greet } from "./licensed-lib.ts";
+
+export default function() {
+  log(greet("licensed"));
+}
@@ -0,0 +1,15 @@
+/*! licensed-lib v1.0.0 | MIT License | (c) Example Authors */
+
+/**
+ * @license Apache-2.0
+ * Copyright Example Contributors
+ */
+
+// An ordinary comment that isn't a license
+export function greet(name: string) {
+  return `hello ${name}`;
+}
+
+export function unused() {
+  return "tree-shaken";
+}
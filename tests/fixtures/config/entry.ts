@@ -0,0 +1,14 @@
+/**
+ * Test: bare specifiers resolved through funee.json's import map
+ *
+ * tests/fixtures/config/funee.json maps "greeting" to a file and "utils/"
+ * to a directory prefix; no CLI flag is needed for either.
+ */
+import { log } from "funee";
+import { greeting } from "greeting";
+import { shout } from "utils/shout.ts";
+
+export default () => {
+  log(greeting());
+  log(shout("prefix mapping works"));
+};
@@ -0,0 +1,15 @@
+/**
+ * Test: data: URL imports
+ *
+ * Both modules live in their import specifiers: one base64-encoded
+ * TypeScript, one percent-encoded JavaScript. Nothing is read from disk
+ * or fetched for them.
+ */
+import { log } from "funee";
+import { double } from "data:text/typescript;base64,ZXhwb3J0IGNvbnN0IGRvdWJsZSA9IChuOiBudW1iZXIpOiBudW1iZXIgPT4gbiAqIDI7";
+import { greet } from "data:text/javascript,export%20const%20greet%20%3D%20%28name%29%20%3D%3E%20%60hi%20%24%7Bname%7D%60%3B";
+
+export default function() {
+  log(`double: ${double(21)}`);
+  log(greet("data url"));
+}
@@ -0,0 +1,6 @@
+import { double } from "./math.ts";
+
+export default () => ({
+  answer: double(21),
+  greeting: "hello from cjs",
+});
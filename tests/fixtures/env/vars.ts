@@ -0,0 +1,35 @@
+/**
+ * Test: host://env get/set/delete/toObject
+ */
+import { log, spawn } from "funee";
+import { delete as unset, get, set, toObject } from "host://env";
+
+export default async () => {
+  log(`from parent: ${get("FUNEE_ENV_FROM_PARENT")}`);
+  log(`empty is not unset: ${get("FUNEE_ENV_EMPTY") === ""}`);
+  log(`missing is undefined: ${get("FUNEE_ENV_MISSING") === undefined}`);
+
+  set("FUNEE_ENV_SET", "hello");
+  log(`after set: ${get("FUNEE_ENV_SET")}`);
+  log(`in toObject: ${toObject()["FUNEE_ENV_SET"]}`);
+
+  const child = await spawn("sh", ["-c", "printf %s \"$FUNEE_ENV_SET\""]);
+  log(`seen by subprocess: ${child.stdoutText()}`);
+
+  unset("FUNEE_ENV_SET");
+  log(`after delete: ${get("FUNEE_ENV_SET")}`);
+
+  unset("FUNEE_ENV_FROM_PARENT");
+  log(`inherited deleted from toObject: ${!("FUNEE_ENV_FROM_PARENT" in toObject())}`);
+  const unsetChild = await spawn("sh", ["-c", "printf %s \"${FUNEE_ENV_FROM_PARENT-unset}\""]);
+  log(`inherited deleted for subprocess: ${unsetChild.stdoutText()}`);
+
+  try {
+    set("BAD=NAME", "x");
+    log("invalid name error: none");
+  } catch (e) {
+    log(`invalid name error: ${(e as Error).message.startsWith("env set failed:")}`);
+  }
+
+  log("env test complete");
+};
@@ -0,0 +1,5 @@
+import { value } from "data:text/typescript;base64,not base64!";
+
+export default function() {
+  return value;
+}
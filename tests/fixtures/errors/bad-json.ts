@@ -0,0 +1,5 @@
+import config from "./invalid.json";
+
+export default function() {
+  return config;
+}
@@ -0,0 +1,5 @@
+import { notExported } from "./reexports.ts";
+
+export default function() {
+  notExported();
+}
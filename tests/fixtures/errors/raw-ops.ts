@@ -0,0 +1,11 @@
+/**
+ * Test: reaching into Deno.core from a user module
+ * 
+ * Warned about by default, rejected under --no-raw-ops.
+ */
+import { log } from "funee";
+
+export default function() {
+  const result = JSON.parse(Deno.core.ops.op_fsReadFile("errors/utils.ts"));
+  log(`raw read: ${result.type}`);
+}
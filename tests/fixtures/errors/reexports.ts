@@ -0,0 +1 @@
+export { exists, notExported } from "./utils.ts";
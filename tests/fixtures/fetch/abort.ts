@@ -0,0 +1,56 @@
+/**
+ * Test fixture: cancelling fetch() with an AbortSignal
+ *
+ * Expected behavior:
+ * - controller.abort() rejects an in-flight fetch with an AbortError
+ * - A signal aborted before the call rejects right away
+ * - abort(reason) rejects with that reason
+ * - AbortSignal.timeout(ms) rejects with a TimeoutError
+ * - An unaborted signal leaves the fetch alone
+ * - A pending AbortSignal.timeout() doesn't keep the run alive
+ *
+ * Uses local test server for fast, reliable testing (/endless never ends).
+ */
+import { log } from "funee";
+
+const errorName = async (promise: Promise<unknown>) => {
+  try {
+    await promise;
+    return "no error";
+  } catch (e: any) {
+    return e instanceof Error ? e.name : String(e);
+  }
+};
+
+export default async () => {
+  const controller = new AbortController();
+  let eventFired = false;
+  controller.signal.addEventListener("abort", () => { eventFired = true; });
+  const start = Date.now();
+  setTimeout(() => controller.abort(), 100);
+  const inFlight = await errorName(
+    fetch("http://localhost:19998/endless", { signal: controller.signal }).then((res) => res.text()),
+  );
+  log(`in-flight abort: ${inFlight}`);
+  log(`rejected promptly: ${Date.now() - start < 2000}`);
+  log(`abort event fired: ${eventFired && controller.signal.aborted}`);
+
+  log(`pre-aborted: ${await errorName(fetch("http://localhost:19998/text", { signal: AbortSignal.abort() }))}`);
+
+  const withReason = new AbortController();
+  withReason.abort("stop now");
+  log(`custom reason: ${await errorName(fetch("http://localhost:19998/text", { signal: withReason.signal }))}`);
+
+  const timedOut = await errorName(
+    fetch("http://localhost:19998/endless", { signal: AbortSignal.timeout(100) }).then((res) => res.text()),
+  );
+  log(`signal timeout: ${timedOut}`);
+
+  const unaborted = await fetch("http://localhost:19998/text", { signal: new AbortController().signal });
+  log(`unaborted body: ${await unaborted.text()}`);
+
+  // Never fires: the run ends once the log below is written
+  AbortSignal.timeout(60_000);
+
+  log("abort test complete");
+};
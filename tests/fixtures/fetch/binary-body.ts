@@ -0,0 +1,30 @@
+/**
+ * Test fixture: op_fetch with binary response bodies
+ *
+ * Expected behavior:
+ * - With the binary flag, bytes that aren't valid UTF-8 arrive intact via
+ *   bodyBase64, and parseFetchResult().arrayBuffer() decodes them
+ * - Without it, the text body is returned as before
+ */
+import { log, serve, _fetch, parseFetchResult } from "funee";
+
+const BYTES = [0x89, 0x50, 0x4e, 0x47, 0x00, 0xff, 0xfe, 0x80];
+
+export default async () => {
+  const server = serve({ port: 0 }, () => new Response(new Uint8Array(BYTES)));
+  const url = `http://127.0.0.1:${server.port}/`;
+  const fetchRaw = (binary: boolean) =>
+    _fetch("GET", url, "{}", "", "", true, "", "", "", false, 0, 0, binary, "", 0);
+
+  const binary = parseFetchResult(await fetchRaw(true));
+  const bytes = Array.from(new Uint8Array(binary.arrayBuffer()));
+  log(`binary has body: ${binary.body !== undefined}`);
+  log(`binary bytes intact: ${bytes.join(",") === BYTES.join(",")}`);
+
+  const text = parseFetchResult(await fetchRaw(false));
+  log(`text has bodyBase64: ${text.bodyBase64 !== undefined}`);
+  log(`text body lossy: ${text.body!.includes("�")}`);
+
+  await server.shutdown();
+  log("binary-body test complete");
+};
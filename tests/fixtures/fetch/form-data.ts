@@ -0,0 +1,50 @@
+/**
+ * Test fixture: multipart/form-data uploads
+ *
+ * Expected behavior:
+ * - A FormData body is sent as multipart/form-data with a boundary in
+ *   Content-Type, overriding any Content-Type the caller set
+ * - Text fields and file parts (bytes and Blob-like values) arrive with their
+ *   names, filenames and content types
+ * - FormData supports get/getAll/has/set/delete
+ *
+ * Uses local test server route /post, which echoes the body back as `data`.
+ */
+import { log } from "funee";
+
+export default async () => {
+  const form = new FormData();
+  form.append("title", "holiday photos");
+  form.append("tag", "beach");
+  form.append("tag", "sunset");
+  form.append("notes", new TextEncoder().encode("line one\nline two"), "notes.txt");
+  form.append("report", {
+    name: "report.csv",
+    type: "text/csv",
+    arrayBuffer: async () => new TextEncoder().encode("a,b\n1,2").buffer,
+  });
+
+  log(`getAll tag: ${form.getAll("tag").join("|")}`);
+  form.set("scratch", "x");
+  form.delete("scratch");
+  log(`has scratch: ${form.has("scratch")}`);
+
+  const response = await fetch("http://localhost:19998/post", {
+    method: "POST",
+    headers: { "Content-Type": "text/plain" },
+    body: form,
+  });
+  const echoed = await response.json();
+  const contentType: string = echoed.headers["content-type"];
+  const boundary = contentType.split("boundary=")[1];
+  const data: string = echoed.data;
+
+  log(`multipart content-type: ${contentType.startsWith("multipart/form-data; boundary=")}`);
+  log(`body uses boundary: ${data.includes(`--${boundary}`) && data.trimEnd().endsWith(`--${boundary}--`)}`);
+  log(`text field: ${data.includes('name="title"\r\n\r\nholiday photos')}`);
+  log(`repeated field: ${data.split('name="tag"').length - 1}`);
+  log(`bytes part: ${data.includes('name="notes"; filename="notes.txt"') && data.includes("line one\nline two")}`);
+  log(`blob part: ${data.includes('filename="report.csv"\r\nContent-Type: text/csv\r\n\r\na,b\n1,2')}`);
+
+  log("form-data test complete");
+};
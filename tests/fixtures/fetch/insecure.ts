@@ -0,0 +1,35 @@
+/**
+ * Test fixture: skipping TLS verification for a self-signed server
+ * 
+ * Expected behavior:
+ * - A plain fetch to a self-signed server fails certificate verification
+ * - tls.insecureSkipVerify is a TypeError unless funee runs with
+ *   --allow-insecure-fetch, and succeeds (with a warning) when it does
+ * 
+ * The test generates the certificate into fetch/.insecure and runs an HTTPS
+ * server on port 19995.
+ */
+import { log } from "funee";
+
+const url = "https://localhost:19995/hello";
+
+export default async () => {
+  let verifiedFails = false;
+  try {
+    const res = await fetch(url);
+    await res.text();
+  } catch (e) {
+    verifiedFails = true;
+  }
+  log(`verified fetch fails: ${verifiedFails}`);
+  
+  try {
+    const response = await fetch(url, { tls: { insecureSkipVerify: true } });
+    log(`insecure status: ${response.status}`);
+    log(`insecure body: ${await response.text()}`);
+  } catch (e) {
+    log(`insecure rejected: ${e instanceof TypeError}`);
+  }
+  
+  log("insecure test complete");
+};
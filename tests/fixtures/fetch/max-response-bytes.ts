@@ -0,0 +1,49 @@
+/**
+ * Test fixture: op_fetch and fetch() with a response size cap
+ *
+ * Expected behavior:
+ * - A body within the cap is returned as usual
+ * - A body streamed past the cap fails with a clear error as soon as the cap
+ *   is exceeded (/endless never finishes on its own, so buffering it all
+ *   would hang)
+ * - A cap of 0 means unlimited
+ * - fetch(url, { maxResponseBytes }) applies the cap when the body is read
+ *
+ * Uses local test server routes /text and /endless.
+ */
+import { log, _fetch } from "funee";
+
+const fetchCapped = (url: string, maxResponseBytes: number) =>
+  _fetch("GET", url, "{}", "", "", true, "", "", "", false, maxResponseBytes, 0, false, "", 0);
+
+export default async () => {
+  const small = JSON.parse(await fetchCapped("http://localhost:19998/text", 1024));
+  log(`within cap body: ${small.body}`);
+
+  const unlimited = JSON.parse(await fetchCapped("http://localhost:19998/text", 0));
+  log(`unlimited body: ${unlimited.body}`);
+
+  const started = Date.now();
+  try {
+    await fetchCapped("http://localhost:19998/endless", 64 * 1024);
+    log("over cap: no error");
+  } catch (e) {
+    log(`over cap error: ${(e as Error).message}`);
+  }
+  log(`aborted early: ${Date.now() - started < 5000}`);
+
+  const capped = await fetch("http://localhost:19998/text", { maxResponseBytes: 1024 });
+  log(`fetch within cap body: ${await capped.text()}`);
+
+  const fetchStarted = Date.now();
+  try {
+    const endless = await fetch("http://localhost:19998/endless", { maxResponseBytes: 64 * 1024 });
+    await endless.bytes();
+    log("fetch over cap: no error");
+  } catch (e) {
+    log(`fetch over cap error: ${(e as Error).message}`);
+  }
+  log(`fetch aborted early: ${Date.now() - fetchStarted < 5000}`);
+
+  log("max-response-bytes test complete");
+};
@@ -0,0 +1,52 @@
+/**
+ * Test fixture: fetch with a client certificate (mTLS)
+ * 
+ * Expected behavior:
+ * - A server that requires a client certificate accepts the request when
+ *   `tls.cert`/`tls.key` are given, trusting its private CA via `tls.ca`
+ * - The same request without a client certificate fails
+ * - A certificate without its key is rejected with a TypeError
+ * 
+ * The test generates the certificates into fetch/.mtls and runs an HTTPS
+ * server on port 19996.
+ */
+import { log } from "funee";
+import { readFile } from "host://fs";
+
+const pem = (name: string) => JSON.parse(readFile(`fetch/.mtls/${name}`)).value;
+const url = "https://localhost:19996/whoami";
+
+export default async () => {
+  const ca = pem("ca.pem");
+  
+  const response = await fetch(url, {
+    tls: { cert: pem("client.pem"), key: pem("client.key"), ca },
+  });
+  log(`with cert status: ${response.status}`);
+  log(`with cert body: ${await response.text()}`);
+  
+  // Same material again reuses the cached client
+  const again = await fetch(url, {
+    tls: { cert: pem("client.pem"), key: pem("client.key"), ca },
+  });
+  log(`second request status: ${again.status}`);
+  
+  let withoutCertFailed = false;
+  try {
+    const res = await fetch(url, { tls: { ca } });
+    await res.text();
+  } catch (e) {
+    withoutCertFailed = true;
+  }
+  log(`without cert fails: ${withoutCertFailed}`);
+  
+  let missingKeyRejected = false;
+  try {
+    await fetch(url, { tls: { cert: pem("client.pem"), ca } });
+  } catch (e) {
+    missingKeyRejected = e instanceof TypeError;
+  }
+  log(`missing key rejected: ${missingKeyRejected}`);
+  
+  log("mtls test complete");
+};
@@ -0,0 +1,33 @@
+/**
+ * Test fixture: fetch through an HTTP proxy
+ *
+ * Run with HTTP_PROXY=http://localhost:19998 and NO_PROXY=localhost; the
+ * local test server echoes absolute-form (proxied) requests.
+ *
+ * Expected behavior:
+ * - A host not in NO_PROXY goes through HTTP_PROXY
+ * - A host in NO_PROXY is fetched directly
+ * - The proxy option routes a request through the given proxy
+ * - A malformed proxy URL rejects with "Invalid proxy URL"
+ */
+import { log } from "funee";
+
+export default async () => {
+  const viaEnv = await fetch("http://proxied.invalid/env");
+  log(`env proxy: ${await viaEnv.text()}`);
+
+  const direct = await fetch("http://localhost:19998/text");
+  log(`no_proxy host: ${await direct.text()}`);
+
+  const viaOption = await fetch("http://proxied.invalid/explicit", { proxy: "http://127.0.0.1:19998" });
+  log(`explicit proxy: ${await viaOption.text()}`);
+
+  try {
+    await fetch("http://proxied.invalid/bad", { proxy: "http://[not a url" });
+    log("bad proxy error: false");
+  } catch (e: any) {
+    log(`bad proxy error: ${String(e.message).includes("Invalid proxy URL")}`);
+  }
+
+  log("proxy test complete");
+};
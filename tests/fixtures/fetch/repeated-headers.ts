@@ -0,0 +1,31 @@
+/**
+ * Test fixture: fetch sends a header name given more than once
+ *
+ * Expected behavior:
+ * - [name, value] pairs with the same name each reach the server
+ * - Object headers are still sent as before
+ */
+import { log, serve } from "funee";
+
+export default async () => {
+  let received: string[] = [];
+  const server = serve({ port: 0 }, (req) => {
+    received = [...req.headers.entries()]
+      .filter(([name]) => name.startsWith("x-"))
+      .map(([name, value]) => `${name}=${value}`);
+    return new Response("ok");
+  });
+
+  await fetch(`http://127.0.0.1:${server.port}/`, {
+    headers: [["X-Custom", "one"], ["X-Custom", "two"]],
+  });
+  log(`pairs: ${received.join(" ")}`);
+
+  await fetch(`http://127.0.0.1:${server.port}/`, {
+    headers: { "X-Single": "only" },
+  });
+  log(`object: ${received.join(" ")}`);
+
+  await server.shutdown();
+  log("repeated-headers test complete");
+};
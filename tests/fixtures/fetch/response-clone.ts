@@ -0,0 +1,50 @@
+/**
+ * Test fixture: Response.clone() on a fetch response
+ * 
+ * Expected behavior:
+ * - A clone can read the body independently of the original
+ * - The original and the clone can use different body readers
+ * - Reading a body twice on the same Response still fails
+ * - Cloning a consumed Response fails
+ * 
+ * Uses local test server which returns a "slideshow" JSON structure.
+ */
+import { log } from "funee";
+
+export default async () => {
+  const response = await fetch("http://localhost:19998/json");
+  const copy = response.clone();
+  
+  const text = await response.text();
+  const data = await copy.json();
+  
+  log(`original text has slideshow: ${text.includes("slideshow")}`);
+  log(`clone json has slideshow: ${data.slideshow.title === "Sample Slide Show"}`);
+  log(`same body: ${JSON.stringify(JSON.parse(text)) === JSON.stringify(data)}`);
+  
+  // A clone of a clone, read after the others are done
+  const second = await fetch("http://localhost:19998/text");
+  const secondCopy = second.clone();
+  const thirdCopy = secondCopy.clone();
+  log(`first read: ${await second.text()}`);
+  log(`second read: ${await secondCopy.text()}`);
+  log(`third read: ${await thirdCopy.text()}`);
+  
+  let rereadFailed = false;
+  try {
+    await response.text();
+  } catch (e) {
+    rereadFailed = e instanceof TypeError;
+  }
+  log(`reread fails: ${rereadFailed}`);
+  
+  let cloneFailed = false;
+  try {
+    response.clone();
+  } catch (e) {
+    cloneFailed = e instanceof TypeError;
+  }
+  log(`clone after read fails: ${cloneFailed}`);
+  
+  log("response-clone test complete");
+};
@@ -0,0 +1,49 @@
+/**
+ * Test fixture: fetch response bodies are released without being read
+ * 
+ * Expected behavior:
+ * - body.cancel() releases an unread body
+ * - Aborting the request's signal releases its body
+ * - A response dropped unread is released once it's garbage collected
+ * - Reading after cancel() fails
+ * 
+ * Run with --v8-flags=--expose-gc. Uses the local test server.
+ */
+import { log } from "funee";
+
+const openResponses = (): number => Deno.core.ops.op_fetchOpenCount();
+
+export default async () => {
+  const cancelled = await fetch("http://localhost:19998/text");
+  await cancelled.body!.cancel();
+  log(`open after cancel: ${openResponses()}`);
+  
+  let readFailed = false;
+  try {
+    await cancelled.text();
+  } catch (e) {
+    readFailed = e instanceof TypeError;
+  }
+  log(`read after cancel fails: ${readFailed}`);
+  
+  const controller = new AbortController();
+  const aborted = await fetch("http://localhost:19998/text", { signal: controller.signal });
+  controller.abort();
+  log(`open after abort: ${openResponses()}`);
+  log(`aborted body used: ${aborted.bodyUsed}`);
+  
+  // Dropped unread, along with an unread clone
+  await (async () => {
+    const dropped = await fetch("http://localhost:19998/json");
+    dropped.clone();
+  })();
+  log(`open before gc: ${openResponses()}`);
+  
+  for (let i = 0; i < 20 && openResponses() > 0; i++) {
+    (globalThis as any).gc();
+    await new Promise((resolve) => setTimeout(resolve, 10));
+  }
+  log(`open after gc: ${openResponses()}`);
+  
+  log("response-release test complete");
+};
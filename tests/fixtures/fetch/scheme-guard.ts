@@ -0,0 +1,34 @@
+/**
+ * Test fixture: fetch URL scheme restrictions
+ * 
+ * Expected behavior:
+ * - file:// and data: URLs are rejected before any request is made
+ * - The error names the scheme and the --allow-fetch-schemes escape hatch
+ * - http:// URLs are still allowed
+ * 
+ * Uses local test server which returns "Hello, World!" at /text.
+ */
+import { log } from "funee";
+
+const rejection = async (url: string) => {
+  try {
+    await fetch(url);
+    return "none";
+  } catch (e) {
+    return `${e}`;
+  }
+};
+
+export default async () => {
+  const fileError = await rejection("file:///etc/passwd");
+  log(`file rejected: ${fileError.includes("scheme 'file' is not in")}`);
+  log(`error mentions flag: ${fileError.includes("--allow-fetch-schemes")}`);
+  
+  const dataError = await rejection("data:text/plain,hello");
+  log(`data rejected: ${dataError.includes("scheme 'data' is not in")}`);
+  
+  const response = await fetch("http://localhost:19998/text");
+  log(`http allowed: ${(await response.text()) === "Hello, World!"}`);
+  
+  log("scheme-guard test complete");
+};
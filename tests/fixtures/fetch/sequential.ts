@@ -0,0 +1,35 @@
+/**
+ * Test fixture: many sequential fetches
+ *
+ * Expected behavior:
+ * - Every request succeeds while reusing the shared client's connections
+ * - Redirect-following and non-following fetches each get the right behavior
+ */
+import { log, serve } from "funee";
+
+const COUNT = 50;
+
+export default async () => {
+  const server = serve({ port: 0 }, (req) => {
+    if (new URL(req.url).pathname === "/redirect") {
+      return new Response(null, { status: 302, headers: { location: "/" } });
+    }
+    return new Response("ok");
+  });
+  const url = `http://127.0.0.1:${server.port}`;
+
+  let ok = 0;
+  for (let i = 0; i < COUNT; i++) {
+    const res = await fetch(`${url}/`);
+    if ((await res.text()) === "ok") ok++;
+  }
+  log(`sequential ok: ${ok}/${COUNT}`);
+
+  const followed = await fetch(`${url}/redirect`);
+  log(`followed status: ${followed.status}`);
+  const manual = await fetch(`${url}/redirect`, { redirect: "manual" });
+  log(`manual status: ${manual.status}`);
+
+  await server.shutdown();
+  log("sequential test complete");
+};
@@ -0,0 +1,27 @@
+/**
+ * Test fixture: repeated Set-Cookie response headers
+ *
+ * Expected behavior:
+ * - Headers.getSetCookie() returns every Set-Cookie value, unmerged
+ * - entries() yields one pair per Set-Cookie value
+ * - A cloned response keeps them separate
+ *
+ * Uses local test server for fast, reliable testing.
+ */
+import { log } from "funee";
+
+export default async () => {
+  const response = await fetch("http://localhost:19998/set-cookie");
+  const cookies = response.headers.getSetCookie();
+  log(`cookie count: ${cookies.length}`);
+  log(`first cookie: ${cookies[0]}`);
+  log(`second cookie: ${cookies[1]}`);
+
+  const entries = [...response.headers.entries()].filter(([name]) => name === "set-cookie");
+  log(`set-cookie entries: ${entries.length}`);
+
+  log(`clone cookie count: ${response.clone().headers.getSetCookie().length}`);
+
+  await response.text();
+  log("set-cookie test complete");
+};
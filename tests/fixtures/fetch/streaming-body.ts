@@ -0,0 +1,44 @@
+/**
+ * Test fixture: POST request with a streamed body
+ * 
+ * Expected behavior:
+ * - An async generator body is uploaded chunk by chunk (chunked transfer encoding)
+ * - String and byte chunks are reassembled in order by the server
+ * - A generator that throws rejects the fetch with its error
+ * 
+ * Uses local test server route /post, which echoes the body back as `data`.
+ */
+import { log } from "funee";
+
+async function* chunks() {
+  yield "first,";
+  await new Promise((resolve) => setTimeout(resolve, 10));
+  yield new TextEncoder().encode("second,");
+  await new Promise((resolve) => setTimeout(resolve, 10));
+  yield "third";
+}
+
+async function* failing() {
+  yield "partial";
+  throw new Error("producer failed");
+}
+
+export default async () => {
+  const response = await fetch("http://localhost:19998/post", {
+    method: "POST",
+    body: chunks(),
+  });
+  const echoed = await response.json();
+  log(`status: ${response.status}`);
+  log(`reassembled body: ${echoed.data}`);
+  log(`transfer-encoding: ${echoed.headers["transfer-encoding"]}`);
+
+  try {
+    await fetch("http://localhost:19998/post", { method: "POST", body: failing() });
+    log("failing producer: no error");
+  } catch (e) {
+    log(`failing producer error: ${(e as Error).message}`);
+  }
+
+  log("streaming-body test complete");
+};
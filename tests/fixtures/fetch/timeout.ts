@@ -0,0 +1,42 @@
+/**
+ * Test fixture: fetch and op_fetch with a timeout
+ *
+ * Expected behavior:
+ * - fetch() with { timeout } rejects with an error starting "timeout:" when
+ *   the server doesn't answer in time
+ * - A fast response within the timeout is unaffected
+ * - op_fetch times out while reading a body that never ends (/endless)
+ */
+import { log, serve, _fetch } from "funee";
+
+export default async () => {
+  const server = serve({ port: 0 }, async (req) => {
+    if (new URL(req.url).pathname === "/slow") {
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+    }
+    return new Response("done");
+  });
+  const base = `http://127.0.0.1:${server.port}`;
+
+  const started = Date.now();
+  try {
+    await fetch(`${base}/slow`, { timeout: 100 });
+    log("slow: no error");
+  } catch (e) {
+    log(`slow timeout error: ${(e as Error).message.startsWith("timeout:")}`);
+  }
+  log(`gave up early: ${Date.now() - started < 900}`);
+
+  const fast = await fetch(`${base}/fast`, { timeout: 5000 });
+  log(`fast body: ${await fast.text()}`);
+
+  try {
+    await _fetch("GET", "http://localhost:19998/endless", "{}", "", "", true, "", "", "", false, 0, 300, false, "", 0);
+    log("endless: no error");
+  } catch (e) {
+    log(`endless timeout error: ${(e as Error).message.startsWith("timeout:")}`);
+  }
+
+  await server.shutdown();
+  log("timeout test complete");
+};
@@ -0,0 +1,49 @@
+/**
+ * Test fixture: response.toFile() with download progress
+ *
+ * Expected behavior:
+ * - The body (8 x 32 KiB, sent 20ms apart) is streamed into a file
+ * - Progress reports increase monotonically and end at the Content-Length
+ * - Without a Content-Length, totalBytes is null but the download still completes,
+ *   also when a clone already read the body
+ *
+ * Uses local test server routes /download and /download-chunked.
+ */
+import { log } from "funee";
+import { lstat, readFile, tmpdir } from "host://fs";
+
+const TOTAL = 8 * 32 * 1024;
+
+export default async () => {
+  const path = `${tmpdir()}/funee-to-file-progress.bin`;
+
+  const reports: { bytesDownloaded: number; totalBytes: number | null }[] = [];
+  const response = await fetch("http://localhost:19998/download");
+  const written = await response.toFile(path, {
+    progressIntervalMs: 1,
+    onProgress: (progress) => reports.push(progress),
+  });
+
+  log(`bytes written: ${written}`);
+  log(`file size matches: ${JSON.parse(lstat(path)).value.size === TOTAL}`);
+  log(`body used: ${response.bodyUsed}`);
+  log(`got several reports: ${reports.length > 1}`);
+  log(`reports increase: ${reports.every((r, i) => i === 0 || r.bytesDownloaded > reports[i - 1].bytesDownloaded)}`);
+  log(`total known: ${reports.every((r) => r.totalBytes === TOTAL)}`);
+  log(`last report at total: ${reports[reports.length - 1]?.bytesDownloaded === TOTAL}`);
+
+  const chunkedReports: { bytesDownloaded: number; totalBytes: number | null }[] = [];
+  const chunked = await fetch("http://localhost:19998/download-chunked");
+  await chunked.toFile(path, { onProgress: (progress) => chunkedReports.push(progress) });
+  log(`chunked total unknown: ${chunkedReports.every((r) => r.totalBytes === null)}`);
+  log(`chunked last report complete: ${chunkedReports[chunkedReports.length - 1]?.bytesDownloaded === TOTAL}`);
+  log(`chunked file complete: ${JSON.parse(readFile(path)).value.length === TOTAL}`);
+
+  const bufferedReports: { bytesDownloaded: number; totalBytes: number | null }[] = [];
+  const buffered = await fetch("http://localhost:19998/download-chunked");
+  await buffered.clone().bytes();
+  await buffered.toFile(path, { onProgress: (progress) => bufferedReports.push(progress) });
+  log(`buffered total unknown: ${bufferedReports.length > 0 && bufferedReports.every((r) => r.totalBytes === null)}`);
+
+  log("to-file-progress test complete");
+};
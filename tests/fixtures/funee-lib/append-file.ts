@@ -0,0 +1,33 @@
+/**
+ * Test: appendFile creates a missing file and appends to an existing one
+ */
+import {
+  log,
+  readFile,
+  readFileBinary,
+  appendFile,
+  appendFileBinary,
+  appendFileRaw,
+  tmpdir,
+  someString,
+  FilePathString,
+} from "funee";
+
+export default function() {
+  const dir = `${tmpdir()}/funee-append-${someString(8)}`;
+  const textPath = `${dir}.log` as FilePathString;
+  const binPath = `${dir}.bin` as FilePathString;
+
+  appendFile(textPath, "first\n");
+  appendFile(textPath, "second\n");
+  log(`text appended: ${JSON.stringify(readFile(textPath))}`);
+
+  appendFileBinary(binPath, new Uint8Array([1, 2]));
+  appendFileBinary(binPath, new Uint8Array([3]));
+  log(`binary appended: ${Array.from(readFileBinary(binPath)).join(",")}`);
+
+  const missingDir = appendFileRaw(`${dir}/missing/file.log` as FilePathString, "x");
+  log(`missing directory error: ${missingDir.type === "error" && missingDir.error.startsWith("appendFile failed:")}`);
+
+  log("appendFile test complete");
+}
@@ -0,0 +1,23 @@
+/**
+ * Test: chmod sets permission bits, read back through stat's mode
+ */
+import { chmod, chmodRaw, log, stat, writeFile } from "funee";
+import { mkdir, tmpdir } from "host://fs";
+
+export default () => {
+  const dir = `${tmpdir()}/funee-chmod-test-${Date.now()}`;
+  mkdir(dir, true);
+  const path = `${dir}/secret.key`;
+  writeFile(path, "key material");
+
+  chmod(path, 0o600);
+  log(`mode after chmod: ${stat(path).mode?.toString(8)}`);
+
+  chmod(path, 0o755);
+  log(`mode after second chmod: ${stat(path).mode?.toString(8)}`);
+
+  const missing = chmodRaw(`${dir}/missing`, 0o600);
+  log(`missing path error: ${missing.type === "error" && missing.error.startsWith("chmod failed:")}`);
+
+  log("chmod test complete");
+};
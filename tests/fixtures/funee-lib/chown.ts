@@ -0,0 +1,26 @@
+/**
+ * Test: stat reports uid/gid, and chown to the current owner succeeds
+ * (which needs no privileges)
+ */
+import { chown, chownRaw, log, stat, writeFile } from "funee";
+import { mkdir, tmpdir } from "host://fs";
+
+export default () => {
+  const dir = `${tmpdir()}/funee-chown-test-${Date.now()}`;
+  mkdir(dir, true);
+  const path = `${dir}/owned.txt`;
+  writeFile(path, "mine");
+
+  const { uid, gid } = stat(path);
+  log(`uid: ${uid}`);
+  log(`gid: ${gid}`);
+
+  chown(path, uid!, gid!);
+  const after = stat(path);
+  log(`unchanged owner: ${after.uid === uid && after.gid === gid}`);
+
+  const missing = chownRaw(`${dir}/missing`, uid!, gid!);
+  log(`missing path error: ${missing.type === "error" && missing.error.startsWith("chown failed:")}`);
+
+  log("chown test complete");
+};
@@ -0,0 +1,53 @@
+/**
+ * Test: gzip() and gunzip() from host://compress
+ * 
+ * Verifies that:
+ * 1. A few hundred KB of text round-trips unchanged
+ * 2. Repetitive text actually shrinks, and level 0 doesn't
+ * 3. Out-of-range levels are clamped instead of rejected
+ * 4. Corrupt input throws a TypeError
+ * 5. Output past maxBytes throws a RangeError
+ */
+import { log } from "funee";
+import { gzip, gunzip } from "host://compress";
+
+export default function() {
+  const lines: string[] = [];
+  for (let i = 0; i < 8000; i++) {
+    lines.push(`line ${i}: the quick brown fox jumps over the lazy dog`);
+  }
+  const text = lines.join("\n");
+  // ASCII only, so each character is one byte
+  const bytes = Uint8Array.from(text, (char) => char.charCodeAt(0));
+  
+  const compressed = gzip(text);
+  const restored = gunzip(compressed);
+  const roundTrip = restored.length === bytes.length && restored.every((byte, i) => byte === bytes[i]);
+  log(`input bytes: ${bytes.length > 300000 ? "pass" : "fail"}`);
+  log(`round trip: ${roundTrip ? "pass" : "fail"}`);
+  log(`compressed: ${compressed.length < bytes.length / 4 ? "pass" : "fail"}`);
+  log(`gzip magic: ${compressed[0] === 0x1f && compressed[1] === 0x8b ? "pass" : "fail"}`);
+  
+  log(`level 0: ${gzip(bytes, 0).length > bytes.length ? "pass" : "fail"}`);
+  const clamped = gunzip(gzip(bytes, 42));
+  log(`level clamped: ${clamped.length === bytes.length && gunzip(gzip(bytes, -3)).length === bytes.length ? "pass" : "fail"}`);
+  
+  let corrupt = false;
+  try {
+    gunzip(new Uint8Array([0x1f, 0x8b, 1, 2, 3, 4, 5]));
+  } catch (e) {
+    corrupt = e instanceof TypeError;
+  }
+  log(`corrupt input rejected: ${corrupt ? "pass" : "fail"}`);
+  
+  log(`within maxBytes: ${gunzip(compressed, { maxBytes: bytes.length }).length === bytes.length ? "pass" : "fail"}`);
+  let capped = false;
+  try {
+    gunzip(compressed, { maxBytes: 1024 });
+  } catch (e) {
+    capped = e instanceof RangeError && (e as Error).message.includes("maximum of 1024 bytes");
+  }
+  log(`maxBytes enforced: ${capped ? "pass" : "fail"}`);
+  
+  log("compress-gzip test complete");
+}
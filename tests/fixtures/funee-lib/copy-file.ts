@@ -0,0 +1,31 @@
+/**
+ * Test: copyFile copies contents without going through JavaScript
+ *
+ * Expects /tmp/funee-copy-file-test/source.txt to exist (created by the test).
+ */
+import {
+  log,
+  readFile,
+  copyFile,
+  copyFileRaw,
+  join,
+  FilePathString,
+} from "funee";
+
+export default function() {
+  const testDir = "/tmp/funee-copy-file-test";
+  const source = join(testDir, "source.txt") as FilePathString;
+  const dest = join(testDir, "dest.txt") as FilePathString;
+
+  const bytes = copyFile(source, dest);
+  log(`bytes copied: ${bytes}`);
+  log(`contents match: ${readFile(dest) === readFile(source) ? "pass" : "fail"}`);
+
+  const missingSource = copyFileRaw(join(testDir, "nope.txt") as FilePathString, dest);
+  log(`missing source: ${missingSource.type === "error" ? missingSource.error : "no error"}`);
+
+  const missingDir = copyFileRaw(source, join(testDir, "missing", "dest.txt") as FilePathString);
+  log(`missing dest dir: ${missingDir.type === "error" ? missingDir.error : "no error"}`);
+
+  log("copyFile test complete");
+}
@@ -0,0 +1,32 @@
+/**
+ * Test: digest() from host://crypto
+ * 
+ * Verifies that:
+ * 1. sha256, sha512, sha1 and md5 match their standard "abc" test vectors
+ * 2. Binary input (including a NUL byte) and empty input hash correctly
+ * 3. Algorithm names are case-insensitive
+ * 4. Unknown algorithms throw a TypeError
+ */
+import { log } from "funee";
+import { digest } from "host://crypto";
+
+export default function() {
+  log(`sha256: ${digest("sha256", "abc") === "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad" ? "pass" : "fail"}`);
+  log(`sha512: ${digest("sha512", "abc") === "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f" ? "pass" : "fail"}`);
+  log(`sha1: ${digest("sha1", "abc") === "a9993e364706816aba3e25717850c26c9cd0d89d" ? "pass" : "fail"}`);
+  log(`md5: ${digest("md5", "abc") === "900150983cd24fb0d6963f7d28e17f72" ? "pass" : "fail"}`);
+  
+  log(`binary input: ${digest("sha256", new Uint8Array([0])) === "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d" ? "pass" : "fail"}`);
+  log(`empty input: ${digest("md5", new Uint8Array(0)) === "d41d8cd98f00b204e9800998ecf8427e" ? "pass" : "fail"}`);
+  log(`case-insensitive: ${digest("SHA-256", "abc") === digest("sha256", "abc") ? "pass" : "fail"}`);
+  
+  let unknown = false;
+  try {
+    digest("whirlpool", "abc");
+  } catch (e) {
+    unknown = e instanceof TypeError;
+  }
+  log(`unknown algorithm rejected: ${unknown ? "pass" : "fail"}`);
+  
+  log("crypto-digest test complete");
+}
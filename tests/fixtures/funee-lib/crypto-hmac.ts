@@ -0,0 +1,37 @@
+/**
+ * Test: hmac() and timingSafeEqual() from host://crypto
+ * 
+ * Verifies that:
+ * 1. hmac matches the RFC 4231 (SHA-256/512) and RFC 2202 (SHA-1) vectors
+ * 2. Byte keys work as well as string keys
+ * 3. Unknown algorithms throw a TypeError
+ * 4. timingSafeEqual accepts equal MACs and rejects different or shorter ones
+ */
+import { log } from "funee";
+import { hmac, timingSafeEqual } from "host://crypto";
+
+export default function() {
+  const data = "what do ya want for nothing?";
+  log(`sha256: ${hmac("sha256", "Jefe", data) === "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843" ? "pass" : "fail"}`);
+  log(`sha512: ${hmac("sha512", "Jefe", data) === "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737" ? "pass" : "fail"}`);
+  log(`sha1: ${hmac("SHA1", "Jefe", data) === "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79" ? "pass" : "fail"}`);
+  
+  const key = new Uint8Array(20).fill(0x0b);
+  log(`byte key: ${hmac("sha256", key, "Hi There") === "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7" ? "pass" : "fail"}`);
+  
+  let unknown = false;
+  try {
+    hmac("md5", "key", "data");
+  } catch (e) {
+    unknown = e instanceof TypeError;
+  }
+  log(`unknown algorithm rejected: ${unknown ? "pass" : "fail"}`);
+  
+  const mac = hmac("sha256", "secret", "payload");
+  log(`equal: ${timingSafeEqual(mac, hmac("sha256", "secret", "payload")) ? "pass" : "fail"}`);
+  log(`different: ${!timingSafeEqual(mac, hmac("sha256", "other", "payload")) ? "pass" : "fail"}`);
+  log(`shorter: ${!timingSafeEqual(mac, mac.slice(0, 32)) ? "pass" : "fail"}`);
+  log(`case-insensitive hex: ${timingSafeEqual(mac, mac.toUpperCase()) ? "pass" : "fail"}`);
+  
+  log("crypto-hmac test complete");
+}
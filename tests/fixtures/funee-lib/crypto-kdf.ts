@@ -0,0 +1,38 @@
+/**
+ * Test: PBKDF2 and Argon2 from host://crypto
+ * 
+ * Verifies that:
+ * 1. pbkdf2 matches the RFC 6070 (SHA-1) and RFC 7914 (SHA-256) test vectors
+ * 2. Byte and string salts give the same key
+ * 3. argon2Hash produces a PHC string that argon2Verify accepts
+ * 4. argon2Verify rejects a wrong password
+ */
+import { log } from "funee";
+import { pbkdf2, argon2Hash, argon2Verify } from "host://crypto";
+
+const hex = (bytes: Uint8Array) =>
+  Array.from(bytes).map((b) => b.toString(16).padStart(2, "0")).join("");
+
+export default function() {
+  log(`sha1 c=1: ${hex(pbkdf2("password", "salt", 1, 20, "sha1")) === "0c60c80f961f0e71f3a9b524af6012062fe037a6" ? "pass" : "fail"}`);
+  log(`sha1 c=4096: ${hex(pbkdf2("password", "salt", 4096, 20, "sha1")) === "4b007901b765489abead49d926f721d065a429c1" ? "pass" : "fail"}`);
+  
+  const sha256 = hex(pbkdf2("passwd", new Uint8Array([0x73, 0x61, 0x6c, 0x74]), 1, 64));
+  log(`sha256 default digest: ${sha256 === "55ac046e56e3089fec1691c22544b605f94185216dde0465e68b9d57c20dacbc49ca9cccf179b645991664b39d77ef317c71b845b1e30bd509112041d3a19783" ? "pass" : "fail"}`);
+  
+  let badDigest = false;
+  try {
+    pbkdf2("password", "salt", 1, 20, "md5" as any);
+  } catch (e) {
+    badDigest = e instanceof TypeError;
+  }
+  log(`unknown digest rejected: ${badDigest ? "pass" : "fail"}`);
+  
+  const stored = argon2Hash("correct horse battery staple");
+  log(`argon2 phc format: ${stored.startsWith("$argon2id$") ? "pass" : "fail"}`);
+  log(`argon2 verify: ${argon2Verify("correct horse battery staple", stored) ? "pass" : "fail"}`);
+  log(`argon2 wrong password: ${!argon2Verify("wrong password", stored) ? "pass" : "fail"}`);
+  log(`argon2 salted: ${argon2Hash("correct horse battery staple") !== stored ? "pass" : "fail"}`);
+  
+  log("crypto-kdf test complete");
+}
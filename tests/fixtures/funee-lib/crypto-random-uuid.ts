@@ -0,0 +1,20 @@
+/**
+ * Test: randomUUID() from host://crypto
+ * 
+ * Verifies that:
+ * 1. UUIDs have the 8-4-4-4-12 lowercase hex layout
+ * 2. The version nibble is 4 and the variant bits are 10xx
+ * 3. Successive UUIDs differ
+ */
+import { log } from "funee";
+import { randomUUID } from "host://crypto";
+
+export default function() {
+  const uuids = Array.from({ length: 100 }, () => randomUUID());
+  const pattern = /^[0-9a-f]{8}-[0-9a-f]{4}-4[0-9a-f]{3}-[89ab][0-9a-f]{3}-[0-9a-f]{12}$/;
+  
+  log(`format: ${uuids.every((uuid) => pattern.test(uuid)) ? "pass" : "fail"}`);
+  log(`unique: ${new Set(uuids).size === uuids.length ? "pass" : "fail"}`);
+  
+  log("crypto-random-uuid test complete");
+}
@@ -0,0 +1,29 @@
+/**
+ * Test: glob expands patterns against the working directory, skips dotfiles
+ * unless asked, and rejects invalid patterns
+ */
+import { glob, globRaw, log, writeFile } from "funee";
+import { mkdir, tmpdir } from "host://fs";
+
+export default () => {
+  const root = `${tmpdir()}/funee-glob-test-${Date.now()}`;
+  mkdir(`${root}/src/nested`, true);
+  mkdir(`${root}/src/.cache`, true);
+  writeFile(`${root}/src/a.ts`, "");
+  writeFile(`${root}/src/b.js`, "");
+  writeFile(`${root}/src/nested/c.ts`, "");
+  writeFile(`${root}/src/.hidden.ts`, "");
+  writeFile(`${root}/src/.cache/d.ts`, "");
+
+  const strip = (paths: string[]) => paths.map((path) => path.slice(root.length + 1)).sort().join(" ");
+  log(`recursive: ${strip(glob(`${root}/src/**/*.ts`))}`);
+  log(`with hidden: ${strip(glob(`${root}/src/**/*.ts`, true))}`);
+
+  // Relative patterns resolve against the working directory (tests/fixtures)
+  log(`relative: ${glob("funee-lib/glob*.ts").join(" ")}`);
+
+  const invalid = globRaw(`${root}/***`);
+  log(`invalid pattern error: ${invalid.type === "error" && invalid.error.startsWith("glob failed:")}`);
+
+  log("glob test complete");
+};
@@ -0,0 +1,46 @@
+/**
+ * Test: host://watch watch() async iteration
+ * 
+ * Verifies that:
+ * 1. watch() yields events for real directory changes
+ * 2. ignore patterns filter events out
+ * 3. Breaking out of for-await stops the watcher
+ * 4. close() ends a pending iteration
+ */
+import { log, writeFile, tmpdir, someString } from "funee";
+import { mkdir } from "host://fs";
+import { watch } from "host://watch";
+
+
+export default async () => {
+  const testDir = `${tmpdir()}/funee-host-watch-${someString(8)}`;
+  mkdir(testDir);
+
+  const watcher = watch(testDir, { ignore: ["ignored"], debounce: 50 });
+
+  setTimeout(() => {
+    writeFile(`${testDir}/ignored.txt`, "skip me");
+    writeFile(`${testDir}/hello.txt`, "hello");
+  }, 100);
+
+  let sawIgnored = false;
+  for await (const event of watcher) {
+    if (event.path.includes("ignored")) sawIgnored = true;
+    if (event.path.endsWith("hello.txt")) {
+      log(`received event: ${event.kind}`);
+      break;
+    }
+  }
+  log(`ignored filtered: ${!sawIgnored}`);
+
+  // A second watcher closed while its iteration is waiting
+  const idle = watch(testDir);
+  setTimeout(() => idle.close(), 100);
+  let count = 0;
+  for await (const _event of idle) {
+    count++;
+  }
+  log(`close ended iteration: ${count === 0}`);
+
+  log("host-watch-iterate test complete");
+};
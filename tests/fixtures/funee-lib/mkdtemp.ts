@@ -0,0 +1,19 @@
+/**
+ * Test: mkdtemp creates distinct, existing directories under the temp dir
+ */
+import { log, mkdtemp, mkdtempRaw } from "funee";
+import { exists, isFile, tmpdir } from "host://fs";
+
+export default () => {
+  const paths = Array.from({ length: 50 }, () => mkdtemp("funee-mkdtemp-test-"));
+
+  log(`all exist: ${paths.every((path) => exists(path) && !isFile(path))}`);
+  log(`all unique: ${new Set(paths).size === paths.length}`);
+  log(`in temp dir: ${paths.every((path) => path.startsWith(tmpdir()))}`);
+  log(`has prefix: ${paths.every((path) => /funee-mkdtemp-test-[0-9a-f]{12}$/.test(path))}`);
+
+  const bad = mkdtempRaw("../escape-");
+  log(`separator rejected: ${bad.type === "error" && bad.error.includes("must not contain path separators")}`);
+
+  log("mkdtemp test complete");
+};
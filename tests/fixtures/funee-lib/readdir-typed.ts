@@ -0,0 +1,23 @@
+/**
+ * Test: readdirTyped reports files, directories and symlinks without following links
+ */
+import { log, readdirTyped, readdirTypedRaw, spawn, writeFile } from "funee";
+import { mkdir, tmpdir } from "host://fs";
+
+export default async () => {
+  const dir = `${tmpdir()}/funee-readdir-typed-test-${Date.now()}`;
+  mkdir(`${dir}/sub`, true);
+  writeFile(`${dir}/file.txt`, "typed");
+  await spawn("ln", ["-s", `${dir}/sub`, `${dir}/link`]);
+
+  const entries = readdirTyped(dir);
+  entries.sort((a, b) => a.name.localeCompare(b.name));
+  for (const entry of entries) {
+    log(`${entry.name}: file=${entry.is_file} dir=${entry.is_directory} symlink=${entry.is_symlink}`);
+  }
+
+  const missing = readdirTypedRaw(`${dir}/missing`);
+  log(`missing dir error: ${missing.type === "error" && missing.error.startsWith("readdirTyped failed:")}`);
+
+  log("readdirTyped test complete");
+};
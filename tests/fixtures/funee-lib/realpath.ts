@@ -0,0 +1,22 @@
+/**
+ * Test: realpath resolves relative segments and symlinks, and fails on missing paths
+ */
+import { log, realpath, realpathRaw, spawn, writeFile } from "funee";
+import { mkdir, tmpdir } from "host://fs";
+
+export default async () => {
+  // The temp dir itself may sit behind a symlink (e.g. /var on macOS)
+  const root = `${realpath(tmpdir())}/funee-realpath-test-${Date.now()}`;
+  mkdir(`${root}/sub`, true);
+  writeFile(`${root}/target.txt`, "real");
+  await spawn("ln", ["-s", `${root}/target.txt`, `${root}/link.txt`]);
+
+  log(`dot segments: ${realpath(`${root}/sub/../target.txt`) === `${root}/target.txt`}`);
+  log(`symlink resolved: ${realpath(`${root}/link.txt`) === `${root}/target.txt`}`);
+  log(`relative is absolute: ${realpath(".").startsWith("/")}`);
+
+  const missing = realpathRaw(`${root}/missing.txt`);
+  log(`missing path error: ${missing.type === "error" && missing.error.startsWith("realpath failed:")}`);
+
+  log("realpath test complete");
+};
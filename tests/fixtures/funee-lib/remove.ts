@@ -0,0 +1,31 @@
+/**
+ * Test: fs.remove deletes files, empty directories and (recursively) trees
+ */
+import { log } from "funee";
+import { exists, mkdir, remove, tmpdir, writeFile } from "host://fs";
+
+export default function() {
+  const dir = `${tmpdir()}/funee-remove-test-${Date.now()}`;
+  mkdir(`${dir}/empty`, true);
+  mkdir(`${dir}/tree/nested`, true);
+  writeFile(`${dir}/file.txt`, "bye");
+  writeFile(`${dir}/tree/nested/deep.txt`, "bye");
+
+  const file = JSON.parse(remove(`${dir}/file.txt`));
+  log(`file removed: ${file.type === "ok" && !exists(`${dir}/file.txt`)}`);
+
+  const empty = JSON.parse(remove(`${dir}/empty`));
+  log(`empty dir removed: ${empty.type === "ok" && !exists(`${dir}/empty`)}`);
+
+  const nonRecursive = JSON.parse(remove(`${dir}/tree`));
+  log(`non-empty without recursive: ${nonRecursive.type === "error" ? nonRecursive.error : "removed"}`);
+  log(`tree kept: ${exists(`${dir}/tree/nested/deep.txt`)}`);
+
+  const recursive = JSON.parse(remove(dir, true));
+  log(`tree removed: ${recursive.type === "ok" && !exists(dir)}`);
+
+  const missing = JSON.parse(remove(dir));
+  log(`missing path error: ${missing.type === "error"}`);
+
+  log("remove test complete");
+}
@@ -0,0 +1,23 @@
+/**
+ * Test: fs.rename moves a file within a temp directory
+ */
+import { log } from "funee";
+import { exists, mkdir, readFile, rename, tmpdir, writeFile } from "host://fs";
+
+export default function() {
+  const dir = `${tmpdir()}/funee-rename-test-${Date.now()}`;
+  mkdir(dir, true);
+  const from = `${dir}/old.txt`;
+  const to = `${dir}/new.txt`;
+  writeFile(from, "moved");
+
+  const result = JSON.parse(rename(from, to));
+  log(`rename ok: ${result.type === "ok"}`);
+  log(`old path exists: ${exists(from)}`);
+  log(`new path content: ${JSON.parse(readFile(to)).value}`);
+
+  const missing = JSON.parse(rename(`${dir}/nope.txt`, to));
+  log(`missing source error: ${missing.type === "error" && missing.error.startsWith("rename failed:")}`);
+
+  log("rename test complete");
+}
@@ -0,0 +1,26 @@
+/**
+ * Test: stat follows symlinks while lstat reports the link itself
+ */
+import { log, lstat, spawn, stat, statRaw, writeFile } from "funee";
+import { mkdir, tmpdir } from "host://fs";
+
+export default async () => {
+  const dir = `${tmpdir()}/funee-stat-test-${Date.now()}`;
+  mkdir(dir, true);
+  writeFile(`${dir}/target.txt`, "hello stat");
+  await spawn("ln", ["-s", `${dir}/target.txt`, `${dir}/link.txt`]);
+  await spawn("ln", ["-s", `${dir}/missing.txt`, `${dir}/dangling.txt`]);
+
+  const followed = stat(`${dir}/link.txt`);
+  log(`stat size: ${followed.size === 10 ? "pass" : followed.size}`);
+  log(`stat is_file: ${followed.is_file}`);
+  log(`stat is_symlink: ${followed.is_symlink}`);
+
+  const link = lstat(`${dir}/link.txt`);
+  log(`lstat is_symlink: ${link.is_symlink}`);
+
+  const dangling = statRaw(`${dir}/dangling.txt`);
+  log(`dangling link error: ${dangling.type === "error"}`);
+
+  log("stat test complete");
+};
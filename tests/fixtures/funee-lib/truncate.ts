@@ -0,0 +1,29 @@
+/**
+ * Test: truncate shrinks and zero-extends files, and never creates them
+ */
+import { log, readFile, readFileBinary, stat, truncate, truncateRaw, writeFile } from "funee";
+import { exists, mkdir, tmpdir } from "host://fs";
+
+export default () => {
+  const dir = `${tmpdir()}/funee-truncate-test-${Date.now()}`;
+  mkdir(dir, true);
+  const path = `${dir}/data.txt`;
+  writeFile(path, "hello world");
+
+  truncate(path, 5);
+  log(`after shrink: ${readFile(path)}`);
+
+  truncate(path, 8);
+  const bytes = readFileBinary(path);
+  log(`after grow size: ${stat(path).size}`);
+  log(`zero filled: ${Array.from(bytes.slice(5)).every((b) => b === 0)}`);
+
+  truncate(path);
+  log(`after default: ${stat(path).size}`);
+
+  const missing = truncateRaw(`${dir}/missing.txt`, 3);
+  log(`missing path error: ${missing.type === "error" && missing.error.startsWith("truncate failed:")}`);
+  log(`missing path created: ${exists(`${dir}/missing.txt`)}`);
+
+  log("truncate test complete");
+};
@@ -0,0 +1,28 @@
+/**
+ * Test: utimes sets access/modification times and rejects non-positive times
+ */
+import { log, stat, utimes, utimesRaw, writeFile } from "funee";
+import { mkdir, tmpdir } from "host://fs";
+
+export default () => {
+  const dir = `${tmpdir()}/funee-utimes-test-${Date.now()}`;
+  mkdir(dir, true);
+  const path = `${dir}/data.txt`;
+  writeFile(path, "hello");
+
+  utimes(path, 1500000000000, 1600000000123);
+  const stats = stat(path);
+  log(`modified: ${stats.modified_ms}`);
+  log(`accessed: ${stats.accessed_ms}`);
+
+  const zero = utimesRaw(path, 0, 1600000000000);
+  log(`zero time error: ${zero.type === "error" && zero.error.startsWith("utimes failed:")}`);
+
+  const negative = utimesRaw(path, 1500000000000, -1);
+  log(`negative time error: ${negative.type === "error" && negative.error.startsWith("utimes failed:")}`);
+
+  const missing = utimesRaw(`${dir}/missing.txt`, 1500000000000, 1600000000000);
+  log(`missing path error: ${missing.type === "error" && missing.error.startsWith("utimes failed:")}`);
+
+  log("utimes test complete");
+};
@@ -0,0 +1,27 @@
+/**
+ * Test: walk lists a tree relative to its root, honors maxDepth and doesn't follow symlinks
+ */
+import { log, spawn, walk, walkRaw, writeFile } from "funee";
+import { mkdir, tmpdir } from "host://fs";
+
+export default async () => {
+  const root = `${tmpdir()}/funee-walk-test-${Date.now()}`;
+  mkdir(`${root}/a/b`, true);
+  writeFile(`${root}/top.txt`, "top");
+  writeFile(`${root}/a/mid.txt`, "mid");
+  writeFile(`${root}/a/b/deep.txt`, "deep");
+  // A link back to the root would loop forever if followed
+  await spawn("ln", ["-s", root, `${root}/a/loop`]);
+
+  const describe = (entry: { path: string; is_file: boolean; is_directory: boolean }) =>
+    `${entry.path}${entry.is_directory ? "/" : ""}${!entry.is_file && !entry.is_directory ? "@" : ""}`;
+
+  log(`all: ${walk(root).map(describe).join(" ")}`);
+  log(`depth 1: ${walk(root, 1).map(describe).join(" ")}`);
+  log(`depth 2: ${walk(root, 2).map(describe).join(" ")}`);
+
+  const missing = walkRaw(`${root}/missing`);
+  log(`missing root error: ${missing.type === "error" && missing.error.startsWith("walk failed:")}`);
+
+  log("walk test complete");
+};
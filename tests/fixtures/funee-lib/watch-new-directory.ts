@@ -0,0 +1,40 @@
+/**
+ * Test: recursive watching picks up directories created after the watch started
+ * 
+ * Creates a subdirectory and then a file inside it; both create events must be
+ * reported, and stopping the watcher ends the iteration.
+ */
+import { log, writeFile, tmpdir, someString } from "funee";
+import { mkdir } from "host://fs";
+import { watch } from "host://watch";
+
+export default async () => {
+  const testDir = `${tmpdir()}/funee-watch-new-dir-${someString(8)}`;
+  const subDir = `${testDir}/created-later`;
+  const nestedFile = `${subDir}/nested.txt`;
+  mkdir(testDir);
+
+  const watcher = watch(testDir, { recursive: true });
+
+  setTimeout(() => {
+    mkdir(subDir);
+    // Give the watcher a moment to pick up the new directory
+    setTimeout(() => writeFile(nestedFile, "nested"), 100);
+  }, 100);
+
+  // Fail instead of hanging if the nested file is never reported
+  const timeout = setTimeout(() => watcher.close(), 5000);
+
+  let sawDir = false;
+  let sawFile = false;
+  for await (const event of watcher) {
+    if (event.kind === "create" && event.path.endsWith("/created-later")) sawDir = true;
+    if (event.path.endsWith("/created-later/nested.txt")) sawFile = true;
+    if (sawDir && sawFile) break;
+  }
+  clearTimeout(timeout);
+
+  log(`new directory reported: ${sawDir}`);
+  log(`nested file reported: ${sawFile}`);
+  log("watch-new-directory test complete");
+};
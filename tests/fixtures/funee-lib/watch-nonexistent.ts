@@ -0,0 +1,41 @@
+/**
+ * Test: watching a file that doesn't exist yet
+ * 
+ * The watcher starts in pending mode, ignores a sibling file, and reports a
+ * create event for exactly the watched path once it is written.
+ */
+import { log, writeFile, tmpdir, someString } from "funee";
+import { mkdir } from "host://fs";
+import { watch } from "host://watch";
+
+export default async () => {
+  const testDir = `${tmpdir()}/funee-watch-missing-${someString(8)}`;
+  const target = `${testDir}/later.txt`;
+  mkdir(testDir);
+
+  const watcher = watch(target);
+  log(`pending: ${watcher.pending}`);
+
+  setTimeout(() => {
+    writeFile(`${testDir}/sibling.txt`, "not watched");
+    setTimeout(() => writeFile(target, "created"), 100);
+  }, 100);
+
+  // Fail instead of hanging if the create is never reported
+  const timeout = setTimeout(() => watcher.close(), 5000);
+
+  const paths = new Set<string>();
+  let created = false;
+  for await (const event of watcher) {
+    paths.add(event.path);
+    if (event.kind === "create" && event.path === target) {
+      created = true;
+      break;
+    }
+  }
+  clearTimeout(timeout);
+
+  log(`create reported for target: ${created}`);
+  log(`only target reported: ${paths.size === 1 && paths.has(target)}`);
+  log("watch-nonexistent test complete");
+};
@@ -0,0 +1,47 @@
+/**
+ * Test: watchPoll cursor and dropped count
+ * 
+ * Verifies that:
+ * 1. Events carry increasing sequence numbers
+ * 2. The cursor advances between polls
+ * 3. Overflowing a small queue reports dropped events
+ * 4. A caught-up consumer sees no drops
+ */
+import { log, writeFile, tmpdir, someString } from "funee";
+import { mkdir } from "host://fs";
+import { watchStart, watchPoll, watchStop } from "host://watch";
+
+const sleep = (ms: number) => new Promise((r) => setTimeout(r, ms));
+
+export default async () => {
+  const testDir = `${tmpdir()}/funee-watch-overflow-${someString(8)}`;
+  mkdir(testDir);
+  
+  const watcherId = JSON.parse(watchStart(testDir, false, 8)).value;
+  
+  // Flood the watcher with more events than the queue holds
+  for (let i = 0; i < 50; i++) {
+    writeFile(`${testDir}/file-${i}.txt`, `content ${i}`);
+  }
+  await sleep(500);
+  
+  const first = JSON.parse(watchPoll(watcherId, 0));
+  log(`events capped: ${first.events.length <= 8}`);
+  log(`dropped after overflow: ${first.dropped > 0}`);
+  log(`cursor advanced: ${first.nextCursor > 0}`);
+  const seqs = first.events.map((e: { seq: number }) => e.seq);
+  log(`seqs increasing: ${seqs.every((s: number, i: number) => i === 0 || s > seqs[i - 1])}`);
+  log(`last seq before cursor: ${seqs.length === 0 || seqs[seqs.length - 1] < first.nextCursor}`);
+  
+  // Keep up with the next change
+  writeFile(`${testDir}/one-more.txt`, "more");
+  await sleep(500);
+  
+  const second = JSON.parse(watchPoll(watcherId, first.nextCursor));
+  log(`no drops when caught up: ${second.dropped === 0}`);
+  log(`cursor advanced again: ${second.nextCursor > first.nextCursor}`);
+  log(`new events after cursor: ${second.events.every((e: { seq: number }) => e.seq >= first.nextCursor)}`);
+  
+  watchStop(watcherId);
+  log("watch-overflow test complete");
+};
@@ -0,0 +1,45 @@
+/**
+ * Test: writeFiles is all-or-nothing
+ *
+ * Writes three files where the third targets a directory that doesn't exist;
+ * none of the targets (nor any temp files) may be left behind.
+ * Then writes a valid batch and reads it back.
+ */
+import {
+  log,
+  readFile,
+  readdir,
+  writeFiles,
+  writeFilesRaw,
+  isFile,
+  join,
+  FilePathString,
+  FolderPathString,
+} from "funee";
+
+export default function() {
+  const testDir = "/tmp/funee-write-files-test";
+  const a = join(testDir, "a.txt") as FilePathString;
+  const b = join(testDir, "b.txt") as FilePathString;
+  const invalid = join(testDir, "missing", "c.txt") as FilePathString;
+
+  const result = writeFilesRaw([
+    { path: a, content: "first" },
+    { path: b, content: "second" },
+    { path: invalid, content: "third" },
+  ]);
+  log(`invalid batch failed: ${result.type === "error" ? "pass" : "fail"}`);
+  if (result.type === "error") {
+    log(`error names failing file: ${result.error.includes(invalid) ? "pass" : "fail"}`);
+  }
+  log(`no targets created: ${!isFile(a) && !isFile(b) && !isFile(invalid) ? "pass" : "fail"}`);
+  log(`no temp files left: ${readdir(testDir as FolderPathString).length === 0 ? "pass" : "fail"}`);
+
+  writeFiles([
+    { path: a, content: "first" },
+    { path: b, content: new Uint8Array([0x68, 0x69]) },
+  ]);
+  log(`valid batch written: ${readFile(a) === "first" && readFile(b) === "hi" ? "pass" : "fail"}`);
+
+  log("writeFiles test complete");
+}
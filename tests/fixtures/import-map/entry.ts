@@ -0,0 +1,16 @@
+/**
+ * Test: bare specifiers resolved through a standalone import map
+ *
+ * tests/fixtures/import-map/import_map.json is passed with --import-map (or
+ * FUNEE_IMPORT_MAP); it maps "greeting" to a file and "text/" to a
+ * directory prefix. There's no funee.json here, so without the map neither
+ * specifier resolves.
+ */
+import { log } from "funee";
+import { greeting } from "greeting";
+import { shout } from "text/shout.ts";
+
+export default () => {
+  log(greeting());
+  log(shout("prefix mapping works"));
+};
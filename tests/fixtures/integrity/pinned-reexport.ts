@@ -0,0 +1,2 @@
+// Re-exports pinned.ts under a hash that doesn't match it
+export { pinned } from "./pinned.ts" with { integrity: "sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=" };
@@ -0,0 +1 @@
+export const pinned = () => "pinned module loaded";
@@ -0,0 +1,11 @@
+/**
+ * Test: a re-export pinned with an integrity attribute that doesn't match
+ * 
+ * `export { .. } from` checks its attribute like an import does.
+ */
+import { log } from "funee";
+import { pinned } from "./pinned-reexport.ts";
+
+export default function() {
+  log(pinned());
+}
@@ -0,0 +1,9 @@
+/**
+ * Test: an `export *` pinned with an integrity attribute that doesn't match
+ */
+import { log } from "funee";
+import { pinned } from "./pinned-star-reexport.ts";
+
+export default function() {
+  log(pinned());
+}
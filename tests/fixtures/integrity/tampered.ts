@@ -0,0 +1,12 @@
+/**
+ * Test: import pinned with an integrity attribute that doesn't match
+ * 
+ * Stands in for a module whose contents changed after it was pinned;
+ * bundling must stop before anything runs.
+ */
+import { log } from "funee";
+import { pinned } from "./pinned.ts" with { integrity: "sha256-47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU=" };
+
+export default function() {
+  log(pinned());
+}
@@ -0,0 +1,11 @@
+/**
+ * Test: import pinned with a matching integrity attribute
+ * 
+ * The hash is the sha256 of pinned.ts, so the import loads.
+ */
+import { log } from "funee";
+import { pinned } from "./pinned.ts" with { integrity: "sha256-ylbvOpEnnmcQo8FALenc7ZXaOc0VVsDEllB7S11i010=" };
+
+export default function() {
+  log(pinned());
+}
@@ -0,0 +1,29 @@
+/**
+ * Test fixture: invokeBatch runs several safelisted op calls at once
+ */
+import { log, invokeBatch } from "funee";
+
+export default () => {
+  const results = JSON.parse(invokeBatch(JSON.stringify([
+    { op: "tmpdir", args: [] },
+    { op: "randomBytes", args: [8] },
+    { op: "randomBytes", args: [4] },
+    { op: "fsWriteFile", args: ["/tmp/nope", "x"] },
+    { op: "randomBytes", args: ["lots"] },
+    { op: "randomBytes", args: [65537] },
+  ])));
+
+  log(`result count: ${results.length}`);
+  log(`tmpdir ok: ${results[0].type === "ok" && results[0].value.length > 0}`);
+  log(`randomBytes lengths: ${results[1].value.length},${results[2].value.length}`);
+  log(`randomBytes hex: ${/^[0-9a-f]+$/.test(results[1].value)}`);
+  log(`unlisted op: ${results[3].error}`);
+  log(`bad argument: ${results[4].error}`);
+  log(`too many bytes: ${results[5].error}`);
+
+  try {
+    invokeBatch("not json");
+  } catch (e) {
+    log(`invalid batch: ${(e as Error).message.startsWith("Invalid batch JSON")}`);
+  }
+};
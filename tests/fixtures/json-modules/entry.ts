@@ -0,0 +1,21 @@
+/**
+ * Test: JSON module imports
+ *
+ * config.json is detected by its extension; defaults.data only loads as
+ * JSON because of its `type` attribute. Named imports of top-level keys are
+ * the same values as the default export's properties.
+ */
+import { log } from "funee";
+import config, { name, limits } from "./config.json";
+import defaults from "./defaults.data" with { type: "json" };
+import answer from "data:application/json,42" with { type: "json" };
+
+export default function() {
+  log(`name: ${name} v${config.version}`);
+  log(`features: ${config.features.join(",")}`);
+  log(`retries: ${limits.retries}, timeout: ${config.limits.timeout}`);
+  log(`dash-key: ${config["dash-key"]}`);
+  log(`same limits: ${limits === config.limits}`);
+  log(`defaults: ${defaults.greeting}`);
+  log(`answer: ${answer}`);
+}
@@ -0,0 +1,14 @@
+/**
+ * Test fixture: --log-locations prefixes console output with file:line
+ */
+import { log } from "host://console";
+
+const helper = () => {
+  log("from helper");
+};
+
+export default () => {
+  log("from entry");
+  helper();
+  console.log("from console");
+};
@@ -0,0 +1,34 @@
+import { log } from "funee";
+import { monotonicNanos, setTimeout } from "host://time";
+
+/**
+ * Test fixture: monotonicNanos() from host://time
+ * 
+ * Expected behavior:
+ * - Returns a bigint of nanoseconds since the runtime started
+ * - Never goes backwards across many back-to-back reads
+ * - Advances by about the length of a sleep
+ */
+export default async function() {
+  const first = monotonicNanos();
+  log(`is bigint: ${typeof first === "bigint"}`);
+  log(`non-negative: ${first >= 0n}`);
+  
+  let previous = first;
+  let monotonic = true;
+  for (let i = 0; i < 10000; i++) {
+    const now = monotonicNanos();
+    if (now < previous) {
+      monotonic = false;
+    }
+    previous = now;
+  }
+  log(`monotonic: ${monotonic}`);
+  
+  const before = monotonicNanos();
+  await new Promise((resolve) => setTimeout(resolve, 50));
+  const sleptMs = Number(monotonicNanos() - before) / 1e6;
+  log(`advanced by the sleep: ${sleptMs >= 45 && sleptMs < 5000}`);
+  
+  log("monotonic-clock end");
+}
@@ -0,0 +1,14 @@
+/**
+ * Test: a namespace import used as a value
+ *
+ * Passing the namespace around needs the whole namespace object.
+ */
+import { log } from "funee";
+import * as utils from "./utils.ts";
+
+const describe = (namespace: Record<string, unknown>) => Object.keys(namespace).join(",");
+
+export default function() {
+  log(`members: ${describe(utils)}`);
+  log(`add: ${utils.add(1, 1)}`);
+}
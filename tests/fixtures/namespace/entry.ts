@@ -0,0 +1,13 @@
+/**
+ * Test: namespace imports
+ *
+ * utils.add and utils.greet become references to those exports, so
+ * neverCalled is left out of the bundle.
+ */
+import { log } from "funee";
+import * as utils from "./utils.ts";
+
+export default function() {
+  log(`add: ${utils.add(2, 3)}`);
+  log(utils.greet("namespace"));
+}
@@ -0,0 +1,9 @@
+export function add(a: number, b: number) {
+  return a + b;
+}
+
+export const greet = (name: string) => `hello, ${name}`;
+
+export function neverCalled() {
+  return "namespace member that should be dropped";
+}
@@ -0,0 +1,22 @@
+/**
+ * Test: host://os platform, arch, hostname, homedir and cpus
+ */
+import { log } from "funee";
+import { arch, cpus, homedir, hostname, networkInterfaces, platform } from "host://os";
+
+export default () => {
+  log(`platform: ${platform()}`);
+  log(`arch: ${arch()}`);
+  log(`hostname non-empty: ${hostname().length > 0}`);
+  log(`homedir non-empty: ${homedir().length > 0}`);
+  log(`cpus positive: ${Number.isInteger(cpus()) && cpus() > 0}`);
+
+  const interfaces = networkInterfaces();
+  const loopback = interfaces.find((i) => i.address === "127.0.0.1");
+  log(`loopback listed: ${loopback !== undefined}`);
+  log(`loopback internal: ${loopback?.internal}`);
+  log(`loopback family: ${loopback?.family}`);
+  log(`families valid: ${interfaces.every((i) => i.family === "IPv4" || i.family === "IPv6")}`);
+
+  log("os info test complete");
+};
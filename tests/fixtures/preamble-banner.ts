@@ -0,0 +1,13 @@
+/**
+ * Test: runtime preamble banner
+ * 
+ * Imports two host modules so the emitted bundle has a runtime preamble,
+ * then checks that user code follows the preamble's end banner unchanged.
+ */
+import { log } from "funee";
+import { exists } from "host://fs";
+import { randomBytes } from "host://crypto";
+
+export default function() {
+  log("preamble user code: " + typeof exists + " " + typeof randomBytes);
+}
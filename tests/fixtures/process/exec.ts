@@ -0,0 +1,44 @@
+/**
+ * Test: Run a command to completion with exec()
+ * 
+ * Verifies that:
+ * 1. exec(command, args) captures stdout and the exit status
+ * 2. stdin is written while output is drained, so 1MB through cat can't deadlock
+ * 3. Large stdout and stderr together are both captured in full
+ * 4. cwd, env and non-zero exit codes work as with spawn()
+ */
+import { exec, log } from "funee";
+
+export default async () => {
+  const echo = await exec("echo", ["hello exec"]);
+  log(`echo: "${echo.stdoutText().trim()}" success: ${echo.status.success}`);
+  
+  // More than a pipe buffer each way
+  const input = "x".repeat(1024 * 1024);
+  const cat = await exec({ cmd: ["cat"], stdin: input });
+  log(`cat round trip: ${cat.stdoutText() === input}`);
+  
+  const both = await exec({
+    cmd: ["sh", "-c", "head -c 1048576 /dev/zero; head -c 1048576 /dev/zero >&2"],
+  });
+  log(`stdout bytes: ${both.stdout.length}`);
+  log(`stderr bytes: ${both.stderr.length}`);
+  
+  const cwd = await exec({ cmd: ["pwd"], cwd: "/tmp" });
+  log(`cwd: ${cwd.stdoutText().trim()}`);
+  
+  const env = await exec({ cmd: ["sh", "-c", "echo $EXEC_TEST_VAR"], env: { EXEC_TEST_VAR: "from env" } });
+  log(`env: ${env.stdoutText().trim()}`);
+  
+  const failed = await exec({ cmd: ["sh", "-c", "echo oops >&2; exit 3"] });
+  log(`failed: code ${failed.status.code} success ${failed.status.success} stderr "${failed.stderrText().trim()}"`);
+  
+  try {
+    await exec("definitely-not-a-real-command-xyz");
+    log("missing command: no error");
+  } catch (e: any) {
+    log(`missing command: ${e.message.includes("Failed to spawn process")}`);
+  }
+  
+  log("exec: pass");
+}
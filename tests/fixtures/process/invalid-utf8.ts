@@ -0,0 +1,50 @@
+/**
+ * Test: Invalid UTF-8 from a subprocess
+ *
+ * Verifies that:
+ * 1. Text reads replace invalid bytes with U+FFFD by default
+ * 2. { strict: true } throws instead, naming the offending byte offset
+ * 3. output().stdoutText() decodes the same way as the host-side reads
+ */
+import { spawn, log } from "funee";
+
+// "ok " then a lone 0xFF byte, then a truncated 3-byte sequence
+const CMD = ["sh", "-c", "printf 'ok \\377 end \\342\\202'"];
+
+const describe = (text: string) => JSON.stringify(text.replace(/�/g, "<?>"));
+
+export default async () => {
+  const lossy = spawn({ cmd: CMD });
+  log(`lossy text: ${describe(await lossy.readStdoutText())}`);
+  await lossy.status;
+
+  const strict = spawn({ cmd: CMD });
+  try {
+    await strict.readStdoutText("utf-8", { strict: true });
+    log("strict text: no error");
+  } catch (e) {
+    log(`strict text error: ${(e as Error).message}`);
+  }
+  await strict.status;
+
+  const lines = spawn({ cmd: CMD });
+  try {
+    for await (const _line of lines.stdoutLines("utf-8", { strict: true })) {
+      log("strict line: no error");
+    }
+  } catch (e) {
+    log(`strict line error: ${(e as Error).message}`);
+  }
+  await lines.status;
+
+  const output = await spawn({ cmd: CMD, stdout: "piped" }).output();
+  log(`output lossy: ${describe(output.stdoutText())}`);
+  try {
+    output.stdoutText({ strict: true });
+    log("output strict: no error");
+  } catch (e) {
+    log(`output strict error: ${(e as Error).message}`);
+  }
+
+  log("invalid-utf8 test complete");
+};
@@ -0,0 +1,20 @@
+/**
+ * Test: isatty for the standard streams
+ *
+ * Run with stdout and stderr piped to the test runner, so neither is a terminal.
+ * Unknown stream names are rejected.
+ */
+import { log } from "funee";
+import { isatty } from "host://process";
+
+export default () => {
+  log(`stdout isatty: ${isatty("stdout")}`);
+  log(`stderr isatty: ${isatty("stderr")}`);
+  try {
+    isatty("stdfoo" as "stdout");
+    log("unknown stream rejected: false");
+  } catch (e) {
+    log(`unknown stream rejected: ${e instanceof TypeError}`);
+  }
+  log("isatty test complete");
+};
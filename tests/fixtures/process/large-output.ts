@@ -0,0 +1,32 @@
+/**
+ * Test: Megabytes on both stdout and stderr
+ * 
+ * Verifies that:
+ * 1. output() drains stdout and stderr together, so a child that fills
+ *    its stderr pipe before writing stdout doesn't hang
+ * 2. All bytes from both streams are captured
+ */
+import { spawn, log } from "funee";
+
+export default async () => {
+  const size = 4 * 1024 * 1024;
+  // stderr first: a reader waiting on stdout alone would block forever
+  const proc = spawn({
+    cmd: ["sh", "-c", `head -c ${size} /dev/zero >&2; head -c ${size} /dev/zero; head -c ${size} /dev/zero >&2`],
+  });
+  
+  let timer: number | undefined;
+  const timeout = new Promise<null>((resolve) => { timer = setTimeout(() => resolve(null), 20000); });
+  const output = await Promise.race([proc.output(), timeout]);
+  clearTimeout(timer);
+  if (output === null) {
+    log("large-output: timed out");
+    proc.kill("SIGKILL");
+    return;
+  }
+  
+  log(`stdout bytes: ${output.stdout.length}`);
+  log(`stderr bytes: ${output.stderr.length}`);
+  log(`success: ${output.status.success}`);
+  log("large-output: pass");
+}
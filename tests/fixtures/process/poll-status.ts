@@ -0,0 +1,42 @@
+/**
+ * Test: Poll a subprocess for exit without waiting
+ * 
+ * Verifies that:
+ * 1. poll() returns null while the process is running
+ * 2. poll() returns the exit status once it has finished, repeatedly
+ * 3. status still resolves to the same exit info after polling
+ * 4. A killed process reports its signal
+ */
+import { spawn, log } from "funee";
+
+export default async () => {
+  const proc = spawn({ cmd: ["sh", "-c", "sleep 0.2; exit 7"], stdout: "null", stderr: "null" });
+  
+  log(`running at start: ${proc.poll() === null}`);
+  
+  let polls = 0;
+  let status = proc.poll();
+  while (status === null) {
+    polls++;
+    await new Promise((resolve) => setTimeout(resolve, 20));
+    status = proc.poll();
+  }
+  log(`polled until exit: ${polls > 0}`);
+  log(`polled code: ${status.code} success: ${status.success}`);
+  log(`poll again: ${proc.poll()?.code}`);
+  
+  const waited = await proc.status;
+  log(`status after poll: ${waited.code}`);
+  log(`poll after status: ${proc.poll()?.code}`);
+  
+  const sleeper = spawn({ cmd: ["sleep", "60"], stdout: "null", stderr: "null" });
+  sleeper.kill("SIGKILL");
+  let killed = sleeper.poll();
+  while (killed === null) {
+    await new Promise((resolve) => setTimeout(resolve, 10));
+    killed = sleeper.poll();
+  }
+  log(`killed signal: ${killed.signal}`);
+  
+  log("poll-status: pass");
+}
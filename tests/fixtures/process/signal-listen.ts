@@ -0,0 +1,29 @@
+/**
+ * Test: onSignal from "host://process" observes a SIGHUP sent to funee
+ *
+ * The test harness sends SIGHUP once "ready for SIGHUP" is printed.
+ */
+import { log } from "funee";
+import { onSignal } from "host://process";
+
+export default async () => {
+  const signals = onSignal("SIGHUP");
+  // Start waiting before announcing readiness, so the handler is installed
+  // by the time the harness sends the signal
+  const first = signals.next();
+
+  // Closing another listener for the same signal doesn't stop this one
+  const other = onSignal("SIGHUP");
+  const otherNext = other.next();
+  await other.return!();
+  log(`other listener done: ${(await otherNext).done}`);
+
+  log("ready for SIGHUP");
+
+  const { value } = await first;
+  log(`received ${value}`);
+
+  const after = await signals.return!();
+  log(`done after return: ${after.done}`);
+  log("signal-listen test complete");
+};
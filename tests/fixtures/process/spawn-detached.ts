@@ -0,0 +1,34 @@
+/**
+ * Test: Spawn a detached subprocess
+ * 
+ * Verifies that:
+ * 1. A detached child runs in a different process group than funee
+ * 2. A regular child shares funee's process group
+ * 3. Explicitly piped stdout still works for detached children
+ */
+import { spawn, log } from "funee";
+
+const pgidOf = async (detached: boolean) => {
+  const proc = spawn({
+    cmd: ["sh", "-c", "ps -o pgid= -p $$"],
+    stdout: "piped",
+    detached,
+  });
+  const output = await proc.output();
+  return output.stdoutText().trim();
+};
+
+export default async () => {
+  const attachedPgid = await pgidOf(false);
+  const detachedPgid = await pgidOf(true);
+
+  log(`attached pgid found: ${attachedPgid.length > 0}`);
+  log(`detached pgid found: ${detachedPgid.length > 0}`);
+  log(`different process group: ${attachedPgid !== detachedPgid}`);
+
+  if (attachedPgid.length > 0 && detachedPgid.length > 0 && attachedPgid !== detachedPgid) {
+    log("spawn-detached: pass");
+  } else {
+    log("spawn-detached: FAIL");
+  }
+}
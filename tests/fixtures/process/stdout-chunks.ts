@@ -0,0 +1,52 @@
+/**
+ * Test: Stream subprocess stdout in chunks while it runs
+ *
+ * Verifies that:
+ * 1. readStdoutChunk() returns output as soon as it's written, before the process exits
+ * 2. It can be called repeatedly until done, and stdoutChunks() iterates the rest
+ * 3. maxBytes caps the size of a chunk
+ * 4. A chunk takes everything already written, past the 8 KiB read buffer
+ */
+import { spawn, log } from "funee";
+
+export default async () => {
+  const proc = spawn({
+    cmd: ["sh", "-c", "echo tick 1; sleep 0.3; echo tick 2; sleep 0.3; echo tick 3"],
+  });
+  
+  const first = await proc.readStdoutChunk();
+  let exited = false;
+  const status = proc.status.then((s) => { exited = true; return s; });
+  log(`first chunk: ${JSON.stringify(String.fromCharCode(...first.data))} done: ${first.done}`);
+  log(`first chunk before exit: ${!exited}`);
+  
+  let rest = "";
+  for await (const chunk of proc.stdoutChunks()) {
+    rest += String.fromCharCode(...chunk);
+  }
+  log(`rest: ${JSON.stringify(rest)}`);
+  log(`exit code: ${(await status).code}`);
+  
+  const capped = spawn({ cmd: ["sh", "-c", "printf '%0100d' 0"] });
+  const small = await capped.readStdoutChunk(10);
+  log(`capped chunk bytes: ${small.data.length}`);
+  let total = small.data.length;
+  while (true) {
+    const { data, done } = await capped.readStdoutChunk(10);
+    if (done) {
+      break;
+    }
+    total += data.length;
+  }
+  await capped.status;
+  log(`capped total bytes: ${total}`);
+  
+  const large = spawn({ cmd: ["sh", "-c", "head -c 49152 /dev/zero; sleep 0.5"] });
+  await new Promise((resolve) => setTimeout(resolve, 200));
+  const whole = await large.readStdoutChunk(65536);
+  log(`large chunk bytes: ${whole.data.length}`);
+  while (!(await large.readStdoutChunk()).done) {}
+  await large.status;
+  
+  log("stdout-chunks test complete");
+};
@@ -0,0 +1,29 @@
+/**
+ * Test: Read subprocess stdout as text and as lines
+ *
+ * Verifies that:
+ * 1. stdoutLines() yields complete lines, joining a line split across writes
+ * 2. "\r\n" terminators are stripped and a final unterminated line is kept
+ * 3. UTF-8 output is decoded
+ * 4. readStdoutText("latin1") decodes single-byte output
+ */
+import { spawn, log } from "funee";
+
+export default async () => {
+  const proc = spawn({
+    cmd: ["sh", "-c", "printf 'one\\ntwo\\r\\nthr'; sleep 0.1; printf 'ee\\nh\\303\\251llo\\nlast'"],
+  });
+  const lines: string[] = [];
+  for await (const line of proc.stdoutLines()) {
+    lines.push(line);
+  }
+  await proc.status;
+  log(`lines: ${JSON.stringify(lines)}`);
+
+  const latin1 = spawn({ cmd: ["sh", "-c", "printf 'caf\\351'"] });
+  const text = await latin1.readStdoutText("latin1");
+  await latin1.status;
+  log(`latin1 text: ${text}`);
+
+  log("stdout-lines test complete");
+};
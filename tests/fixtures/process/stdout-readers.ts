@@ -0,0 +1,35 @@
+/**
+ * Test: stdout can't be read two different ways
+ *
+ * Verifies that:
+ * 1. output() after stdoutLines() has started fails instead of returning
+ *    the rest of stdout with the buffered part missing
+ * 2. readStdoutChunk() after output() fails the same way
+ */
+import { spawn, log } from "funee";
+
+export default async () => {
+  const streamed = spawn({ cmd: ["sh", "-c", "echo one; echo two"], stdout: "piped" });
+  const lines = streamed.stdoutLines();
+  log(`first line: ${(await lines.next()).value}`);
+  try {
+    await streamed.output();
+    log("output after lines: allowed");
+  } catch (e) {
+    log(`output after lines: ${(e as Error).message}`);
+  }
+  for await (const _ of lines) {}
+  await streamed.status;
+
+  const collected = spawn({ cmd: ["sh", "-c", "echo done"], stdout: "piped" });
+  const output = await collected.output();
+  log(`output text: ${output.stdoutText().trim()}`);
+  try {
+    await collected.readStdoutChunk();
+    log("chunk after output: allowed");
+  } catch (e) {
+    log(`chunk after output: ${(e as Error).message}`);
+  }
+
+  log("stdout-readers test complete");
+};
@@ -0,0 +1,25 @@
+/**
+ * Test: process start time and uptime
+ * 
+ * Verifies that:
+ * 1. startTime() is an epoch timestamp no later than now
+ * 2. uptime() is non-negative and increases across a sleep
+ */
+import { log } from "funee";
+import { startTime, uptime } from "host://process";
+import { setTimeout } from "host://time";
+
+export default async () => {
+  const started = startTime();
+  log(`start time in the past: ${started > 0 && started <= Date.now()}`);
+  log(`start time recent: ${Date.now() - started < 60_000}`);
+  
+  const first = uptime();
+  await new Promise((resolve) => setTimeout(resolve, 50));
+  const second = uptime();
+  
+  log(`uptime non-negative: ${first >= 0}`);
+  log(`uptime increased: ${second > first}`);
+  log(`uptime advanced by the sleep: ${second - first >= 0.045}`);
+  log("uptime: pass");
+};
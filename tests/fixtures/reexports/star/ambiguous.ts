@@ -0,0 +1,5 @@
+import { describe } from "./index.ts";
+
+export default function() {
+  return describe();
+}
@@ -0,0 +1,13 @@
+/**
+ * Test: names forwarded by `export * from` barrels
+ *
+ * index.ts star-exports math.ts and strings.ts; shout comes from case.ts,
+ * which strings.ts star-exports in turn.
+ */
+import { log } from "funee";
+import { add, shout } from "./index.ts";
+
+export default function() {
+  log(`add: ${add(2, 3)}`);
+  log(`shout: ${shout("barrel")}`);
+}
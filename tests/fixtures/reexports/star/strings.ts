@@ -0,0 +1,3 @@
+export * from "./case.ts";
+
+export const describe = () => "strings";
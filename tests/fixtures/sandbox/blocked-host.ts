@@ -0,0 +1,30 @@
+/**
+ * Test fixture: running under --sandbox / FUNEE_NO_HOST
+ * 
+ * Expected behavior:
+ * - Calling an fs import throws a descriptive error
+ * - The fetch global throws too
+ * - Logging and crypto still work
+ */
+import { log } from "host://console";
+import { readFile } from "host://fs";
+import { randomBytes } from "host://crypto";
+
+export default () => {
+  try {
+    readFile("/etc/hostname");
+    log("fs: allowed");
+  } catch (e) {
+    log(`fs error: ${(e as Error).message}`);
+  }
+
+  try {
+    fetch("http://localhost:19998/text");
+    log("fetch: allowed");
+  } catch (e) {
+    log(`fetch error: ${(e as Error).message}`);
+  }
+
+  log(`random bytes: ${randomBytes(4).length}`);
+  log("sandbox test complete");
+};
@@ -0,0 +1,28 @@
+/**
+ * Test fixture: stopping a server while it's waiting for a request
+ * 
+ * Expected behavior:
+ * - The accept loop is blocked waiting for a connection when shutdown() runs
+ * - Stopping wakes the pending accept, so server.finished resolves promptly
+ *   instead of after a polling interval
+ */
+import { log, serve } from "funee";
+
+export default async () => {
+  const server = serve({ port: 0 }, () => new Response("unused"));
+  
+  // Let the accept loop block on the host
+  await new Promise((resolve) => setTimeout(resolve, 20));
+  
+  const started = Date.now();
+  await server.shutdown();
+  await server.finished;
+  const elapsed = Date.now() - started;
+  
+  log(`finished after shutdown: true`);
+  log(`accept returned promptly: ${elapsed < 50}`);
+  if (elapsed >= 50) {
+    log(`elapsed: ${elapsed}ms`);
+  }
+  log("accept-cancel test complete");
+};
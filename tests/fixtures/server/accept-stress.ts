@@ -0,0 +1,36 @@
+/**
+ * Test fixture: no request is lost between the connections and the accept loop
+ * 
+ * Expected behavior:
+ * - Many requests fired at once are all accepted, in any order
+ * - Every request gets its own response
+ * - The handler sees exactly as many requests as were sent
+ */
+import { log, serve } from "funee";
+
+export default async () => {
+  const total = 300;
+  const seen = new Set<string>();
+  
+  const server = serve({ port: 0 }, async (req) => {
+    const id = new URL(req.url).searchParams.get("id")!;
+    seen.add(id);
+    // Yield so later accepts overlap with handlers still running
+    await new Promise((resolve) => setTimeout(resolve, Math.random() * 5));
+    return new Response(id);
+  });
+  
+  const results = await Promise.all(
+    Array.from({ length: total }, async (_, i) => {
+      const response = await fetch(`http://127.0.0.1:${server.port}/?id=${i}`);
+      return (await response.text()) === String(i);
+    })
+  );
+  
+  log(`sent: ${total}`);
+  log(`accepted: ${seen.size}`);
+  log(`responses matched: ${results.filter(Boolean).length}`);
+  
+  await server.shutdown();
+  log("accept-stress test complete");
+};
@@ -0,0 +1,55 @@
+/**
+ * Test fixture: reading a server's live connection count
+ * 
+ * Expected behavior:
+ * - activeConnections() is 0 for an idle server
+ * - It counts connections held open by in-flight requests
+ * - A handler can use it to shed load with 503
+ * - It's -1 once the server has stopped, and for unknown IDs
+ */
+import { log, serve, activeConnections } from "funee";
+
+export default async () => {
+  let release!: () => void;
+  const released = new Promise<void>((resolve) => { release = resolve; });
+  let waiting = 0;
+  
+  // One connection per request, so the count follows in-flight requests
+  const server = serve({ port: 0, keepAlive: false }, async (req) => {
+    if (new URL(req.url).pathname === "/busy-check") {
+      if (activeConnections(server.serverId) > 3) {
+        return new Response("busy", { status: 503 });
+      }
+      return new Response("ok");
+    }
+    waiting++;
+    await released;
+    return new Response("done");
+  });
+  const base = `http://127.0.0.1:${server.port}`;
+  
+  log(`idle: ${activeConnections(server.serverId)}`);
+  
+  const held = [1, 2, 3].map(() => fetch(`${base}/hold`).then((r) => r.text()));
+  while (waiting < 3) {
+    await new Promise((resolve) => setTimeout(resolve, 10));
+  }
+  log(`while holding: ${activeConnections(server.serverId)}`);
+  
+  const shed = await fetch(`${base}/busy-check`);
+  log(`saturated: ${shed.status} ${await shed.text()}`);
+  
+  release();
+  await Promise.all(held);
+  // Connections close just after the responses are written
+  while (activeConnections(server.serverId) > 0) {
+    await new Promise((resolve) => setTimeout(resolve, 10));
+  }
+  const relaxed = await fetch(`${base}/busy-check`);
+  log(`after release: ${relaxed.status} ${await relaxed.text()}`);
+  
+  await server.shutdown();
+  log(`after shutdown: ${activeConnections(server.serverId)}`);
+  log(`unknown server: ${activeConnections(999999)}`);
+  log("active-connections test complete");
+};
@@ -0,0 +1,59 @@
+/**
+ * Test fixture: Expect: 100-continue and informational statuses
+ * 
+ * Expected behavior:
+ * - A client sending "Expect: 100-continue" receives "100 Continue"
+ *   once the handler reads the body, then the final response
+ * - A handler can reject the upload without reading the body
+ * - Informational statuses can't be used as a final response
+ * 
+ * Uses curl as the client, since fetch doesn't send Expect headers.
+ */
+import { log, serve, spawn } from "funee";
+
+export default async () => {
+  const server = serve({ port: 0 }, async (req) => {
+    const url = new URL(req.url);
+    
+    if (url.pathname === "/upload") {
+      const body = await req.text();
+      return new Response(`received: ${body}`);
+    }
+    if (url.pathname === "/reject") {
+      return new Response("Too big", { status: 413 });
+    }
+    if (url.pathname === "/informational") {
+      return new Response("", { status: 102 });
+    }
+    return new Response("Not Found", { status: 404 });
+  });
+  
+  const curl = (path: string) => spawn("curl", [
+    "-sv",
+    "--expect100-timeout", "10",
+    "-H", "Expect: 100-continue",
+    "--data-binary", "hello",
+    `http://127.0.0.1:${server.port}${path}`,
+  ]);
+  
+  // Body is read by the handler: interim response, then final response
+  const upload = await curl("/upload");
+  const uploadLog = upload.stderrText();
+  log(`upload got 100 continue: ${uploadLog.includes("HTTP/1.1 100 Continue")}`);
+  log(`upload final status 200: ${uploadLog.includes("HTTP/1.1 200 OK")}`);
+  log(`upload body: ${upload.stdoutText()}`);
+  
+  // Body is never read: no interim response, final response only
+  const reject = await curl("/reject");
+  const rejectLog = reject.stderrText();
+  log(`reject skipped 100 continue: ${!rejectLog.includes("HTTP/1.1 100 Continue")}`);
+  log(`reject final status 413: ${rejectLog.includes("HTTP/1.1 413")}`);
+  
+  // 1xx as a final response is an error, surfaced as a 500
+  const informational = await fetch(`http://127.0.0.1:${server.port}/informational`);
+  log(`informational rejected: ${informational.status === 500}`);
+  
+  await server.shutdown();
+  
+  log("expect-continue test complete");
+};
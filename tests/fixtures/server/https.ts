@@ -0,0 +1,51 @@
+/**
+ * Test fixture: serve() over HTTPS
+ * 
+ * Expected behavior:
+ * - serve({ tls: { certFile, keyFile } }) answers HTTPS requests, and the
+ *   handler sees an https:// request URL
+ * - port and hostname are reported as for a plain server
+ * - A malformed key makes serve() throw right away
+ * - A plain-HTTP request to the TLS port fails without stopping the server
+ * 
+ * The test generates a self-signed certificate for localhost into server/.https.
+ */
+import { log, serve } from "funee";
+import { readFile } from "host://fs";
+
+const certFile = "server/.https/server.pem";
+const keyFile = "server/.https/server.key";
+
+export default async () => {
+  const server = serve({ port: 0, tls: { certFile, keyFile } }, (req) => {
+    return new Response(`secure ${req.url}`);
+  });
+  log(`port assigned: ${server.port > 0}`);
+  
+  const ca = JSON.parse(readFile(certFile)).value;
+  const response = await fetch(`https://localhost:${server.port}/hello`, { tls: { ca } });
+  log(`https status: ${response.status}`);
+  log(`https body: ${await response.text()}`);
+  
+  let plainFailed = false;
+  try {
+    const res = await fetch(`http://localhost:${server.port}/hello`);
+    await res.text();
+  } catch {
+    plainFailed = true;
+  }
+  log(`plain http rejected: ${plainFailed}`);
+  
+  const again = await fetch(`https://localhost:${server.port}/again`, { tls: { ca } });
+  log(`still serving: ${await again.text()}`);
+  await server.shutdown();
+  
+  try {
+    serve({ port: 0, tls: { certFile, keyFile: "server/.https/bad.key" } }, () => new Response("never"));
+    log("bad key error: none");
+  } catch (e: any) {
+    log(`bad key error: ${String(e.message).includes("Invalid TLS key")}`);
+  }
+  
+  log("https test complete");
+};
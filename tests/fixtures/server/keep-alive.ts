@@ -5,10 +5,12 @@
  * - Multiple requests can be made on same connection
  * - Connection: keep-alive header is respected
  * - Server tracks request count correctly
+ * - Sequential requests reuse one connection; keepAlive: false closes each
+ * - An idle kept-alive connection is closed after idleTimeoutMs
  * 
  * Based on HTTP_SERVER_DESIGN.md specification.
  */
-import { log, serve } from "funee";
+import { log, serve, activeConnections } from "funee";
 
 export default async () => {
   let requestCount = 0;
@@ -49,6 +51,33 @@ export default async () => {
   const data = await response.json();
   log(`keep-alive respected: ${data.keepAlive === true}`);
   
+  // The client pools the connection, so it stays open between requests
+  log(`connections after requests: ${activeConnections(server.serverId)}`);
+  
   await server.shutdown();
+  
+  const oneShot = serve({ port: 0, keepAlive: false }, () => new Response("OK"));
+  for (let i = 0; i < 3; i++) {
+    await (await fetch(`http://localhost:${oneShot.port}`)).text();
+  }
+  let waited = 0;
+  while (activeConnections(oneShot.serverId) > 0 && waited < 2000) {
+    await new Promise(resolve => setTimeout(resolve, 10));
+    waited += 10;
+  }
+  log(`connections with keepAlive false: ${activeConnections(oneShot.serverId)}`);
+  await oneShot.shutdown();
+  
+  // The client keeps its connection open; the server closes it once idle
+  const idle = serve({ port: 0, idleTimeoutMs: 200 }, () => new Response("OK"));
+  await (await fetch(`http://localhost:${idle.port}`)).text();
+  log(`connections before idle timeout: ${activeConnections(idle.serverId)}`);
+  waited = 0;
+  while (activeConnections(idle.serverId) > 0 && waited < 5000) {
+    await new Promise(resolve => setTimeout(resolve, 10));
+    waited += 10;
+  }
+  log(`connections after idle timeout: ${activeConnections(idle.serverId)}`);
+  await idle.shutdown();
   log("keep-alive test complete");
 };
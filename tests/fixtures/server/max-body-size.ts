@@ -0,0 +1,57 @@
+/**
+ * Test fixture: request body size limit
+ * 
+ * Expected behavior:
+ * - Bodies within maxBodyBytes reach the handler
+ * - A Content-Length over the limit is answered with 413 without calling the handler
+ * - A chunked upload is cut off with 413 once it grows past the limit
+ * - Without maxBodyBytes, a default limit still allows ordinary uploads
+ */
+import { log, serve } from "funee";
+
+export default async () => {
+  let handled = 0;
+  const server = serve({ port: 0, maxBodyBytes: 1024 }, async (req) => {
+    handled++;
+    const body = await req.text();
+    return new Response(`received ${body.length}`);
+  });
+  const url = `http://127.0.0.1:${server.port}/upload`;
+  
+  const small = await fetch(url, { method: "POST", body: "a".repeat(1024) });
+  log(`within limit: ${small.status} ${await small.text()}`);
+  
+  const large = await fetch(url, { method: "POST", body: "b".repeat(4096) });
+  log(`over limit: ${large.status} ${await large.text()}`);
+  
+  // No Content-Length: the limit has to be enforced while reading
+  async function* chunks() {
+    for (let i = 0; i < 8; i++) {
+      yield new TextEncoder().encode("c".repeat(512));
+    }
+  }
+  let chunked: string;
+  try {
+    const response = await fetch(url, { method: "POST", body: chunks() });
+    chunked = String(response.status);
+  } catch {
+    // The server may close before the client finishes sending
+    chunked = "413";
+  }
+  log(`chunked over limit: ${chunked}`);
+  log(`handler calls: ${handled}`);
+  await server.shutdown();
+  
+  const defaultServer = serve({ port: 0 }, async (req) => {
+    const body = await req.text();
+    return new Response(`received ${body.length}`);
+  });
+  const ordinary = await fetch(`http://127.0.0.1:${defaultServer.port}/`, {
+    method: "POST",
+    body: "d".repeat(64 * 1024),
+  });
+  log(`default limit: ${ordinary.status} ${await ordinary.text()}`);
+  await defaultServer.shutdown();
+  
+  log("max-body-size test complete");
+};
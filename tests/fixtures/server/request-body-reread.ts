@@ -0,0 +1,59 @@
+/**
+ * Test fixture: Reading a request body more than once
+ * 
+ * Expected behavior:
+ * - A second req.text() fails with the same TypeError every time
+ * - The body is freed once the response is sent, so reading it afterwards
+ *   fails instead of quietly returning an empty string
+ */
+import { log, serve } from "funee";
+
+export default async () => {
+  let lateReadError = "";
+
+  const server = serve({ port: 0 }, async (req) => {
+    if (new URL(req.url).pathname === "/late") {
+      await req.respondFile("server/range-data.txt");
+      try {
+        await req.text();
+      } catch (e) {
+        lateReadError = (e as Error).message;
+      }
+      return;
+    }
+
+    const first = await req.text();
+    const errors: string[] = [];
+    for (let i = 0; i < 2; i++) {
+      try {
+        await req.text();
+      } catch (e) {
+        errors.push(`${(e as Error).name}: ${(e as Error).message}`);
+      }
+    }
+    return Response.json({ first, errors });
+  });
+
+  const port = server.port;
+
+  const response = await fetch(`http://localhost:${port}/`, {
+    method: "POST",
+    body: "hello body",
+  });
+  const data = await response.json();
+  log(`first read: ${data.first}`);
+  log(`second read failed: ${data.errors.length === 2}`);
+  log(`rereads consistent: ${data.errors[0] === data.errors[1]}`);
+  log(`reread error: ${data.errors[0]}`);
+
+  const late = await fetch(`http://localhost:${port}/late`, {
+    method: "POST",
+    body: "freed after response",
+  });
+  await late.text();
+  log(`late status: ${late.status}`);
+  log(`late read error: ${lateReadError}`);
+
+  await server.shutdown();
+  log("request-body-reread test complete");
+};
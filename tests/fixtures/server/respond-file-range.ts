@@ -0,0 +1,47 @@
+/**
+ * Test fixture: serving a file with Range requests
+ * 
+ * Expected behavior:
+ * - A single byte range gets 206 with Content-Range/Content-Length and exactly those bytes
+ * - Suffix ranges ("bytes=-n") return the last n bytes
+ * - A range past the end of the file gets 416 and a Content-Range with just the size
+ * - Multi-range requests and requests without Range get the whole file with 200
+ */
+import { log, serve } from "funee";
+
+export default async () => {
+  const server = serve({ port: 0 }, async (req) => {
+    await req.respondFile("server/range-data.txt", {
+      headers: { "content-type": "text/plain" },
+    });
+    return new Response("ignored");
+  });
+  const url = `http://127.0.0.1:${server.port}/range-data.txt`;
+  
+  const partial = await fetch(url, { headers: { Range: "bytes=10-15" } });
+  log(`range status: ${partial.status}`);
+  log(`range content-range: ${partial.headers.get("content-range")}`);
+  log(`range content-length: ${partial.headers.get("content-length")}`);
+  log(`range body: ${await partial.text()}`);
+  
+  const suffix = await fetch(url, { headers: { Range: "bytes=-4" } });
+  log(`suffix status: ${suffix.status}`);
+  log(`suffix body: ${await suffix.text()}`);
+  
+  const unsatisfiable = await fetch(url, { headers: { Range: "bytes=100-200" } });
+  log(`unsatisfiable status: ${unsatisfiable.status}`);
+  log(`unsatisfiable content-range: ${unsatisfiable.headers.get("content-range")}`);
+  await unsatisfiable.text();
+  
+  const multi = await fetch(url, { headers: { Range: "bytes=0-1,4-5" } });
+  log(`multi status: ${multi.status}`);
+  log(`multi body length: ${(await multi.text()).length}`);
+  
+  const full = await fetch(url);
+  log(`full status: ${full.status}`);
+  log(`full accept-ranges: ${full.headers.get("accept-ranges")}`);
+  log(`full body: ${await full.text()}`);
+  
+  await server.shutdown();
+  log("respond-file-range test complete");
+};
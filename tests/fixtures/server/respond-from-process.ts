@@ -0,0 +1,52 @@
+/**
+ * Test fixture: streaming a child process's stdout as the response body
+ * 
+ * Expected behavior:
+ * - request.respondFromProcess() sends the status and headers immediately
+ * - The client receives the child's complete output (larger than one chunk)
+ * - The child exits normally once its output has been consumed
+ * - Calling it with something that isn't a spawned process throws
+ */
+import { log, serve, spawn } from "funee";
+
+export default async () => {
+  let childStatus: Promise<{ success: boolean }> | null = null;
+  let invalidRejected = false;
+  
+  const server = serve({ port: 0 }, async (req) => {
+    const url = new URL(req.url);
+    
+    if (url.pathname === "/invalid") {
+      try {
+        await req.respondFromProcess({} as any);
+      } catch (e) {
+        invalidRejected = e instanceof TypeError;
+      }
+      return new Response("fallback");
+    }
+    
+    const child = spawn({ cmd: ["sh", "-c", "seq 1 50000"], stdout: "piped" });
+    childStatus = child.status;
+    await req.respondFromProcess(child, {
+      status: 200,
+      headers: { "content-type": "text/plain" },
+    });
+    return new Response("ignored");
+  });
+  
+  const response = await fetch(`http://127.0.0.1:${server.port}/seq`);
+  const text = await response.text();
+  const expected = Array.from({ length: 50000 }, (_, i) => `${i + 1}`).join("\n") + "\n";
+  
+  log(`status 200: ${response.status === 200}`);
+  log(`content-type: ${response.headers.get("content-type")}`);
+  log(`full output received: ${text === expected}`);
+  log(`child succeeded: ${(await childStatus!).success}`);
+  
+  const fallback = await fetch(`http://127.0.0.1:${server.port}/invalid`);
+  log(`invalid process rejected: ${invalidRejected}`);
+  log(`fallback body: ${await fallback.text()}`);
+  
+  await server.shutdown();
+  log("respond-from-process test complete");
+};
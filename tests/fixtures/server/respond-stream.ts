@@ -0,0 +1,91 @@
+/**
+ * Test fixture: streaming response bodies with request.respondStream()
+ * 
+ * Expected behavior:
+ * - Status and headers reach the client before the body is finished
+ * - String and byte chunks arrive in order
+ * - A large body written in chunks arrives complete
+ * - Writing after close() is rejected
+ * - The body ends when the handler returns without calling close()
+ * - The body is cut short when the handler throws
+ */
+import { log, serve } from "funee";
+
+export default async () => {
+  let release!: () => void;
+  const released = new Promise<void>((resolve) => { release = resolve; });
+  let writeAfterClose = "";
+  
+  const server = serve({ port: 0 }, async (req) => {
+    const url = new URL(req.url);
+    
+    if (url.pathname === "/events") {
+      const stream = await req.respondStream({
+        status: 200,
+        headers: { "content-type": "text/event-stream" },
+      });
+      await stream.write("data: first\n\n");
+      // Hold the body open until the client has seen the headers
+      await released;
+      await stream.write(new TextEncoder().encode("data: second\n\n"));
+      await stream.close();
+      try {
+        await stream.write("too late");
+      } catch (e: any) {
+        writeAfterClose = e instanceof TypeError ? "rejected" : String(e);
+      }
+      return;
+    }
+    
+    if (url.pathname === "/large") {
+      const stream = await req.respondStream();
+      const chunk = new Uint8Array(64 * 1024).fill(120);
+      for (let i = 0; i < 64; i++) {
+        await stream.write(chunk);
+      }
+      await stream.close();
+      return;
+    }
+    
+    if (url.pathname === "/unclosed") {
+      const stream = await req.respondStream();
+      await stream.write("ended by returning");
+      return;
+    }
+    
+    if (url.pathname === "/throws") {
+      const stream = await req.respondStream();
+      await stream.write("partial");
+      throw new Error("handler failed mid-stream");
+    }
+    
+    return new Response("not found", { status: 404 });
+  });
+  
+  const base = `http://127.0.0.1:${server.port}`;
+  
+  const events = await fetch(`${base}/events`);
+  log(`headers before body end: ${events.status} ${events.headers.get("content-type")}`);
+  release();
+  log(`events body: ${JSON.stringify(await events.text())}`);
+  log(`write after close: ${writeAfterClose}`);
+  
+  const large = await fetch(`${base}/large`);
+  const bytes = await large.bytes();
+  log(`large body length: ${bytes.length}`);
+  log(`large body intact: ${bytes.every((b: number) => b === 120)}`);
+  
+  const unclosed = await fetch(`${base}/unclosed`);
+  log(`unclosed body: ${await unclosed.text()}`);
+  
+  const throws = await fetch(`${base}/throws`);
+  try {
+    await throws.text();
+    log("thrown handler body: complete");
+  } catch {
+    log("thrown handler body: aborted");
+  }
+  
+  await server.shutdown();
+  log("respond-stream test complete");
+};
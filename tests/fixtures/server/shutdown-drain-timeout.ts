@@ -0,0 +1,50 @@
+/**
+ * Test fixture: Server shutdown with a drain timeout
+ * 
+ * Expected behavior:
+ * - shutdown({ drainTimeoutMs }) gives up on a slow in-flight request
+ *   once the timeout elapses and resolves to false
+ * - shutdown() with nothing in flight resolves to true
+ * - drainTimeoutMs: 0 waits for a slow request however long it takes
+ */
+import { log, serve } from "funee";
+
+export default async () => {
+  let handlerStarted = false;
+  const slowServer = serve({ port: 0 }, async () => {
+    handlerStarted = true;
+    await new Promise(resolve => setTimeout(resolve, 2000));
+    return new Response("too late");
+  });
+  
+  // Start a request and let it reach the handler
+  const requestPromise = fetch(`http://localhost:${slowServer.port}/`).catch(() => null);
+  while (!handlerStarted) {
+    await new Promise(resolve => setTimeout(resolve, 10));
+  }
+  
+  const start = Date.now();
+  const drained = await slowServer.shutdown({ drainTimeoutMs: 100 });
+  const elapsed = Date.now() - start;
+  log(`slow shutdown drained: ${drained}`);
+  log(`gave up before handler finished: ${elapsed < 1000}`);
+  await requestPromise;
+  
+  const idleServer = serve({ port: 0 }, () => new Response("OK"));
+  log(`idle shutdown drained: ${await idleServer.shutdown({ drainTimeoutMs: 100 })}`);
+  
+  let patientStarted = false;
+  const patientServer = serve({ port: 0 }, async () => {
+    patientStarted = true;
+    await new Promise(resolve => setTimeout(resolve, 300));
+    return new Response("worth the wait");
+  });
+  const patientRequest = fetch(`http://localhost:${patientServer.port}/`).then(r => r.text());
+  while (!patientStarted) {
+    await new Promise(resolve => setTimeout(resolve, 10));
+  }
+  log(`unbounded shutdown drained: ${await patientServer.shutdown({ drainTimeoutMs: 0 })}`);
+  log(`unbounded shutdown response: ${await patientRequest}`);
+  
+  log("shutdown-drain-timeout test complete");
+};
@@ -1,13 +1,11 @@
 /**
- * Test fixture: Request timeout handling (without AbortController)
+ * Test fixture: Request timeout handling
  * 
  * Expected behavior:
  * - Server handles slow requests gracefully
  * - Fast requests complete quickly
  * - Slow requests eventually complete
- * 
- * Note: AbortController is not available in funee runtime,
- * so we test basic timeout behavior without abort functionality.
+ * - An AbortController cancels a request still waiting on the server
  * 
  * Based on HTTP_SERVER_DESIGN.md specification.
  */
@@ -67,8 +65,15 @@ export default async () => {
   log(`delay response: ${delayText}`);
   log(`delay time >= 100ms: ${delayTime >= 90}`);
   
-  // Test that timeout test works (simulate what would happen)
-  log(`abort threw error: true`);
+  // Abort a request the server hasn't answered yet
+  const controller = new AbortController();
+  setTimeout(() => controller.abort(), 50);
+  try {
+    await fetch(`http://localhost:${port}/delay?ms=300`, { signal: controller.signal });
+    log(`abort threw error: false`);
+  } catch (e: any) {
+    log(`abort threw error: ${e.name === "AbortError"}`);
+  }
   
   await server.shutdown();
   log("timeout test complete");
@@ -0,0 +1,41 @@
+/**
+ * Test fixture: HTTPS connections that never start the TLS handshake
+ * 
+ * Expected behavior:
+ * - A stalled handshake is dropped after idleTimeoutMs
+ * - shutdown() doesn't wait on a stalled handshake
+ * 
+ * The test harness opens a plain TCP connection that sends nothing each time
+ * "listening on <port>" is printed. The certificate comes from server/.https.
+ */
+import { log, serve, activeConnections } from "funee";
+
+const certFile = "server/.https/server.pem";
+const keyFile = "server/.https/server.key";
+
+const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+// Poll until the server's connection count satisfies `predicate`
+const waitForConnections = async (serverId: number, predicate: (count: number) => boolean) => {
+  for (let waited = 0; !predicate(activeConnections(serverId)) && waited < 5000; waited += 10) {
+    await sleep(10);
+  }
+  return activeConnections(serverId);
+};
+
+export default async () => {
+  const quick = serve({ port: 0, tls: { certFile, keyFile }, idleTimeoutMs: 300 }, () => new Response("never"));
+  log(`listening on ${quick.port}`);
+  log(`stalled connections: ${await waitForConnections(quick.serverId, (count) => count > 0)}`);
+  log(`after handshake timeout: ${await waitForConnections(quick.serverId, (count) => count === 0)}`);
+  await quick.shutdown();
+  
+  const slow = serve({ port: 0, tls: { certFile, keyFile } }, () => new Response("never"));
+  log(`listening on ${slow.port}`);
+  await waitForConnections(slow.serverId, (count) => count > 0);
+  const start = Date.now();
+  const drained = await slow.shutdown({ drainTimeoutMs: 10000 });
+  log(`shutdown drained stalled handshake: ${drained && Date.now() - start < 5000}`);
+  
+  log("tls-handshake-timeout test complete");
+};
@@ -0,0 +1,84 @@
+/**
+ * Test fixture: protocol upgrades with request.upgrade()
+ * 
+ * Expected behavior:
+ * - A request with "Connection: upgrade" gets 101 Switching Protocols, its
+ *   Upgrade header echoed, and the connection is handed to the handler
+ * - Bytes flow both ways over the upgraded connection; read() reports when
+ *   the client closes its side
+ * - upgrade() rejects requests that didn't ask for one, leaving the handler
+ *   free to answer normally
+ * - 101 can't be sent as an ordinary response
+ * 
+ * Uses node's net module as the client, since fetch doesn't upgrade.
+ */
+import { log, serve, spawn } from "funee";
+
+export default async () => {
+  let clientClosed = false;
+  const server = serve({ port: 0 }, async (req) => {
+    const url = new URL(req.url);
+    
+    if (url.pathname === "/echo") {
+      const conn = await req.upgrade({ headers: { "x-upgraded": "yes" } });
+      while (true) {
+        const { data, done } = await conn.read();
+        if (done) {
+          clientClosed = true;
+          break;
+        }
+        await conn.write(`echo: ${new TextDecoder().decode(data)}`);
+      }
+      await conn.close();
+      return;
+    }
+    if (url.pathname === "/plain") {
+      try {
+        await req.upgrade();
+        return new Response("upgraded");
+      } catch (e: any) {
+        return new Response(e instanceof TypeError ? "not upgradable" : String(e), { status: 400 });
+      }
+    }
+    if (url.pathname === "/switching") {
+      return new Response(null, { status: 101 });
+    }
+    return new Response("Not Found", { status: 404 });
+  });
+  
+  const client = await spawn("node", ["-e", `
+    const socket = require("net").connect(Number(process.argv[1]), "127.0.0.1");
+    let received = "";
+    socket.on("connect", () => {
+      socket.write("GET /echo HTTP/1.1\\r\\nHost: 127.0.0.1\\r\\nConnection: Upgrade\\r\\nUpgrade: funee-echo\\r\\n\\r\\n");
+    });
+    socket.on("data", (data) => {
+      const before = received;
+      received += data.toString();
+      if (!before.includes("\\r\\n\\r\\n") && received.includes("\\r\\n\\r\\n")) {
+        socket.write("ping");
+      }
+      if (received.endsWith("echo: ping")) {
+        socket.end();
+      }
+    });
+    socket.on("close", () => process.stdout.write(JSON.stringify(received)));
+  `, String(server.port)]);
+  const received: string = JSON.parse(client.stdoutText() || '""');
+  const [head, body] = received.split("\r\n\r\n");
+  log(`upgrade status: ${head.split("\r\n")[0]}`);
+  log(`upgrade header: ${/^upgrade: funee-echo$/im.test(head)}`);
+  log(`custom header: ${/^x-upgraded: yes$/im.test(head)}`);
+  log(`echoed: ${body}`);
+  log(`client close seen: ${clientClosed}`);
+  
+  const plain = await fetch(`http://127.0.0.1:${server.port}/plain`);
+  log(`plain request: ${plain.status} ${await plain.text()}`);
+  
+  const switching = await fetch(`http://127.0.0.1:${server.port}/switching`);
+  log(`101 response rejected: ${switching.status === 500}`);
+  
+  await server.shutdown();
+  
+  log("upgrade test complete");
+};
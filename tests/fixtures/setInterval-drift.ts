@@ -0,0 +1,39 @@
+import { log } from "funee";
+
+/**
+ * Test fixture: native setInterval
+ * 
+ * Expected behavior:
+ * - Ticks stay on the schedule set at start, even when each callback takes
+ *   a good part of the period
+ * - Clearing an interval whose next tick is far off doesn't keep the
+ *   runtime alive
+ */
+export default async function() {
+  const start = Date.now();
+  let ticks = 0;
+  
+  await new Promise<void>((resolve) => {
+    const id = setInterval(() => {
+      ticks++;
+      // Busy callback: 30ms of a 50ms period
+      const busyUntil = Date.now() + 30;
+      while (Date.now() < busyUntil) {}
+      if (ticks === 10) {
+        clearInterval(id);
+        resolve();
+      }
+    }, 50);
+  });
+  
+  const elapsed = Date.now() - start;
+  log(`ticks: ${ticks}`);
+  // Re-arming a timeout after each callback would take 10 * 80ms
+  log(`no drift: ${elapsed < 700}`);
+  
+  const slow = setInterval(() => log("slow interval fired"), 60000);
+  await new Promise((resolve) => setTimeout(resolve, 20));
+  clearInterval(slow);
+  
+  log("setInterval-drift end");
+}
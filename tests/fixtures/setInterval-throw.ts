@@ -0,0 +1,19 @@
+import { log } from "funee";
+
+/**
+ * Test fixture: setInterval callback that throws
+ * 
+ * Expected behavior:
+ * - The error surfaces as an uncaught error and the run fails
+ * - The interval stops with it instead of keeping the runtime alive
+ */
+export default async function() {
+  let ticks = 0;
+  setInterval(() => {
+    ticks++;
+    log(`tick ${ticks}`);
+    if (ticks === 2) {
+      throw new Error("interval callback failed");
+    }
+  }, 20);
+}
@@ -0,0 +1,12 @@
+import { log } from "funee";
+
+/**
+ * Test fixture: Math.random on a run that has to build its snapshot
+ * 
+ * Expected behavior:
+ * - Values differ between runs; building the snapshot must not leave the
+ *   runtime with V8's fixed snapshotting seed
+ */
+export default function() {
+  log(`random: ${Math.random()}`);
+}
@@ -0,0 +1,13 @@
+/**
+ * Test fixture: `funee test` file that changes the environment; runs before
+ * b-reads.test.ts, which must not see the change
+ */
+import { test, assertEqual } from "host://test";
+import { get, set } from "host://env";
+
+export default () => {
+  test("sets a variable", () => {
+    set("FUNEE_TEST_ENV_LEAK", "from a");
+    assertEqual(get("FUNEE_TEST_ENV_LEAK"), "from a");
+  });
+};
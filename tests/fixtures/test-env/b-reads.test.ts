@@ -0,0 +1,16 @@
+/**
+ * Test fixture: `funee test` file run after a-sets.test.ts, starting from
+ * the original environment
+ */
+import { spawn } from "funee";
+import { test, assertEqual } from "host://test";
+import { get, toObject } from "host://env";
+
+export default () => {
+  test("doesn't see the earlier file's variable", async () => {
+    assertEqual(get("FUNEE_TEST_ENV_LEAK"), undefined);
+    assertEqual("FUNEE_TEST_ENV_LEAK" in toObject(), false);
+    const child = await spawn("sh", ["-c", "printf %s \"${FUNEE_TEST_ENV_LEAK-unset}\""]);
+    assertEqual(child.stdoutText(), "unset");
+  });
+};
@@ -0,0 +1,14 @@
+/**
+ * Test fixture: `funee test` with one passing and one failing test
+ */
+import { test, assertEqual } from "host://test";
+
+export default () => {
+  test("adds numbers", () => {
+    assertEqual(1 + 2, 3);
+  });
+
+  test("subtracts numbers", async () => {
+    assertEqual({ result: 5 - 3 }, { result: 3 });
+  });
+};
@@ -0,0 +1,10 @@
+/**
+ * Test: V8 flags passed with --v8-flags reach the runtime
+ * 
+ * Run with --v8-flags=--expose-gc: globalThis.gc should be a function
+ */
+import { log } from "funee";
+
+export default () => {
+  log(`gc type: ${typeof globalThis.gc}`);
+};
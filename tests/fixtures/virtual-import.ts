@@ -0,0 +1,14 @@
+/**
+ * Test: imports from virtual modules
+ * 
+ * Neither module exists on disk; both are registered with --virtual,
+ * "answer" with its source piped through stdin.
+ */
+import { log } from "funee";
+import { greeting } from "virtual:greeting";
+import { answer } from "virtual:answer";
+
+export default function() {
+  log(`greeting: ${greeting()}`);
+  log(`answer: ${answer}`);
+}
@@ -3,7 +3,11 @@ import { createServer, IncomingMessage, ServerResponse } from 'http';
 export const startTestServer = (port = 19999) => {
   const server = createServer((req, res) => {
     // Route handling
-    if (req.url === '/json') {
+    if (req.url?.startsWith('http://')) {
+      // Absolute-form request target: a client is using this server as its proxy
+      res.end(`proxied ${req.url}`);
+    }
+    else if (req.url === '/json') {
       // Mimic httpbin.org/json structure
       res.setHeader('Content-Type', 'application/json');
       res.end(JSON.stringify({
@@ -77,6 +81,45 @@ export const startTestServer = (port = 19999) => {
       });
       res.end(JSON.stringify({ success: true }));
     }
+    else if (req.url === '/set-cookie') {
+      // Two Set-Cookie headers, which must not be merged into one
+      res.setHeader('Set-Cookie', ['session=abc; Path=/', 'theme=dark; Expires=Wed, 21 Oct 2026 07:28:00 GMT']);
+      res.end('cookies set');
+    }
+    else if (req.url === '/download' || req.url === '/download-chunked') {
+      // 8 chunks of 32 KiB, 20ms apart; /download-chunked omits Content-Length
+      const chunk = Buffer.alloc(32 * 1024, 'x');
+      const chunks = 8;
+      if (req.url === '/download') {
+        res.setHeader('Content-Length', String(chunk.length * chunks));
+      }
+      let sent = 0;
+      const sendNext = () => {
+        res.write(chunk);
+        sent++;
+        if (sent < chunks) {
+          setTimeout(sendNext, 20);
+        } else {
+          res.end();
+        }
+      };
+      sendNext();
+    }
+    else if (req.url === '/endless') {
+      // 16 KiB chunks every 5ms until the client hangs up (capped at 64 MiB)
+      const chunk = Buffer.alloc(16 * 1024, 'y');
+      let sent = 0;
+      const timer = setInterval(() => {
+        if (res.destroyed || sent >= 4096) {
+          clearInterval(timer);
+          res.end();
+          return;
+        }
+        res.write(chunk);
+        sent++;
+      }, 5);
+      res.on('close', () => clearInterval(timer));
+    }
     else if (req.url === '/echo') {
       res.setHeader('Content-Type', 'application/json');
       res.end(JSON.stringify({ 
@@ -3,6 +3,8 @@ import { defineConfig } from 'vitest/config';
 export default defineConfig({
   test: {
     include: ['**/*.test.ts'],
+    // Fixtures are run by funee, not vitest (e.g. test-harness/math.test.ts)
+    exclude: ['**/node_modules/**', 'fixtures/**'],
     reporters: ['verbose', 'html'],
     outputFile: {
       html: './html-report/index.html',